@@ -19,7 +19,7 @@
 //!
 //! ```console
 //! $ printf '{\n  "a": 1,\n  "b":\n}\n' | cargo run --example parse_error
-//! error: unexpected char while parsing Object at byte position 19
+//! error: unexpected char while parsing Object at byte position 19 (expected a JSON value)
 //!    4 | }
 //!      | ^ here
 //! ```
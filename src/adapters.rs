@@ -0,0 +1,800 @@
+use alloc::{borrow::Cow, borrow::ToOwned, boxed::Box, string::String, vec::Vec};
+use core::{fmt::Display, str::FromStr};
+
+use crate::{
+    DisplayJson, JsonErrorCode, JsonFormatter, JsonParseError, JsonValueKind, RawJson,
+    RawJsonMember, RawJsonValue,
+};
+
+/// A wrapper that formats and parses a value as a JSON string using its [`Display`] and [`FromStr`] implementations.
+///
+/// This is useful for types that have a canonical textual representation, such as
+/// identifiers, version numbers, or UUIDs, without having to write a dedicated newtype.
+/// If [`FromStr::from_str()`] fails, the error is reported as
+/// [`JsonParseError::InvalidValue`] pointing at the string value.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use std::net::Ipv4Addr;
+/// use nojson::{Json, JsonStringOf};
+///
+/// let addr = JsonStringOf(Ipv4Addr::new(127, 0, 0, 1));
+/// assert_eq!(Json(addr).to_string(), r#""127.0.0.1""#);
+///
+/// let parsed: Json<JsonStringOf<u8>> = r#""42""#.parse()?;
+/// assert_eq!(parsed.0.0, 42);
+///
+/// // Non-string values and unparsable strings are rejected.
+/// assert!("42".parse::<Json<JsonStringOf<u8>>>().is_err());
+/// assert!(r#""256""#.parse::<Json<JsonStringOf<u8>>>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JsonStringOf<T>(#[allow(missing_docs)] pub T);
+
+impl<T: Display> DisplayJson for JsonStringOf<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        f.string(&self.0)
+    }
+}
+
+impl<'text, 'raw, T> TryFrom<RawJsonValue<'text, 'raw>> for JsonStringOf<T>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn Send + Sync + core::error::Error>>,
+{
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        value
+            .to_unquoted_string_str()?
+            .parse()
+            .map(Self)
+            .map_err(|e| value.invalid(e).with_code(JsonErrorCode::InvalidString))
+    }
+}
+
+/// A wrapper that formats a number as a JSON string and parses it from either a JSON number or a JSON string.
+///
+/// This is mainly useful for 64-bit integers consumed by JavaScript,
+/// where numbers above 2^53 cannot be represented exactly.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{Json, NumberAsString};
+///
+/// let id = NumberAsString(9007199254740993u64);
+/// assert_eq!(Json(id).to_string(), r#""9007199254740993""#);
+///
+/// let parsed: Json<[NumberAsString<u64>; 2]> = r#"["9007199254740993", 42]"#.parse()?;
+/// assert_eq!(parsed.0, [id, NumberAsString(42)]);
+///
+/// assert!("true".parse::<Json<NumberAsString<u64>>>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NumberAsString<T>(#[allow(missing_docs)] pub T);
+
+impl<T: Display> DisplayJson for NumberAsString<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        f.string(&self.0)
+    }
+}
+
+impl<'text, 'raw, T> TryFrom<RawJsonValue<'text, 'raw>> for NumberAsString<T>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn Send + Sync + core::error::Error>>,
+{
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        let text = match value.kind() {
+            JsonValueKind::String => value.to_unquoted_string_str()?,
+            _ => Cow::Borrowed(value.as_number_str()?),
+        };
+        let code = if value.kind().is_string() {
+            JsonErrorCode::InvalidString
+        } else {
+            JsonErrorCode::InvalidNumber
+        };
+        text.parse()
+            .map(Self)
+            .map_err(|e| value.invalid(e).with_code(code))
+    }
+}
+
+/// A wrapper that formats and parses bytes as a standard Base64 (RFC 4648) JSON string.
+///
+/// The standard alphabet (`A-Z`, `a-z`, `0-9`, `+`, `/`) with `=` padding is used for both
+/// formatting and parsing. Parsing always produces a `Base64<Vec<u8>>`, and errors for
+/// invalid characters or padding point at the offending character in the JSON text.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{Base64, Json};
+///
+/// assert_eq!(Json(Base64(b"hello")).to_string(), r#""aGVsbG8=""#);
+///
+/// let parsed: Json<Base64<Vec<u8>>> = r#""aGVsbG8=""#.parse()?;
+/// assert_eq!(parsed.0.0, b"hello");
+///
+/// let e = r#""aGV*bG8=""#.parse::<Json<Base64<Vec<u8>>>>().expect_err("invalid character");
+/// assert_eq!(e.position(), 4);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Base64<T>(#[allow(missing_docs)] pub T);
+
+impl<T: AsRef<[u8]>> Display for Base64<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        const ALPHABET: &[u8; 64] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+        for chunk in self.0.as_ref().chunks(3) {
+            let n = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |n, (i, b)| n | u32::from(*b) << (16 - 8 * i));
+            let mut buf = [b'='; 4];
+            for (i, c) in buf.iter_mut().enumerate().take(chunk.len() + 1) {
+                *c = ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3f];
+            }
+            f.write_str(core::str::from_utf8(&buf).expect("infallible"))?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: AsRef<[u8]>> DisplayJson for Base64<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        f.string(self)
+    }
+}
+
+impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for Base64<Vec<u8>> {
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        let text = value.to_unquoted_string_str()?;
+        decode_base64(text.as_bytes())
+            .map(Self)
+            .map_err(|(offset, reason)| {
+                value
+                    .invalid_at(offset, reason)
+                    .with_code(JsonErrorCode::InvalidString)
+            })
+    }
+}
+
+fn decode_base64(text: &[u8]) -> Result<Vec<u8>, (usize, &'static str)> {
+    if !text.len().is_multiple_of(4) {
+        return Err((text.len(), "base64 text length must be a multiple of 4"));
+    }
+
+    let mut bytes = Vec::with_capacity(text.len() / 4 * 3);
+    for (chunk_index, chunk) in text.chunks(4).enumerate() {
+        let is_last = (chunk_index + 1) * 4 == text.len();
+        let offset = chunk_index * 4;
+        let mut n = 0;
+        let mut len = 4;
+        for (i, &c) in chunk.iter().enumerate() {
+            let v = match c {
+                b'A'..=b'Z' => c - b'A',
+                b'a'..=b'z' => c - b'a' + 26,
+                b'0'..=b'9' => c - b'0' + 52,
+                b'+' => 62,
+                b'/' => 63,
+                b'=' if is_last && i >= 2 && (i == 3 || chunk[3] == b'=') => {
+                    len = len.min(i);
+                    continue;
+                }
+                b'=' => return Err((offset + i, "unexpected base64 padding")),
+                _ => return Err((offset + i, "invalid base64 character")),
+            };
+            if len < 4 {
+                return Err((offset + i, "unexpected base64 padding"));
+            }
+            n |= u32::from(v) << (18 - 6 * i);
+        }
+        bytes.extend_from_slice(&n.to_be_bytes()[1..len]);
+    }
+    Ok(bytes)
+}
+
+/// A wrapper that formats and parses bytes as a lowercase hexadecimal JSON string.
+///
+/// Both lowercase and uppercase digits are accepted when parsing.
+/// Parsing always produces a `HexBytes<Vec<u8>>`, and errors for invalid digits
+/// point at the offending character in the JSON text.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{HexBytes, Json};
+///
+/// assert_eq!(Json(HexBytes([0xde, 0xad, 0xbe, 0xef])).to_string(), r#""deadbeef""#);
+///
+/// let parsed: Json<HexBytes<Vec<u8>>> = r#""DEADbeef""#.parse()?;
+/// assert_eq!(parsed.0.0, [0xde, 0xad, 0xbe, 0xef]);
+///
+/// let e = r#""deadbxef""#.parse::<Json<HexBytes<Vec<u8>>>>().expect_err("invalid digit");
+/// assert_eq!(e.position(), 6);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HexBytes<T>(#[allow(missing_docs)] pub T);
+
+impl<T: AsRef<[u8]>> Display for HexBytes<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for b in self.0.as_ref() {
+            write!(f, "{b:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: AsRef<[u8]>> DisplayJson for HexBytes<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        f.string(self)
+    }
+}
+
+impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for HexBytes<Vec<u8>> {
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        let text = value.to_unquoted_string_str()?;
+        let digits = text.as_bytes();
+        if !digits.len().is_multiple_of(2) {
+            return Err(value
+                .invalid_at(digits.len(), "hex text must have an even length")
+                .with_code(JsonErrorCode::InvalidString));
+        }
+
+        let digit = |i: usize| {
+            char::from(digits[i])
+                .to_digit(16)
+                .map(|d| d as u8)
+                .ok_or_else(|| {
+                    value
+                        .invalid_at(i, "invalid hex digit")
+                        .with_code(JsonErrorCode::InvalidString)
+                })
+        };
+        (0..digits.len())
+            .step_by(2)
+            .map(|i| Ok(digit(i)? << 4 | digit(i + 1)?))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// A wrapper that parses either a single value or an array of values into a [`Vec`].
+///
+/// When formatting, a vector with exactly one element is written as a bare value,
+/// and any other vector is written as an array.
+/// If you always want an array on output, format the inner `Vec` directly instead.
+///
+/// Note that a JSON array is always interpreted as "many" values,
+/// so this adapter is not suitable for element types that are themselves encoded as arrays.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{Json, OneOrMany};
+///
+/// let one: Json<OneOrMany<String>> = r#""a""#.parse()?;
+/// assert_eq!(one.0.0, ["a"]);
+/// assert_eq!(one.to_string(), r#""a""#);
+///
+/// let many: Json<OneOrMany<String>> = r#"["a", "b"]"#.parse()?;
+/// assert_eq!(many.0.0, ["a", "b"]);
+/// assert_eq!(many.to_string(), r#"["a","b"]"#);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OneOrMany<T>(#[allow(missing_docs)] pub Vec<T>);
+
+impl<T: DisplayJson> DisplayJson for OneOrMany<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        match self.0.as_slice() {
+            [value] => value.fmt(f),
+            values => values.fmt(f),
+        }
+    }
+}
+
+impl<'text, 'raw, T> TryFrom<RawJsonValue<'text, 'raw>> for OneOrMany<T>
+where
+    T: TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError>,
+{
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        if value.kind().is_array() {
+            value.try_into().map(Self)
+        } else {
+            T::try_from(value).map(|v| Self(alloc::vec![v]))
+        }
+    }
+}
+
+/// A wrapper that formats the items of an iterator as a JSON array without collecting them first.
+///
+/// The iterator is cloned each time the value is formatted, so formatting the same value twice
+/// iterates the source twice.
+/// Borrowing iterators such as `slice.iter().map(..)` are cheap to clone; for other sources,
+/// wrap a type whose [`IntoIterator`] implementation re-creates the items on each call.
+///
+/// This adapter only supports formatting.
+///
+/// # Examples
+///
+/// ```
+/// use nojson::{Json, LazyArray};
+///
+/// let squares = LazyArray((1..=3).map(|i| i * i));
+/// assert_eq!(Json(&squares).to_string(), "[1,4,9]");
+///
+/// let output = nojson::json(|f| f.object(|f| f.member("squares", &squares)));
+/// assert_eq!(output.to_string(), r#"{"squares":[1,4,9]}"#);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LazyArray<I>(#[allow(missing_docs)] pub I);
+
+impl<I> DisplayJson for LazyArray<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: DisplayJson,
+{
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        f.array(|f| f.elements(self.0.clone()))
+    }
+}
+
+/// A wrapper that accepts common loosely typed encodings of numbers and booleans when parsing.
+///
+/// If the value cannot be converted into `T` as-is, the following coercions are tried:
+/// - A string containing a number or boolean (e.g., `"42"`, `" 1.5 "`, `"true"`) is parsed as that value
+/// - The numbers `0` and `1` (or the strings `"0"` and `"1"`) are interpreted as `false` and `true`
+///
+/// If none of them succeed, the error from the original conversion is returned.
+/// Formatting is the same as `T`.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{Json, Lenient};
+///
+/// let value: Json<Lenient<u32>> = r#""42""#.parse()?;
+/// assert_eq!(value.0.0, 42);
+/// assert_eq!(value.to_string(), "42");
+///
+/// let value: Json<[Lenient<bool>; 2]> = r#"[1, "false"]"#.parse()?;
+/// assert_eq!(value.0, [Lenient(true), Lenient(false)]);
+///
+/// assert!(r#""forty-two""#.parse::<Json<Lenient<u32>>>().is_err());
+/// assert!("2".parse::<Json<Lenient<bool>>>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lenient<T>(#[allow(missing_docs)] pub T);
+
+impl<T: DisplayJson> DisplayJson for Lenient<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'text, 'raw, T> TryFrom<RawJsonValue<'text, 'raw>> for Lenient<T>
+where
+    T: for<'a, 'b> TryFrom<RawJsonValue<'a, 'b>, Error = JsonParseError>,
+{
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        let error = match T::try_from(value) {
+            Ok(v) => return Ok(Self(v)),
+            Err(e) => e,
+        };
+
+        let text = match value.kind() {
+            JsonValueKind::String => value.to_unquoted_string_str()?,
+            _ => Cow::Borrowed(value.as_raw_str()),
+        };
+        let text = text.trim();
+        let candidates = [
+            value.kind().is_string().then_some(text),
+            match text {
+                "0" => Some("false"),
+                "1" => Some("true"),
+                _ => None,
+            },
+        ];
+        for candidate in candidates.into_iter().flatten() {
+            let Ok(raw) = RawJson::parse(candidate) else {
+                continue;
+            };
+            let kind = raw.value().kind();
+            if (kind.is_number() || kind.is_bool())
+                && let Ok(v) = T::try_from(raw.value())
+            {
+                return Ok(Self(v));
+            }
+        }
+        Err(error)
+    }
+}
+
+/// A wrapper that parses an empty JSON string (or `null`) as [`None`] and formats [`None`] as `null`.
+///
+/// If the `TRIM` parameter is `true`, strings consisting only of whitespace are also treated as empty.
+/// Any other value is converted into `T` and wrapped in [`Some`].
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{EmptyStringAsNone, Json};
+///
+/// let value: Json<[EmptyStringAsNone<u32>; 3]> = r#"["", null, 3]"#.parse()?;
+/// assert_eq!(value.0.map(|v| v.0), [None, None, Some(3)]);
+/// assert_eq!(value.to_string(), "[null,null,3]");
+///
+/// let value: Json<EmptyStringAsNone<String, true>> = r#"" ""#.parse()?;
+/// assert_eq!(value.0.0, None);
+/// let value: Json<EmptyStringAsNone<String>> = r#"" ""#.parse()?;
+/// assert_eq!(value.0.0.as_deref(), Some(" "));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EmptyStringAsNone<T, const TRIM: bool = false>(#[allow(missing_docs)] pub Option<T>);
+
+impl<T: DisplayJson, const TRIM: bool> DisplayJson for EmptyStringAsNone<T, TRIM> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'text, 'raw, T, const TRIM: bool> TryFrom<RawJsonValue<'text, 'raw>>
+    for EmptyStringAsNone<T, TRIM>
+where
+    T: TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError>,
+{
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        if value.kind().is_string() {
+            let s = value.to_unquoted_string_str()?;
+            if s.is_empty() || (TRIM && s.trim().is_empty()) {
+                return Ok(Self(None));
+            }
+        }
+        Option::<T>::try_from(value).map(Self)
+    }
+}
+
+/// A wrapper that parses JSON `null` (or a missing object member) as [`Default::default()`].
+///
+/// Formatting is the same as `T`, so default values are written as-is rather than as `null`.
+///
+/// Missing members are handled by the [`TryFrom<RawJsonMember>`](RawJsonMember) implementation.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{Json, NullAsDefault, RawJson};
+///
+/// let json = RawJson::parse(r#"{"retries": null, "tags": ["a"]}"#)?;
+/// let retries: NullAsDefault<u32> = json.value().to_member("retries")?.required()?.try_into()?;
+/// let tags: NullAsDefault<Vec<String>> = json.value().to_member("tags")?.try_into()?;
+/// let timeout: NullAsDefault<u32> = json.value().to_member("timeout")?.try_into()?;
+/// assert_eq!(retries.0, 0);
+/// assert_eq!(tags.0, ["a"]);
+/// assert_eq!(timeout.0, 0);
+///
+/// assert_eq!(Json(retries).to_string(), "0");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NullAsDefault<T>(#[allow(missing_docs)] pub T);
+
+impl<T: DisplayJson> DisplayJson for NullAsDefault<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'text, 'raw, T> TryFrom<RawJsonValue<'text, 'raw>> for NullAsDefault<T>
+where
+    T: TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError> + Default,
+{
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        Option::<T>::try_from(value).map(|v| Self(v.unwrap_or_default()))
+    }
+}
+
+impl<'text, 'raw, 'a, T> TryFrom<RawJsonMember<'text, 'raw, 'a>> for NullAsDefault<T>
+where
+    T: TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError> + Default,
+{
+    type Error = JsonParseError;
+
+    fn try_from(member: RawJsonMember<'text, 'raw, 'a>) -> Result<Self, Self::Error> {
+        member
+            .optional()
+            .map_or_else(|| Ok(Self::default()), Self::try_from)
+    }
+}
+
+/// A wrapper that formats a sensitive value as the string `"***"` instead of its content.
+///
+/// This is useful for logging request or configuration structs containing secrets
+/// (e.g., passwords or API tokens) without maintaining separate "loggable" types.
+/// Parsing is the same as `T`, so the same struct can be used for both input and audit output.
+/// The [`Debug`](core::fmt::Debug) implementation doesn't reveal the content either.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{Json, Redacted};
+///
+/// let password: Json<Redacted<String>> = r#""hunter2""#.parse()?;
+/// assert_eq!(password.0.0, "hunter2");
+/// assert_eq!(password.to_string(), r#""***""#);
+/// assert_eq!(format!("{:?}", password.0), r#"Redacted("***")"#);
+///
+/// let output = nojson::json(|f| {
+///     f.object(|f| {
+///         f.member("user", "alice")?;
+///         f.member("token", Redacted(12345))
+///     })
+/// });
+/// assert_eq!(output.to_string(), r#"{"user":"alice","token":"***"}"#);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Redacted<T>(#[allow(missing_docs)] pub T);
+
+const REDACTED_PLACEHOLDER: &str = "***";
+
+impl<T> core::fmt::Debug for Redacted<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Redacted")
+            .field(&REDACTED_PLACEHOLDER)
+            .finish()
+    }
+}
+
+impl<T> DisplayJson for Redacted<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        f.string(REDACTED_PLACEHOLDER)
+    }
+}
+
+impl<'text, 'raw, T> TryFrom<RawJsonValue<'text, 'raw>> for Redacted<T>
+where
+    T: TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError>,
+{
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        T::try_from(value).map(Self)
+    }
+}
+
+/// A value that is either a JSON string or a JSON number.
+///
+/// This is useful for fields whose representation differs between producers
+/// (e.g., `"code": 404` and `"code": "404"`).
+/// The value is kept as text, and [`StringOrNumber::parse()`] converts it into a typed value
+/// regardless of the original representation.
+///
+/// The text of [`StringOrNumber::Number`] is written to the output as-is,
+/// so it must be a valid JSON number.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{Json, StringOrNumber};
+///
+/// let codes: Json<[StringOrNumber; 2]> = r#"[404, "404"]"#.parse()?;
+/// assert_eq!(codes.0[0], StringOrNumber::Number("404".to_owned()));
+/// assert_eq!(codes.0[1], StringOrNumber::String("404".to_owned()));
+/// assert_eq!(codes.0[0].parse::<u16>(), Ok(404));
+/// assert_eq!(codes.0[1].parse::<u16>(), Ok(404));
+///
+/// // The original representation is preserved.
+/// assert_eq!(codes.to_string(), r#"[404,"404"]"#);
+///
+/// assert!("null".parse::<Json<StringOrNumber>>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StringOrNumber {
+    /// A JSON string (unescaped content).
+    String(String),
+
+    /// A JSON number (raw text).
+    Number(String),
+}
+
+impl StringOrNumber {
+    /// Returns the text of the value (unescaped string content or raw number text).
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::String(s) | Self::Number(s) => s,
+        }
+    }
+
+    /// Returns `true` if the value was a JSON string.
+    pub fn is_string(&self) -> bool {
+        matches!(self, Self::String(_))
+    }
+
+    /// Returns `true` if the value was a JSON number.
+    pub fn is_number(&self) -> bool {
+        matches!(self, Self::Number(_))
+    }
+
+    /// Parses the text of the value into `T` using [`FromStr`].
+    pub fn parse<T: FromStr>(&self) -> Result<T, T::Err> {
+        self.as_str().parse()
+    }
+}
+
+impl Display for StringOrNumber {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl DisplayJson for StringOrNumber {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        match self {
+            Self::String(s) => f.string(s),
+            Self::Number(s) => f.literal(JsonValueKind::Float, s),
+        }
+    }
+}
+
+impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for StringOrNumber {
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        if value.kind().is_string() {
+            value.try_into().map(Self::String)
+        } else {
+            value.as_number_str().map(|s| Self::Number(s.to_owned()))
+        }
+    }
+}
+
+/// A wrapper that expands `${NAME}` environment variable placeholders in a JSON string when parsing.
+///
+/// After expansion, the string is converted into `T` using [`FromStr`], as in [`JsonStringOf`].
+/// Use `$${` to write a literal `${`.
+/// If a variable is not defined (or is not valid Unicode), the error points at its placeholder
+/// in the JSON text (unless the string contains escape sequences, in which case it points at
+/// the start of the string).
+///
+/// When formatting, the inner value is written as a JSON string as-is (placeholders are not restored).
+///
+/// This type is available only when the `std` feature is enabled.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{EnvExpanded, Json, RawJson};
+///
+/// // Cargo sets `CARGO_PKG_NAME` when running tests.
+/// let name: Json<EnvExpanded> = r#""${CARGO_PKG_NAME}.log""#.parse()?;
+/// assert_eq!(name.0.0, "nojson.log");
+///
+/// let literal: Json<EnvExpanded> = r#""$${CARGO_PKG_NAME}""#.parse()?;
+/// assert_eq!(literal.0.0, "${CARGO_PKG_NAME}");
+///
+/// let text = r#"{"port": "${NOJSON_DOC_UNDEFINED}"}"#;
+/// let json = RawJson::parse(text)?;
+/// let e = EnvExpanded::<u16>::try_from(json.value().expect_member("port")).unwrap_err();
+/// assert_eq!(e.position(), text.find("${").unwrap());
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EnvExpanded<T = String>(#[allow(missing_docs)] pub T);
+
+#[cfg(feature = "std")]
+impl<T: Display> DisplayJson for EnvExpanded<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        f.string(&self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'text, 'raw, T> TryFrom<RawJsonValue<'text, 'raw>> for EnvExpanded<T>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn Send + Sync + core::error::Error>>,
+{
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        let s = value.to_unquoted_string_str()?;
+        let expanded =
+            expand_env_vars(&s, |name| std::env::var(name).ok()).map_err(|(offset, reason)| {
+                value
+                    .invalid_at(offset, reason)
+                    .with_code(JsonErrorCode::InvalidString)
+            })?;
+        expanded
+            .parse()
+            .map(Self)
+            .map_err(|e| value.invalid(e).with_code(JsonErrorCode::InvalidString))
+    }
+}
+
+// Expands `${NAME}` placeholders using `lookup`.
+// On failure, returns the byte offset of the offending placeholder and the reason.
+#[cfg(feature = "std")]
+fn expand_env_vars<F>(s: &str, lookup: F) -> Result<String, (usize, String)>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut expanded = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('$') {
+        let offset = s.len() - rest.len() + i;
+        expanded.push_str(&rest[..i]);
+        rest = &rest[i..];
+        if let Some(after) = rest.strip_prefix("$${") {
+            expanded.push_str("${");
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("${") {
+            let Some(end) = after.find('}') else {
+                return Err((offset, String::from("unterminated placeholder")));
+            };
+            let name = &after[..end];
+            let Some(var) = lookup(name) else {
+                return Err((
+                    offset,
+                    alloc::format!("undefined environment variable: {name}"),
+                ));
+            };
+            expanded.push_str(&var);
+            rest = &after[end + 1..];
+        } else {
+            expanded.push('$');
+            rest = &rest[1..];
+        }
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
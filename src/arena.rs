@@ -0,0 +1,104 @@
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+
+use crate::{
+    JsonParseError, RawJsonValue,
+    parse::{JsonParser, Plain},
+    raw::JsonValueIndexEntry,
+};
+
+/// A container that parses many small JSON documents into shared buffers.
+///
+/// Parsing each message into its own [`RawJsonOwned`](crate::RawJsonOwned) allocates a text buffer
+/// and an index per message. [`JsonArena`] instead appends the texts and indices of all parsed
+/// documents to a single text buffer and a single index buffer, which are reused after
+/// [`JsonArena::clear()`]. Once the buffers have grown large enough, parsing does not allocate.
+///
+/// Documents are identified by the sequential IDs returned by [`JsonArena::parse()`].
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// let mut arena = nojson::JsonArena::new();
+/// for batch in [["1", "[2, 3]"], ["4", r#"{"five": 5}"#]] {
+///     for message in batch {
+///         arena.parse(message)?;
+///     }
+///     assert_eq!(arena.len(), 2);
+///     let first: u32 = arena.get(0).expect("exists").try_into()?;
+///     assert!(first == 1 || first == 4);
+///
+///     // Reuses the buffers for the next batch.
+///     arena.clear();
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct JsonArena {
+    text: String,
+    values: Vec<JsonValueIndexEntry>,
+    documents: Vec<(Range<usize>, Range<usize>)>,
+}
+
+impl JsonArena {
+    /// Makes a new empty [`JsonArena`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a JSON string and stores it in this arena, returning the ID of the document.
+    ///
+    /// If the text is not valid JSON, an error is returned and the arena is left unchanged.
+    pub fn parse(&mut self, text: &str) -> Result<usize, JsonParseError> {
+        let values_start = self.values.len();
+        JsonParser::<Plain>::new(text).parse_into(&mut self.values)?;
+
+        let text_start = self.text.len();
+        self.text.push_str(text);
+        self.documents
+            .push((text_start..self.text.len(), values_start..self.values.len()));
+        Ok(self.documents.len() - 1)
+    }
+
+    /// Returns the top-level value of the document with the given ID.
+    ///
+    /// Returns `None` if there is no such document.
+    pub fn get(&self, id: usize) -> Option<RawJsonValue<'_, '_>> {
+        let (text, values) = self.documents.get(id)?;
+        Some(RawJsonValue::root_of(
+            &self.text[text.clone()],
+            &self.values[values.clone()],
+        ))
+    }
+
+    /// Returns an iterator over the top-level values of all documents in ID order.
+    pub fn iter(&self) -> impl '_ + Iterator<Item = RawJsonValue<'_, '_>> {
+        (0..self.len()).map(|id| self.get(id).expect("infallible"))
+    }
+
+    /// Returns the number of documents in this arena.
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Returns `true` if this arena contains no documents.
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Removes all documents, keeping the allocated buffers for reuse.
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.values.clear();
+        self.documents.clear();
+    }
+
+    /// Returns the approximate number of heap-allocated bytes held by this arena.
+    pub fn memory_usage(&self) -> usize {
+        self.text.capacity()
+            + self.values.capacity() * core::mem::size_of::<JsonValueIndexEntry>()
+            + self.documents.capacity() * core::mem::size_of::<(Range<usize>, Range<usize>)>()
+    }
+}
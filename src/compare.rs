@@ -0,0 +1,333 @@
+use alloc::{borrow::Cow, collections::BTreeMap, format, string::String, vec::Vec};
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+use crate::{JsonParseError, JsonValueKind, RawJson, RawJsonValue, raw::push_member_path};
+
+/// Compares two JSON texts structurally.
+///
+/// The texts are considered equal if they represent the same JSON value, regardless of
+/// whitespace, member order, string escapes, or number notation
+/// (e.g., `1`, `1.0` and `1e0` are equal).
+/// Numbers are compared by their exact decimal value, so they are never rounded
+/// (e.g., `9007199254740993` and `9007199254740992` are different, and so are `1e-400` and `0`).
+/// If an object contains duplicate member names, the last one wins.
+///
+/// Use [`assert_json_eq()`] in tests to get a readable diff on mismatch.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// assert!(nojson::json_eq(r#"{"a": [1, "x"], "b": null}"#, r#"{"b":null,"a":[1.0,"x"]}"#)?);
+/// assert!(!nojson::json_eq("[1, 2]", "[2, 1]")?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn json_eq(left: &str, right: &str) -> Result<bool, JsonParseError> {
+    let left = RawJson::parse(left)?;
+    let right = RawJson::parse(right)?;
+    let mut diffs = Vec::new();
+    diff_values(
+        left.value(),
+        right.value(),
+        &mut String::from("$"),
+        &mut diffs,
+    );
+    Ok(diffs.is_empty())
+}
+
+/// Asserts that two JSON texts are structurally equal (see [`json_eq()`]).
+///
+/// # Panics
+///
+/// Panics if either text is not valid JSON, or if the texts are not equal.
+/// In the latter case, the panic message lists every difference with its path
+/// (e.g., `$.users[0].name`).
+///
+/// # Examples
+///
+/// ```
+/// nojson::assert_json_eq(r#"{"a": 1, "b": [true]}"#, r#"{"b": [true], "a": 1}"#);
+/// ```
+///
+/// ```should_panic
+/// // Panics with:
+/// //   $.a: 1 != 2
+/// //   $.c: missing in left
+/// nojson::assert_json_eq(r#"{"a": 1}"#, r#"{"a": 2, "c": 3}"#);
+/// ```
+#[track_caller]
+pub fn assert_json_eq(left: &str, right: &str) {
+    let left_json = RawJson::parse(left)
+        .unwrap_or_else(|e| panic!("left is not valid JSON: {e}\n  left: {left}"));
+    let right_json = RawJson::parse(right)
+        .unwrap_or_else(|e| panic!("right is not valid JSON: {e}\n right: {right}"));
+
+    let mut diffs = Vec::new();
+    diff_values(
+        left_json.value(),
+        right_json.value(),
+        &mut String::from("$"),
+        &mut diffs,
+    );
+    if !diffs.is_empty() {
+        panic!(
+            "JSON texts are not equal:\n  {}\n\n  left: {left}\n right: {right}",
+            diffs.join("\n  ")
+        );
+    }
+}
+
+// Returns `true` if the two values are structurally equal (see `json_eq()`).
+#[cfg(feature = "schema")]
+pub(crate) fn values_eq(left: RawJsonValue<'_, '_>, right: RawJsonValue<'_, '_>) -> bool {
+    let mut diffs = Vec::new();
+    diff_values(left, right, &mut String::new(), &mut diffs);
+    diffs.is_empty()
+}
+
+fn diff_values(
+    left: RawJsonValue<'_, '_>,
+    right: RawJsonValue<'_, '_>,
+    path: &mut String,
+    diffs: &mut Vec<String>,
+) {
+    match (left.kind(), right.kind()) {
+        (JsonValueKind::Array, JsonValueKind::Array) => {
+            let left = left.to_array().expect("infallible").collect::<Vec<_>>();
+            let right = right.to_array().expect("infallible").collect::<Vec<_>>();
+            for i in 0..left.len().max(right.len()) {
+                let len = path.len();
+                path.push_str(&format!("[{i}]"));
+                match (left.get(i), right.get(i)) {
+                    (Some(l), Some(r)) => diff_values(*l, *r, path, diffs),
+                    (Some(_), None) => diffs.push(format!("{path}: missing in right")),
+                    (None, _) => diffs.push(format!("{path}: missing in left")),
+                }
+                path.truncate(len);
+            }
+        }
+        (JsonValueKind::Object, JsonValueKind::Object) => {
+            let left = members(left);
+            let right = members(right);
+            for (name, l) in &left {
+                let len = path.len();
+                push_member_path(path, name);
+                match right.get(name) {
+                    Some(r) => diff_values(*l, *r, path, diffs),
+                    None => diffs.push(format!("{path}: missing in right")),
+                }
+                path.truncate(len);
+            }
+            for name in right.keys() {
+                if !left.contains_key(name) {
+                    let len = path.len();
+                    push_member_path(path, name);
+                    diffs.push(format!("{path}: missing in left"));
+                    path.truncate(len);
+                }
+            }
+        }
+        _ if scalar_eq(left, right) => {}
+        _ => diffs.push(format!(
+            "{path}: {} != {}",
+            left.as_raw_str(),
+            right.as_raw_str()
+        )),
+    }
+}
+
+// Hashes `value` so that values equal according to `diff_values()` produce the same hash.
+pub(crate) fn hash_value<H: Hasher>(value: RawJsonValue<'_, '_>, hasher: &mut H) {
+    match value.kind() {
+        // Extension values are indexed as `null` and compared by their text.
+        JsonValueKind::Null | JsonValueKind::Boolean => {
+            hasher.write_u8(0);
+            value.as_raw_str().hash(hasher);
+        }
+        JsonValueKind::Integer | JsonValueKind::Float => {
+            hasher.write_u8(1);
+            Decimal::new(value.as_raw_str()).hash(hasher);
+        }
+        JsonValueKind::String => {
+            hasher.write_u8(2);
+            value
+                .to_unquoted_string_str()
+                .expect("infallible")
+                .hash(hasher);
+        }
+        JsonValueKind::Array => {
+            hasher.write_u8(3);
+            let mut len = 0;
+            for element in value.to_array().expect("infallible") {
+                hash_value(element, hasher);
+                len += 1;
+            }
+            hasher.write_usize(len);
+        }
+        JsonValueKind::Object => {
+            let members = members(value);
+            hasher.write_u8(4);
+            hasher.write_usize(members.len());
+            for (name, value) in members {
+                name.hash(hasher);
+                hash_value(value, hasher);
+            }
+        }
+    }
+}
+
+// Compares two values structurally (see `RawJsonValue::structural_cmp()`).
+pub(crate) fn cmp_values(left: RawJsonValue<'_, '_>, right: RawJsonValue<'_, '_>) -> Ordering {
+    let rank = |value: RawJsonValue<'_, '_>| match value.kind() {
+        _ if value.is_extension() => 6,
+        JsonValueKind::Null => 0,
+        JsonValueKind::Boolean => 1,
+        JsonValueKind::Integer | JsonValueKind::Float => 2,
+        JsonValueKind::String => 3,
+        JsonValueKind::Array => 4,
+        JsonValueKind::Object => 5,
+    };
+    rank(left)
+        .cmp(&rank(right))
+        .then_with(|| match left.kind() {
+            JsonValueKind::Integer | JsonValueKind::Float => {
+                Decimal::new(left.as_raw_str()).cmp(&Decimal::new(right.as_raw_str()))
+            }
+            JsonValueKind::String => left
+                .to_unquoted_string_str()
+                .expect("infallible")
+                .cmp(&right.to_unquoted_string_str().expect("infallible")),
+            JsonValueKind::Array => {
+                let mut right = right.to_array().expect("infallible");
+                for l in left.to_array().expect("infallible") {
+                    let Some(r) = right.next() else {
+                        return Ordering::Greater;
+                    };
+                    let ordering = cmp_values(l, r);
+                    if ordering.is_ne() {
+                        return ordering;
+                    }
+                }
+                if right.next().is_some() {
+                    Ordering::Less
+                } else {
+                    Ordering::Equal
+                }
+            }
+            JsonValueKind::Object => {
+                let left = members(left);
+                let right = members(right);
+                left.iter()
+                    .zip(&right)
+                    .map(|((ln, lv), (rn, rv))| ln.cmp(rn).then_with(|| cmp_values(*lv, *rv)))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or_else(|| left.len().cmp(&right.len()))
+            }
+            // `null`, booleans (`false` < `true`), and extension values.
+            _ => left.as_raw_str().cmp(right.as_raw_str()),
+        })
+}
+
+// A JSON number normalized as `sign * 0.digits * 10^exponent` for exact comparison.
+#[derive(PartialEq, Eq, Hash)]
+struct Decimal {
+    negative: bool,
+    // Significant digits without leading or trailing zeros (empty for zero).
+    digits: Vec<u8>,
+    exponent: i64,
+}
+
+impl Decimal {
+    fn new(text: &str) -> Self {
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(text) => (true, text),
+            None => (false, text),
+        };
+        let (mantissa, exponent) = match text.find(['e', 'E']) {
+            Some(i) => (&text[..i], parse_exponent(&text[i + 1..])),
+            None => (text, 0),
+        };
+        let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+        let mut digits = integer.bytes().chain(fraction.bytes()).collect::<Vec<_>>();
+        let leading_zeros = digits.iter().take_while(|&&d| d == b'0').count();
+        digits.drain(..leading_zeros);
+        while digits.last() == Some(&b'0') {
+            digits.pop();
+        }
+        let exponent = exponent
+            .saturating_add(integer.len() as i64)
+            .saturating_sub(leading_zeros as i64);
+        Self {
+            negative: negative && !digits.is_empty(),
+            exponent: if digits.is_empty() { 0 } else { exponent },
+            digits,
+        }
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let magnitude = |d: &Self| (!d.digits.is_empty(), d.exponent);
+        let ordering = match (self.negative, other.negative) {
+            (false, true) => return Ordering::Greater,
+            (true, false) => return Ordering::Less,
+            _ => magnitude(self)
+                .cmp(&magnitude(other))
+                .then_with(|| self.digits.cmp(&other.digits)),
+        };
+        if self.negative {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+// Parses the exponent part of a JSON number, saturating on overflow.
+fn parse_exponent(text: &str) -> i64 {
+    let (negative, digits) = match text.as_bytes().first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text),
+    };
+    let magnitude = digits.bytes().fold(0i64, |n, d| {
+        n.saturating_mul(10).saturating_add(i64::from(d - b'0'))
+    });
+    if negative { -magnitude } else { magnitude }
+}
+
+// Returns the members of an object sorted by name, keeping only the last occurrence of duplicate names.
+fn members<'text, 'raw>(
+    value: RawJsonValue<'text, 'raw>,
+) -> BTreeMap<Cow<'text, str>, RawJsonValue<'text, 'raw>> {
+    value
+        .to_object()
+        .expect("infallible")
+        .map(|(name, value)| (name.to_unquoted_string_str().expect("infallible"), value))
+        .collect()
+}
+
+fn scalar_eq(left: RawJsonValue<'_, '_>, right: RawJsonValue<'_, '_>) -> bool {
+    match (left.kind(), right.kind()) {
+        (JsonValueKind::String, JsonValueKind::String) => {
+            left.to_unquoted_string_str().expect("infallible")
+                == right.to_unquoted_string_str().expect("infallible")
+        }
+        (
+            JsonValueKind::Integer | JsonValueKind::Float,
+            JsonValueKind::Integer | JsonValueKind::Float,
+        ) => Decimal::new(left.as_raw_str()) == Decimal::new(right.as_raw_str()),
+        (l, r) => l == r && left.as_raw_str() == right.as_raw_str(),
+    }
+}
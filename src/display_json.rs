@@ -1,7 +1,7 @@
 use alloc::{borrow::Cow, borrow::ToOwned, boxed::Box, rc::Rc, string::String, vec::Vec};
 use core::fmt::Display;
 
-use crate::JsonFormatter;
+use crate::{JsonFormatter, JsonValueKind};
 
 /// A variant of the [`Display`] trait for JSON.
 ///
@@ -17,6 +17,7 @@ use crate::JsonFormatter;
 /// - Collection types (arrays, vectors, sets, maps)
 /// - Nullable types (via `Option<T>`)
 /// - Reference types
+/// - Ranges (`{"start": ..., "end": ...}`) and bounds (`{"Included": ...}`, `{"Excluded": ...}`, or `"Unbounded"`)
 ///
 /// # Examples
 ///
@@ -125,183 +126,259 @@ impl<T: DisplayJson + ?Sized> DisplayJson for alloc::sync::Arc<T> {
     }
 }
 
+impl<T: DisplayJson + Copy> DisplayJson for core::cell::Cell<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+/// Formats the borrowed value.
+///
+/// If the cell is currently mutably borrowed, formatting fails with [`core::fmt::Error`]
+/// instead of panicking.
+impl<T: DisplayJson + ?Sized> DisplayJson for core::cell::RefCell<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        let value = self.try_borrow().map_err(|_| core::fmt::Error)?;
+        value.fmt(f)
+    }
+}
+
+/// Formats the inner value while holding the lock.
+///
+/// A poisoned lock is not treated as an error: the inner value is still formatted as is.
+#[cfg(feature = "std")]
+impl<T: DisplayJson + ?Sized> DisplayJson for std::sync::Mutex<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        let value = self.lock().unwrap_or_else(|e| e.into_inner());
+        value.fmt(f)
+    }
+}
+
+/// Formats the inner value while holding a read lock.
+///
+/// As with [`Mutex`](std::sync::Mutex), a poisoned lock is not treated as an error:
+/// the inner value is still formatted as is.
+#[cfg(feature = "std")]
+impl<T: DisplayJson + ?Sized> DisplayJson for std::sync::RwLock<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        let value = self.read().unwrap_or_else(|e| e.into_inner());
+        value.fmt(f)
+    }
+}
+
+// Atomics are formatted using a snapshot taken with `Ordering::Relaxed`.
+macro_rules! impl_display_json_for_atomic {
+    ($($width:literal: $t:ident),+) => {
+        $(
+            #[cfg(target_has_atomic = $width)]
+            impl DisplayJson for core::sync::atomic::$t {
+                fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+                    DisplayJson::fmt(&self.load(core::sync::atomic::Ordering::Relaxed), f)
+                }
+            }
+        )+
+    };
+}
+
+impl_display_json_for_atomic!(
+    "8": AtomicBool,
+    "8": AtomicI8,
+    "8": AtomicU8,
+    "16": AtomicI16,
+    "16": AtomicU16,
+    "32": AtomicI32,
+    "32": AtomicU32,
+    "64": AtomicI64,
+    "64": AtomicU64,
+    "ptr": AtomicIsize,
+    "ptr": AtomicUsize
+);
+
+// A blanket impl for every `F: Fn(&mut JsonFormatter) -> fmt::Result` would overlap with
+// the impls for references and smart pointers (e.g., `&F` is also `Fn`), so closures are
+// supported through trait objects instead (e.g., `&dyn Fn(..)` or `Box<dyn Fn(..)>`).
+// Use `json()` to wrap a closure without naming the trait object type.
+impl DisplayJson for dyn Fn(&mut JsonFormatter<'_, '_>) -> core::fmt::Result + '_ {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        self(f)
+    }
+}
+
+impl DisplayJson for dyn Fn(&mut JsonFormatter<'_, '_>) -> core::fmt::Result + Send + Sync + '_ {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        self(f)
+    }
+}
+
 impl<T: DisplayJson> DisplayJson for Option<T> {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
         if let Some(v) = self {
             v.fmt(f)
         } else {
-            write!(f.inner_mut(), "null")
+            f.literal(JsonValueKind::Null, "null")
         }
     }
 }
 
 impl DisplayJson for bool {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Boolean, self)
     }
 }
 
 impl DisplayJson for i8 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for i16 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for i32 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for i64 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for i128 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for isize {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for u8 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for u16 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for u32 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for u64 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for u128 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for usize {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for core::num::NonZeroI8 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for core::num::NonZeroI16 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for core::num::NonZeroI32 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for core::num::NonZeroI64 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for core::num::NonZeroI128 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for core::num::NonZeroIsize {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for core::num::NonZeroU8 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for core::num::NonZeroU16 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for core::num::NonZeroU32 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for core::num::NonZeroU64 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for core::num::NonZeroU128 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for core::num::NonZeroUsize {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "{self}")
+        f.literal(JsonValueKind::Integer, self)
     }
 }
 
 impl DisplayJson for f32 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        if self.is_finite() {
-            write!(f.inner_mut(), "{self}")
-        } else {
-            write!(f.inner_mut(), "null")
-        }
+        f.float(*self)
     }
 }
 
 impl DisplayJson for f64 {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        if self.is_finite() {
-            write!(f.inner_mut(), "{self}")
-        } else {
-            write!(f.inner_mut(), "null")
-        }
+        f.float(*self)
     }
 }
 
@@ -343,6 +420,26 @@ impl DisplayJson for std::path::PathBuf {
     }
 }
 
+/// Formats the string as a JSON string.
+///
+/// Unlike [`Path`](std::path::Path), the conversion is not lossy:
+/// if the string is not valid UTF-8, formatting fails with [`core::fmt::Error`].
+#[cfg(feature = "std")]
+impl DisplayJson for std::ffi::OsStr {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        f.string(self.to_str().ok_or(core::fmt::Error)?)
+    }
+}
+
+/// Same as [`OsStr`](std::ffi::OsStr): formatting fails with [`core::fmt::Error`]
+/// if the string is not valid UTF-8.
+#[cfg(feature = "std")]
+impl DisplayJson for std::ffi::OsString {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        self.as_os_str().fmt(f)
+    }
+}
+
 #[cfg(feature = "std")]
 impl DisplayJson for std::net::SocketAddr {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
@@ -424,7 +521,81 @@ impl<T: DisplayJson> DisplayJson for std::collections::HashSet<T> {
 
 impl DisplayJson for () {
     fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
-        write!(f.inner_mut(), "null")
+        f.literal(JsonValueKind::Null, "null")
+    }
+}
+
+/// Formats the range as a `{"start": ..., "end": ...}` object.
+///
+/// [`RangeInclusive`](core::ops::RangeInclusive) uses the same encoding,
+/// so the kind of range is determined by the type it is converted back to.
+///
+/// # Examples
+///
+/// ```
+/// use std::ops::Range;
+///
+/// let text = nojson::Json(1..3).to_string();
+/// assert_eq!(text, r#"{"start":1,"end":3}"#);
+///
+/// let range: nojson::Json<Range<u8>> = text.parse()?;
+/// assert_eq!(range.0, 1..3);
+/// # Ok::<(), nojson::JsonParseError>(())
+/// ```
+impl<T: DisplayJson> DisplayJson for core::ops::Range<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        f.object(|f| {
+            f.member("start", &self.start)?;
+            f.member("end", &self.end)
+        })
+    }
+}
+
+/// Formats the range as a `{"start": ..., "end": ...}` object, where `end` is included.
+///
+/// # Examples
+///
+/// ```
+/// use std::ops::RangeInclusive;
+///
+/// let text = nojson::Json(1..=3).to_string();
+/// assert_eq!(text, r#"{"start":1,"end":3}"#);
+///
+/// let range: nojson::Json<RangeInclusive<u8>> = text.parse()?;
+/// assert_eq!(range.0, 1..=3);
+/// # Ok::<(), nojson::JsonParseError>(())
+/// ```
+impl<T: DisplayJson> DisplayJson for core::ops::RangeInclusive<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        f.object(|f| {
+            f.member("start", self.start())?;
+            f.member("end", self.end())
+        })
+    }
+}
+
+/// Formats the bound as `{"Included": ...}`, `{"Excluded": ...}`, or `"Unbounded"`.
+///
+/// # Examples
+///
+/// ```
+/// use std::ops::Bound;
+///
+/// let bounds = [Bound::Included(1), Bound::Excluded(2), Bound::Unbounded];
+/// let text = nojson::Json(bounds).to_string();
+/// assert_eq!(text, r#"[{"Included":1},{"Excluded":2},"Unbounded"]"#);
+///
+/// let parsed: nojson::Json<[Bound<u8>; 3]> = text.parse()?;
+/// assert_eq!(parsed.0, bounds);
+/// # Ok::<(), nojson::JsonParseError>(())
+/// ```
+impl<T: DisplayJson> DisplayJson for core::ops::Bound<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        match self {
+            core::ops::Bound::Included(v) => f.object(|f| f.member("Included", v)),
+            core::ops::Bound::Excluded(v) => f.object(|f| f.member("Excluded", v)),
+            core::ops::Bound::Unbounded => f.string("Unbounded"),
+        }
     }
 }
 
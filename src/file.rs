@@ -0,0 +1,313 @@
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+
+use crate::{DisplayJson, Json, JsonFormatter, JsonParseError, RawJsonOwned, RawJsonValue};
+
+/// An error that occurred while loading a JSON file
+/// (see [`RawJsonOwned::from_file()`] and [`Json::from_file()`]).
+#[derive(Debug)]
+pub enum JsonFileError {
+    /// Reading the file failed.
+    Io {
+        /// Path of the file.
+        path: PathBuf,
+
+        /// Underlying I/O error.
+        error: std::io::Error,
+    },
+
+    /// The file content could not be parsed or converted.
+    Parse {
+        /// Path of the file.
+        path: PathBuf,
+
+        /// Line number (1-based) where the error occurred.
+        line: NonZeroUsize,
+
+        /// Column number (1-based, in characters) where the error occurred.
+        column: NonZeroUsize,
+
+        /// Underlying parse error.
+        error: JsonParseError,
+    },
+}
+
+impl JsonFileError {
+    /// Returns the path of the file that could not be loaded.
+    pub fn path(&self) -> &Path {
+        match self {
+            JsonFileError::Io { path, .. } | JsonFileError::Parse { path, .. } => path,
+        }
+    }
+
+    fn parse(path: &Path, text: &str, error: JsonParseError) -> Self {
+        let (line, column) = error
+            .get_line_and_column_numbers(text)
+            .unwrap_or((NonZeroUsize::MIN, NonZeroUsize::MIN));
+        JsonFileError::Parse {
+            path: path.to_path_buf(),
+            line,
+            column,
+            error,
+        }
+    }
+}
+
+impl core::fmt::Display for JsonFileError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            JsonFileError::Io { path, error } => {
+                write!(f, "failed to read {}: {error}", path.display())
+            }
+            JsonFileError::Parse {
+                path,
+                line,
+                column,
+                error,
+            } => write!(f, "{}:{line}:{column}: {error}", path.display()),
+        }
+    }
+}
+
+impl core::error::Error for JsonFileError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            JsonFileError::Io { error, .. } => Some(error),
+            JsonFileError::Parse { error, .. } => Some(error),
+        }
+    }
+}
+
+impl RawJsonOwned {
+    /// Reads and parses a JSON file.
+    ///
+    /// Files with the `.jsonc` extension are parsed as JSONC (see [`RawJsonOwned::parse_jsonc()`]);
+    /// all other files are parsed as plain JSON.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), nojson::JsonFileError> {
+    /// let config = nojson::RawJsonOwned::from_file("config.jsonc")?;
+    /// println!("{}", config.value());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, JsonFileError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|error| JsonFileError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        let result = if path.extension().is_some_and(|ext| ext == "jsonc") {
+            RawJsonOwned::parse_jsonc(text.as_str()).map(|(json, _)| json)
+        } else {
+            RawJsonOwned::parse(text.as_str())
+        };
+        result.map_err(|e| JsonFileError::parse(path, &text, e))
+    }
+}
+
+impl<T> Json<T>
+where
+    T: for<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError>,
+{
+    /// Reads a JSON file and converts its content into `T`.
+    ///
+    /// The file is parsed as described in [`RawJsonOwned::from_file()`].
+    /// Both I/O and parse errors are reported as [`JsonFileError`] together with the file path.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), nojson::JsonFileError> {
+    /// let nojson::Json(servers) = nojson::Json::<Vec<String>>::from_file("servers.json")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, JsonFileError> {
+        let path = path.as_ref();
+        let json = RawJsonOwned::from_file(path)?;
+        json.value()
+            .try_into()
+            .map(Json)
+            .map_err(|e| JsonFileError::parse(path, json.text(), e))
+    }
+}
+
+/// A loader that expands include directives such as `{"$include": "base.json"}` across JSON files.
+///
+/// When an object contains the include key, the referenced files (a single path or an array of paths,
+/// relative to the including file) are loaded, their own includes are expanded recursively,
+/// and the results are deep-merged in order. The remaining members of the including object
+/// are then deep-merged on top, so they override the included values.
+/// Objects are merged member by member; any other values are replaced.
+///
+/// An object that consists only of a single include is replaced by the included value,
+/// which may be of any kind (e.g., `{"servers": {"$include": "servers.json"}}` where
+/// `servers.json` contains an array). Including a non-object value together with other members
+/// or other files is reported as an error, since it cannot be merged.
+///
+/// Include cycles are reported as errors pointing at the offending include directive.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), nojson::JsonFileError> {
+/// // base.json:   {"server": {"host": "localhost", "port": 80}}
+/// // config.json: {"$include": "base.json", "server": {"port": 8080}}
+/// let config = nojson::JsonIncludeResolver::new("$include").load("config.json")?;
+/// assert_eq!(config.text(), r#"{"server":{"host":"localhost","port":8080}}"#);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JsonIncludeResolver {
+    key: String,
+}
+
+impl JsonIncludeResolver {
+    /// Makes a new [`JsonIncludeResolver`] that uses `key` as the include directive.
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.to_owned(),
+        }
+    }
+
+    /// Loads a JSON (or JSONC) file and expands its include directives.
+    ///
+    /// Files are read as described in [`RawJsonOwned::from_file()`].
+    /// Note that comments are not preserved in the resulting document.
+    pub fn load<P: AsRef<Path>>(&self, path: P) -> Result<RawJsonOwned, JsonFileError> {
+        let path = path.as_ref();
+        let canonical = canonicalize(path)?;
+        let node = self.load_node(&canonical, &mut Vec::new())?;
+        let text = Json(&node).to_string();
+        Ok(RawJsonOwned::parse(text).expect("infallible"))
+    }
+
+    fn load_node(&self, path: &Path, stack: &mut Vec<PathBuf>) -> Result<Node, JsonFileError> {
+        let json = RawJsonOwned::from_file(path)?;
+        stack.push(path.to_path_buf());
+        let node = self.expand(json.value(), path, json.text(), stack)?;
+        stack.pop();
+        Ok(node)
+    }
+
+    fn expand(
+        &self,
+        value: RawJsonValue<'_, '_>,
+        path: &Path,
+        text: &str,
+        stack: &mut Vec<PathBuf>,
+    ) -> Result<Node, JsonFileError> {
+        if value.kind().is_array() {
+            let elements = value
+                .to_array()
+                .expect("infallible")
+                .map(|v| self.expand(v, path, text, stack))
+                .collect::<Result<_, _>>()?;
+            return Ok(Node::Array(elements));
+        }
+        if !value.kind().is_object() {
+            return Ok(Node::Raw(value.as_raw_str().to_owned()));
+        }
+
+        let mut includes = Vec::new();
+        if let Some(include) = value.to_member(&self.key).expect("infallible").optional() {
+            let targets = if include.kind().is_array() {
+                include.to_array().expect("infallible").collect()
+            } else {
+                alloc::vec![include]
+            };
+            for target in targets {
+                let target_path = target
+                    .to_unquoted_string_str()
+                    .map_err(|e| JsonFileError::parse(path, text, e))?;
+                let dir = path.parent().unwrap_or(Path::new(""));
+                let target_path = canonicalize(&dir.join(target_path.as_ref()))?;
+                if stack.contains(&target_path) {
+                    let e = target.invalid(alloc::format!(
+                        "include cycle detected: {}",
+                        target_path.display()
+                    ));
+                    return Err(JsonFileError::parse(path, text, e));
+                }
+                let node = self.load_node(&target_path, stack)?;
+                includes.push((target, target_path, node));
+            }
+        }
+
+        let mut members = Vec::new();
+        for (name, v) in value.to_object().expect("infallible") {
+            let name = name.to_unquoted_string_str().expect("infallible");
+            if name != self.key {
+                members.push((name.into_owned(), self.expand(v, path, text, stack)?));
+            }
+        }
+
+        // A lone include is replaced by the included value as is, even if it is not an object.
+        if includes.len() == 1 && members.is_empty() {
+            let (_, _, node) = includes.pop().expect("infallible");
+            return Ok(node);
+        }
+
+        let mut merged = Node::Object(Vec::new());
+        for (target, target_path, node) in includes {
+            if !matches!(node, Node::Object(_)) {
+                let e = target.invalid(alloc::format!(
+                    "included value is not an object and cannot be merged: {}",
+                    target_path.display()
+                ));
+                return Err(JsonFileError::parse(path, text, e));
+            }
+            merged = merged.merge(node);
+        }
+        Ok(merged.merge(Node::Object(members)))
+    }
+}
+
+fn canonicalize(path: &Path) -> Result<PathBuf, JsonFileError> {
+    std::fs::canonicalize(path).map_err(|error| JsonFileError::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+// A JSON value under construction by `JsonIncludeResolver`.
+#[derive(Debug)]
+enum Node {
+    Raw(String),
+    Array(Vec<Node>),
+    Object(Vec<(String, Node)>),
+}
+
+impl Node {
+    fn merge(self, other: Node) -> Node {
+        match (self, other) {
+            (Node::Object(mut members), Node::Object(others)) => {
+                for (name, value) in others {
+                    if let Some(i) = members.iter().position(|(n, _)| *n == name) {
+                        let (name, old) = members.remove(i);
+                        members.insert(i, (name, old.merge(value)));
+                    } else {
+                        members.push((name, value));
+                    }
+                }
+                Node::Object(members)
+            }
+            (_, other) => other,
+        }
+    }
+}
+
+impl DisplayJson for Node {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        match self {
+            Node::Raw(text) => f.raw_json(text),
+            Node::Array(elements) => f.value(elements),
+            Node::Object(members) => f.object(|f| f.members(members.iter().map(|(n, v)| (n, v)))),
+        }
+    }
+}
@@ -1,6 +1,21 @@
-use core::fmt::{Display, Write};
+use alloc::{format, string::String, vec::Vec};
+use core::{
+    cell::Cell,
+    fmt::{Display, LowerExp, Write},
+    ops::Range,
+};
 
-use crate::DisplayJson;
+use crate::{DisplayJson, JsonKeyCase, JsonValueKind};
+
+// SGR parameters used when ANSI colors are enabled (similar to jq's default colors).
+mod color {
+    pub const NULL: &str = "1;30";
+    pub const BOOLEAN: &str = "33";
+    pub const NUMBER: &str = "36";
+    pub const STRING: &str = "32";
+    pub const KEY: &str = "1;34";
+    pub const PUNCTUATION: &str = "1";
+}
 
 /// A formatter for JSON values that controls the layout and formatting of the output.
 ///
@@ -38,8 +53,219 @@ use crate::DisplayJson;
 pub struct JsonFormatter<'a, 'b> {
     inner: &'a mut core::fmt::Formatter<'b>,
     level: usize,
+    settings: JsonFormatOptions,
+
+    // Width of the member name (and separator) preceding the value being formatted,
+    // used by the width-aware layout.
+    prefix_width: usize,
+
+    // Array indices and member names leading to the value being formatted
+    // (only maintained when `track_path` is enabled).
+    path: Vec<PathSegment>,
+
+    // Set while formatting containers whose layout is decided once they have been formatted
+    // (see `JsonFormatter::record()`). The output is written to it instead of `inner` meanwhile.
+    recording: Option<Recording>,
+
+    // Set while formatting a nested value in the `NestedSettings::Inherit` mode.
+    settings_locked: bool,
+}
+
+// Written in place of elided values and after truncated strings.
+const ELISION_MARKER: &str = "…";
+
+#[derive(Debug, Clone)]
+enum PathSegment {
+    Index(usize),
+    Name(String),
+}
+
+/// A reusable set of formatting options for [`JsonFormatter`].
+///
+/// Options can be built once and applied to a formatter with [`JsonFormatter::apply()`]
+/// or [`json_with()`](crate::json_with), instead of calling the individual setters in every formatting closure.
+/// Each builder method corresponds to the `JsonFormatter::set_*()` method of the same name.
+///
+/// # Examples
+///
+/// ```
+/// use nojson::JsonFormatOptions;
+///
+/// let options = JsonFormatOptions::new().indent_size(2).spacing(true);
+/// let output = nojson::json(|f| {
+///     f.apply(&options);
+///     f.object(|f| f.member("items", [1, 2]))
+/// });
+/// assert_eq!(
+///     output.to_string(),
+///     "{\n  \"items\": [\n    1,\n    2\n  ]\n}"
+/// );
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JsonFormatOptions {
     indent_size: usize,
     spacing: bool,
+    non_finite_float: NonFiniteFloat,
+    float_precision: FloatPrecision,
+    float_positional_range: Option<(i32, i32)>,
+    force_float_decimal_point: bool,
+    unquoted_keys: bool,
+    key_case: Option<JsonKeyCase>,
+    single_quoted_strings: bool,
+    trailing_commas: bool,
+    crlf: bool,
+    max_inline_width: Option<usize>,
+    inline_numeric_arrays: bool,
+    escape_non_ascii: bool,
+    ansi_colors: bool,
+    max_depth: Option<usize>,
+    track_path: bool,
+    elide_depth: Option<usize>,
+    elide_elements: Option<usize>,
+    max_string_len: Option<usize>,
+    sort_keys: bool,
+    nested_settings: NestedSettings,
+}
+
+impl JsonFormatOptions {
+    /// Makes a new [`JsonFormatOptions`] instance with the default settings (compact output).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the indentation size (see [`JsonFormatter::set_indent_size()`]).
+    pub fn indent_size(mut self, size: usize) -> Self {
+        self.indent_size = size;
+        self
+    }
+
+    /// Sets whether to insert spaces (see [`JsonFormatter::set_spacing()`]).
+    pub fn spacing(mut self, enable: bool) -> Self {
+        self.spacing = enable;
+        self
+    }
+
+    /// Sets the non-finite float policy (see [`JsonFormatter::set_non_finite_float()`]).
+    pub fn non_finite_float(mut self, policy: NonFiniteFloat) -> Self {
+        self.non_finite_float = policy;
+        self
+    }
+
+    /// Sets the float precision (see [`JsonFormatter::set_float_precision()`]).
+    pub fn float_precision(mut self, precision: FloatPrecision) -> Self {
+        self.float_precision = precision;
+        self
+    }
+
+    /// Sets the positional float exponent range (see [`JsonFormatter::set_float_positional_range()`]).
+    pub fn float_positional_range(mut self, range: Option<Range<i32>>) -> Self {
+        self.float_positional_range = range.map(|r| (r.start, r.end));
+        self
+    }
+
+    /// Sets whether whole floats always have a decimal point (see [`JsonFormatter::set_force_float_decimal_point()`]).
+    pub fn force_float_decimal_point(mut self, enable: bool) -> Self {
+        self.force_float_decimal_point = enable;
+        self
+    }
+
+    /// Sets whether identifier member names are unquoted (see [`JsonFormatter::set_unquoted_keys()`]).
+    pub fn unquoted_keys(mut self, enable: bool) -> Self {
+        self.unquoted_keys = enable;
+        self
+    }
+
+    /// Sets the naming convention of member names (see [`JsonFormatter::set_key_case()`]).
+    pub fn key_case(mut self, case: Option<JsonKeyCase>) -> Self {
+        self.key_case = case;
+        self
+    }
+
+    /// Sets whether strings are single-quoted (see [`JsonFormatter::set_single_quoted_strings()`]).
+    pub fn single_quoted_strings(mut self, enable: bool) -> Self {
+        self.single_quoted_strings = enable;
+        self
+    }
+
+    /// Sets whether trailing commas are written (see [`JsonFormatter::set_trailing_commas()`]).
+    pub fn trailing_commas(mut self, enable: bool) -> Self {
+        self.trailing_commas = enable;
+        self
+    }
+
+    /// Sets whether line breaks are `\r\n` (see [`JsonFormatter::set_crlf()`]).
+    pub fn crlf(mut self, enable: bool) -> Self {
+        self.crlf = enable;
+        self
+    }
+
+    /// Sets the maximum inline width (see [`JsonFormatter::set_max_inline_width()`]).
+    pub fn max_inline_width(mut self, width: Option<usize>) -> Self {
+        self.max_inline_width = width;
+        self
+    }
+
+    /// Sets whether numeric arrays are kept on one line (see [`JsonFormatter::set_inline_numeric_arrays()`]).
+    pub fn inline_numeric_arrays(mut self, enable: bool) -> Self {
+        self.inline_numeric_arrays = enable;
+        self
+    }
+
+    /// Sets whether non-ASCII characters are escaped (see [`JsonFormatter::set_escape_non_ascii()`]).
+    pub fn escape_non_ascii(mut self, enable: bool) -> Self {
+        self.escape_non_ascii = enable;
+        self
+    }
+
+    /// Sets whether ANSI colors are written (see [`JsonFormatter::set_ansi_colors()`]).
+    pub fn ansi_colors(mut self, enable: bool) -> Self {
+        self.ansi_colors = enable;
+        self
+    }
+
+    /// Sets the maximum nesting depth (see [`JsonFormatter::set_max_depth()`]).
+    pub fn max_depth(mut self, depth: Option<usize>) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Sets whether the path of the current value is tracked (see [`JsonFormatter::set_track_path()`]).
+    pub fn track_path(mut self, enable: bool) -> Self {
+        self.track_path = enable;
+        self
+    }
+
+    /// Sets the depth beyond which containers are elided (see [`JsonFormatter::set_elide_depth()`]).
+    pub fn elide_depth(mut self, depth: Option<usize>) -> Self {
+        self.elide_depth = depth;
+        self
+    }
+
+    /// Sets the number of elements and members kept in each container
+    /// (see [`JsonFormatter::set_elide_elements()`]).
+    pub fn elide_elements(mut self, count: Option<usize>) -> Self {
+        self.elide_elements = count;
+        self
+    }
+
+    /// Sets the maximum number of characters written for each string value
+    /// (see [`JsonFormatter::set_max_string_len()`]).
+    pub fn max_string_len(mut self, len: Option<usize>) -> Self {
+        self.max_string_len = len;
+        self
+    }
+
+    /// Sets whether object members are sorted by name (see [`JsonFormatter::set_sort_keys()`]).
+    pub fn sort_keys(mut self, enable: bool) -> Self {
+        self.sort_keys = enable;
+        self
+    }
+
+    /// Sets how nested values handle settings changes (see [`JsonFormatter::set_nested_settings()`]).
+    pub fn nested_settings(mut self, mode: NestedSettings) -> Self {
+        self.nested_settings = mode;
+        self
+    }
 }
 
 impl<'a, 'b> JsonFormatter<'a, 'b> {
@@ -47,15 +273,59 @@ impl<'a, 'b> JsonFormatter<'a, 'b> {
         Self {
             inner,
             level: 0,
-            indent_size: 0,
-            spacing: false,
+            settings: JsonFormatOptions::default(),
+            prefix_width: 0,
+            path: Vec::new(),
+            recording: None,
+            settings_locked: false,
+        }
+    }
+
+    /// Runs `f` with a formatter that writes JSON into `writer`.
+    ///
+    /// This allows building JSON text incrementally into any [`core::fmt::Write`] target
+    /// (e.g., a [`String`]) without defining a [`Display`] wrapper type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nojson::JsonFormatter;
+    ///
+    /// let mut text = String::from("data = ");
+    /// JsonFormatter::write_to(&mut text, |f| {
+    ///     f.set_spacing(true);
+    ///     f.object(|f| f.member("items", [1, 2]))
+    /// })?;
+    /// assert_eq!(text, r#"data = { "items": [1, 2] }"#);
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn write_to<W, F>(writer: &mut W, f: F) -> core::fmt::Result
+    where
+        W: Write + ?Sized,
+        F: FnOnce(&mut JsonFormatter<'_, '_>) -> core::fmt::Result,
+    {
+        struct WriteTo<F>(Cell<Option<F>>);
+
+        impl<F> Display for WriteTo<F>
+        where
+            F: FnOnce(&mut JsonFormatter<'_, '_>) -> core::fmt::Result,
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut fmt = JsonFormatter::new(f);
+                self.0.take().ok_or(core::fmt::Error)?(&mut fmt)
+            }
         }
+
+        write!(writer, "{}", WriteTo(Cell::new(Some(f))))
     }
 
     /// Formats a value that implements the [`DisplayJson`] trait.
     ///
     /// This is the primary method for writing a value to the JSON output.
     ///
+    /// The value inherits the current settings. Whether the settings it changes while formatting itself
+    /// remain in effect afterwards depends on [`JsonFormatter::set_nested_settings()`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -63,7 +333,16 @@ impl<'a, 'b> JsonFormatter<'a, 'b> {
     /// assert_eq!(output.to_string(), "[1,2,3]");
     /// ```
     pub fn value<T: DisplayJson>(&mut self, value: T) -> core::fmt::Result {
-        value.fmt(self)
+        match self.settings.nested_settings {
+            NestedSettings::Propagate => value.fmt(self),
+            NestedSettings::Scoped => self.scoped(|f| value.fmt(f)),
+            NestedSettings::Inherit => {
+                let locked = core::mem::replace(&mut self.settings_locked, true);
+                let result = value.fmt(self);
+                self.settings_locked = locked;
+                result
+            }
+        }
     }
 
     /// Formats a value as a JSON string with proper escaping.
@@ -78,13 +357,44 @@ impl<'a, 'b> JsonFormatter<'a, 'b> {
     /// assert_eq!(output.to_string(), r#""Hello\nWorld""#);
     /// ```
     pub fn string<T: Display>(&mut self, content: T) -> core::fmt::Result {
-        write!(self.inner, "\"")?;
+        self.quoted_truncated(content, color::STRING, self.settings.max_string_len)
+    }
+
+    fn quoted<T: Display>(&mut self, content: T, color: &str) -> core::fmt::Result {
+        self.quoted_truncated(content, color, None)
+    }
+
+    fn quoted_truncated<T: Display>(
+        &mut self,
+        content: T,
+        color: &str,
+        max_len: Option<usize>,
+    ) -> core::fmt::Result {
+        self.start_color(color)?;
+        let quote = if self.settings.single_quoted_strings {
+            '\''
+        } else {
+            '"'
+        };
+        write!(self.out(), "{quote}")?;
         {
-            let mut fmt = JsonStringContentFormatter { inner: self.inner };
+            let single_quoted = self.settings.single_quoted_strings;
+            let escape_non_ascii = self.settings.escape_non_ascii;
+            let mut fmt = JsonStringContentFormatter {
+                inner: self.out(),
+                single_quoted,
+                escape_non_ascii,
+                remaining: max_len,
+                truncated: false,
+            };
             write!(fmt, "{content}")?;
+            if fmt.truncated {
+                fmt.remaining = None;
+                fmt.write_str(ELISION_MARKER)?;
+            }
         }
-        write!(self.inner, "\"")?;
-        Ok(())
+        write!(self.out(), "{quote}")?;
+        self.end_color()
     }
 
     /// Creates a JSON array with the provided formatting function.
@@ -120,160 +430,1658 @@ impl<'a, 'b> JsonFormatter<'a, 'b> {
     where
         F: FnOnce(&mut JsonArrayFormatter<'a, 'b, '_>) -> core::fmt::Result,
     {
-        write!(self.inner, "[")?;
+        if self.recording.is_none()
+            && (self.inline_width().is_some()
+                || (self.settings.inline_numeric_arrays && self.settings.indent_size > 0))
+        {
+            return self.record(|fmt| fmt.expanded_array(f));
+        }
+        self.expanded_array(f)
+    }
+
+    fn expanded_array<F>(&mut self, f: F) -> core::fmt::Result
+    where
+        F: FnOnce(&mut JsonArrayFormatter<'a, 'b, '_>) -> core::fmt::Result,
+    {
+        if self.is_elided_depth() {
+            return self.string(ELISION_MARKER);
+        }
+        self.check_depth()?;
+        self.open_container(false)?;
+
+        let settings = self.settings;
+        self.level += 1;
+        let mut array = JsonArrayFormatter { fmt: self, len: 0 };
+        f(&mut array)?;
+        let empty = array.len == 0;
+        self.level -= 1;
+        self.settings = settings;
+
+        self.close_container(false, empty)
+    }
+
+    /// Creates a JSON object with the provided formatting function.
+    ///
+    /// This method starts a new JSON object and provides a [`JsonObjectFormatter`] to the callback
+    /// function for adding members to the object. It handles proper indentation, spacing, and
+    /// braces placement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.object(|f| {
+    ///         f.member("name", "Alice")?;
+    ///         f.member("age", 30)
+    ///     })
+    /// });
+    /// assert_eq!(output.to_string(), r#"{"name":"Alice","age":30}"#);
+    ///
+    /// // With pretty-printing
+    /// let pretty = nojson::json(|f| {
+    ///     f.set_indent_size(2);
+    ///     f.set_spacing(true);
+    ///     f.object(|f| {
+    ///         f.member("name", "Alice")?;
+    ///         f.member("age", 30)
+    ///     })
+    /// });
+    /// ```
+    pub fn object<F>(&mut self, f: F) -> core::fmt::Result
+    where
+        F: FnOnce(&mut JsonObjectFormatter<'a, 'b, '_>) -> core::fmt::Result,
+    {
+        if self.recording.is_none() && (self.settings.sort_keys || self.inline_width().is_some()) {
+            return self.record(|fmt| fmt.expanded_object(f));
+        }
+        self.expanded_object(f)
+    }
+
+    fn expanded_object<F>(&mut self, f: F) -> core::fmt::Result
+    where
+        F: FnOnce(&mut JsonObjectFormatter<'a, 'b, '_>) -> core::fmt::Result,
+    {
+        if self.is_elided_depth() {
+            return self.string(ELISION_MARKER);
+        }
+        self.check_depth()?;
+        self.open_container(true)?;
 
-        let indent_size = self.indent_size;
-        let spacing = self.spacing;
+        let settings = self.settings;
         self.level += 1;
-        let mut array = JsonArrayFormatter {
+        // Members of sorted objects are elided after sorting,
+        // so that the kept ones are the first in sorted order.
+        let sorted = self.recording.is_some() && settings.sort_keys;
+        let mut object = JsonObjectFormatter {
             fmt: self,
-            empty: true,
+            len: 0,
+            sorted,
         };
-        f(&mut array)?;
-        let empty = array.empty;
+        f(&mut object)?;
+        if sorted
+            && let Some(count) = settings.elide_elements
+            && object.len > count
+        {
+            object.fmt.settings = settings;
+            object.push_member(ELISION_MARKER, ELISION_MARKER)?;
+        }
+        let empty = object.len == 0;
         self.level -= 1;
-        self.indent_size = indent_size;
-        self.spacing = spacing;
+        self.settings = settings;
+
+        self.close_container(true, empty)
+    }
+
+    /// Creates an internally tagged JSON object such as `{"type":"circle","radius":1}`.
+    ///
+    /// The `tag_field` member is written first, followed by the members added by `f`.
+    /// This is the encoding counterpart of [`RawJsonValue::to_tagged()`](crate::RawJsonValue::to_tagged).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.tagged_object("type", "circle", |f| f.member("radius", 1))
+    /// });
+    /// assert_eq!(output.to_string(), r#"{"type":"circle","radius":1}"#);
+    /// ```
+    pub fn tagged_object<F>(&mut self, tag_field: &str, tag: &str, f: F) -> core::fmt::Result
+    where
+        F: FnOnce(&mut JsonObjectFormatter<'a, 'b, '_>) -> core::fmt::Result,
+    {
+        self.object(|object| {
+            object.member(tag_field, tag)?;
+            f(object)
+        })
+    }
+
+    /// Creates an adjacently tagged JSON object such as `{"type":"circle","data":{"radius":1}}`.
+    ///
+    /// This is the encoding counterpart of [`RawJsonValue::to_adjacently_tagged()`](crate::RawJsonValue::to_adjacently_tagged).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.adjacently_tagged("type", "line", "data", [1, 2])
+    /// });
+    /// assert_eq!(output.to_string(), r#"{"type":"line","data":[1,2]}"#);
+    /// ```
+    pub fn adjacently_tagged<T: DisplayJson>(
+        &mut self,
+        tag_field: &str,
+        tag: &str,
+        content_field: &str,
+        content: T,
+    ) -> core::fmt::Result {
+        self.object(|object| {
+            object.member(tag_field, tag)?;
+            object.member(content_field, content)
+        })
+    }
+
+    /// Creates an externally tagged JSON object such as `{"circle":{"radius":1}}`.
+    ///
+    /// This is the encoding counterpart of [`RawJsonValue::to_externally_tagged()`](crate::RawJsonValue::to_externally_tagged).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.externally_tagged("circle", nojson::object(|f| f.member("radius", 1)))
+    /// });
+    /// assert_eq!(output.to_string(), r#"{"circle":{"radius":1}}"#);
+    /// ```
+    pub fn externally_tagged<T: DisplayJson>(&mut self, tag: &str, value: T) -> core::fmt::Result {
+        self.object(|object| object.member(tag, value))
+    }
+
+    /// Writes an already-serialized JSON text verbatim.
+    ///
+    /// This is useful for embedding cached or pre-rendered JSON fragments without re-parsing
+    /// or double-escaping them. The text is written as-is, so the current indentation and
+    /// spacing settings are not applied to its contents.
+    ///
+    /// # Note
+    ///
+    /// It is the responsibility of the caller to ensure that `text` is a valid JSON value.
+    /// In debug builds, this is checked with a [`debug_assert!`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let cached = r#"{"id":1,"tags":["a"]}"#;
+    /// let output = nojson::json(|f| {
+    ///     f.array(|f| {
+    ///         f.element(nojson::json(|f| f.raw_json(cached)))?;
+    ///         f.raw_element("null")
+    ///     })
+    /// });
+    /// assert_eq!(output.to_string(), r#"[{"id":1,"tags":["a"]},null]"#);
+    /// ```
+    pub fn raw_json(&mut self, text: &str) -> core::fmt::Result {
+        debug_assert!(
+            crate::RawJson::parse(text).is_ok(),
+            "invalid raw JSON text: {text:?}"
+        );
+        self.out().write_str(text)
+    }
+
+    /// Returns the current formatting options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.set_indent_size(2);
+    ///     assert_eq!(f.options(), nojson::JsonFormatOptions::new().indent_size(2));
+    ///     f.value(1)
+    /// });
+    /// assert_eq!(output.to_string(), "1");
+    /// ```
+    pub fn options(&self) -> JsonFormatOptions {
+        self.settings
+    }
+
+    /// Replaces all formatting options with `options`.
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let pretty = nojson::JsonFormatOptions::new().indent_size(2).spacing(true);
+    /// let output = nojson::json(|f| {
+    ///     f.apply(&pretty);
+    ///     f.value([1])
+    /// });
+    /// assert_eq!(output.to_string(), "[\n  1\n]");
+    /// ```
+    pub fn apply(&mut self, options: &JsonFormatOptions) {
+        self.update_settings(|s| *s = *options);
+    }
+
+    /// Returns a mutable reference to the inner [`core::fmt::Formatter`].
+    ///
+    /// This method provides direct access to the wrapped formatter, which can be useful
+    /// for implementing custom formatting logic for primitive types.
+    ///
+    /// # Note
+    ///
+    /// It is the responsibility of the user to ensure that the content written to the inner formatter is valid JSON.
+    ///
+    /// Containers whose layout depends on their content (see [`JsonFormatter::set_max_inline_width()`],
+    /// [`JsonFormatter::set_inline_numeric_arrays()`], and [`JsonFormatter::set_sort_keys()`]) are buffered
+    /// until they are complete, and content written to the inner formatter meanwhile bypasses that buffer.
+    /// Use [`JsonFormatter::raw_json()`] to write such content instead.
+    pub fn inner_mut(&mut self) -> &mut core::fmt::Formatter<'b> {
+        self.inner
+    }
+
+    /// Returns the current indentation level.
+    ///
+    /// The indentation level increases when entering arrays and objects, and
+    /// decreases when exiting them.
+    pub fn get_level(&self) -> usize {
+        self.level
+    }
+
+    /// Returns the number of spaces used for each indentation level.
+    pub fn get_indent_size(&self) -> usize {
+        self.settings.indent_size
+    }
+
+    /// Sets the number of spaces used for each indentation level.
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    pub fn set_indent_size(&mut self, size: usize) {
+        self.update_settings(|s| s.indent_size = size);
+    }
+
+    /// Returnes whether inserting a space after ':', ',', and '{'.
+    pub fn get_spacing(&self) -> bool {
+        self.settings.spacing
+    }
+
+    /// Sets whether inserting a space after ':', ',', and '{'.
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    pub fn set_spacing(&mut self, enable: bool) {
+        self.update_settings(|s| s.spacing = enable);
+    }
+
+    /// Returns how non-finite floating-point numbers (NaN and infinities) are formatted.
+    pub fn get_non_finite_float(&self) -> NonFiniteFloat {
+        self.settings.non_finite_float
+    }
+
+    /// Sets how non-finite floating-point numbers (NaN and infinities) are formatted.
+    ///
+    /// JSON has no representation for these values, so by default they are emitted as `null`.
+    /// See [`NonFiniteFloat`] for the available policies.
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nojson::NonFiniteFloat;
+    ///
+    /// let output = nojson::json(|f| {
+    ///     f.set_non_finite_float(NonFiniteFloat::String);
+    ///     f.value([1.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY])
+    /// });
+    /// assert_eq!(output.to_string(), r#"[1,"NaN","Infinity","-Infinity"]"#);
+    ///
+    /// // Formatting fails instead of silently emitting `null`.
+    /// let mut buf = String::new();
+    /// let output = nojson::json(|f| {
+    ///     f.set_non_finite_float(NonFiniteFloat::Error);
+    ///     f.value(f32::NAN)
+    /// });
+    /// assert!(std::fmt::write(&mut buf, format_args!("{output}")).is_err());
+    /// ```
+    pub fn set_non_finite_float(&mut self, policy: NonFiniteFloat) {
+        self.update_settings(|s| s.non_finite_float = policy);
+    }
+
+    /// Returns the precision used when formatting floating-point numbers.
+    pub fn get_float_precision(&self) -> FloatPrecision {
+        self.settings.float_precision
+    }
+
+    /// Sets the precision used when formatting floating-point numbers.
+    ///
+    /// By default ([`FloatPrecision::Shortest`]), floats are formatted with the shortest
+    /// representation that round-trips to the same value.
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nojson::FloatPrecision;
+    ///
+    /// let output = nojson::json(|f| {
+    ///     f.set_float_precision(FloatPrecision::Decimals(2));
+    ///     f.value([1.0, 2.345, 1.0 / 3.0])
+    /// });
+    /// assert_eq!(output.to_string(), "[1.00,2.35,0.33]");
+    ///
+    /// let output = nojson::json(|f| {
+    ///     f.set_float_precision(FloatPrecision::SignificantDigits(3));
+    ///     f.value([1.0, 123456.0, 0.00123456])
+    /// });
+    /// assert_eq!(output.to_string(), "[1,123000,0.00123]");
+    /// ```
+    pub fn set_float_precision(&mut self, precision: FloatPrecision) {
+        self.update_settings(|s| s.float_precision = precision);
+    }
+
+    /// Returns the range of decimal exponents for which floats are written in positional notation.
+    pub fn get_float_positional_range(&self) -> Option<Range<i32>> {
+        self.settings
+            .float_positional_range
+            .map(|(start, end)| start..end)
+    }
+
+    /// Sets the range of decimal exponents for which floats are written in positional notation.
+    ///
+    /// A float whose decimal exponent (the `e` in `d.ddd × 10^e`) falls outside `range`
+    /// is written in scientific notation (e.g., `1e30` or `1.5e-7`).
+    /// If `None` (default), scientific notation is never used.
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Same thresholds as JavaScript's `Number.prototype.toString()`.
+    /// let output = nojson::json(|f| {
+    ///     f.set_float_positional_range(Some(-6..21));
+    ///     f.value([1e30, 1.5e-7, 123.25, 1e20])
+    /// });
+    /// assert_eq!(output.to_string(), "[1e30,1.5e-7,123.25,100000000000000000000]");
+    /// ```
+    pub fn set_float_positional_range(&mut self, range: Option<Range<i32>>) {
+        self.update_settings(|s| s.float_positional_range = range.map(|r| (r.start, r.end)));
+    }
+
+    /// Returns whether floats that are whole numbers are always written with a decimal point.
+    pub fn get_force_float_decimal_point(&self) -> bool {
+        self.settings.force_float_decimal_point
+    }
+
+    /// Sets whether floats that are whole numbers are always written with a decimal point.
+    ///
+    /// When enabled, `2.0f64` is written as `2.0` instead of `2` (and `1e30` as `1.0e30`),
+    /// so that consumers distinguishing integers from other numbers by their textual
+    /// representation treat the value as a float. Integer types are not affected.
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.set_force_float_decimal_point(true);
+    ///     f.object(|f| {
+    ///         f.member("count", 2)?;
+    ///         f.member("ratio", 2.0)
+    ///     })
+    /// });
+    /// assert_eq!(output.to_string(), r#"{"count":2,"ratio":2.0}"#);
+    /// ```
+    pub fn set_force_float_decimal_point(&mut self, enable: bool) {
+        self.update_settings(|s| s.force_float_decimal_point = enable);
+    }
+
+    /// Returns whether line breaks are written as `\r\n` instead of `\n` when pretty-printing.
+    pub fn get_crlf(&self) -> bool {
+        self.settings.crlf
+    }
+
+    /// Sets whether line breaks are written as `\r\n` instead of `\n` when pretty-printing.
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.set_indent_size(2);
+    ///     f.set_crlf(true);
+    ///     f.value([1, 2])
+    /// });
+    /// assert_eq!(output.to_string(), "[\r\n  1,\r\n  2\r\n]");
+    /// ```
+    pub fn set_crlf(&mut self, enable: bool) {
+        self.update_settings(|s| s.crlf = enable);
+    }
+
+    /// Returns the maximum line width for keeping arrays and objects on a single line when pretty-printing.
+    pub fn get_max_inline_width(&self) -> Option<usize> {
+        self.settings.max_inline_width
+    }
+
+    /// Sets the maximum line width for keeping arrays and objects on a single line when pretty-printing.
+    ///
+    /// If `Some(width)` and the indent size is non-zero, an array or object is written in its
+    /// compact form (honoring the spacing setting) when the resulting line, including the indentation
+    /// and the preceding member name, fits within `width` characters.
+    /// Otherwise, it is expanded over multiple lines as usual, and the same rule is applied to each of its children.
+    /// If `None` (default), arrays and objects are always expanded.
+    ///
+    /// Each container is formatted into a temporary buffer to measure its width,
+    /// so formatting is slower when this setting is enabled.
+    /// Also, settings changed inside an array or object that fits are applied, but
+    /// changes to layout-related settings (such as the indent size) inside
+    /// a container that is expanded are not.
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.set_indent_size(2);
+    ///     f.set_spacing(true);
+    ///     f.set_max_inline_width(Some(20));
+    ///     f.object(|f| {
+    ///         f.member("point", [1, 2])?;
+    ///         f.member("names", ["Alice", "Bob", "Charlie"])
+    ///     })
+    /// });
+    /// assert_eq!(
+    ///     format!("\n{output}"),
+    ///     r#"
+    /// {
+    ///   "point": [1, 2],
+    ///   "names": [
+    ///     "Alice",
+    ///     "Bob",
+    ///     "Charlie"
+    ///   ]
+    /// }"#
+    /// );
+    /// ```
+    pub fn set_max_inline_width(&mut self, width: Option<usize>) {
+        self.update_settings(|s| s.max_inline_width = width);
+    }
+
+    /// Returns the maximum nesting depth of arrays and objects.
+    pub fn get_max_depth(&self) -> Option<usize> {
+        self.settings.max_depth
+    }
+
+    /// Sets the maximum nesting depth of arrays and objects.
+    ///
+    /// If `Some(depth)`, starting an array or object nested within `depth` other arrays or objects
+    /// fails with [`core::fmt::Error`]. This turns runaway recursion (e.g., a [`DisplayJson`]
+    /// implementation that accidentally formats itself) into an error instead of a stack overflow.
+    /// If `None` (default), the depth is not limited.
+    ///
+    /// Note that [`ToString::to_string()`] panics if formatting fails,
+    /// so use [`core::fmt::Write`] (e.g., `write!()`) to handle the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fmt::Write;
+    ///
+    /// let nested = nojson::json(|f| {
+    ///     f.set_max_depth(Some(2));
+    ///     f.value([[1], [2]])
+    /// });
+    /// assert_eq!(nested.to_string(), "[[1],[2]]");
+    ///
+    /// let too_deep = nojson::json(|f| {
+    ///     f.set_max_depth(Some(2));
+    ///     f.value([[[1]]])
+    /// });
+    /// assert!(write!(String::new(), "{too_deep}").is_err());
+    /// ```
+    pub fn set_max_depth(&mut self, depth: Option<usize>) {
+        self.update_settings(|s| s.max_depth = depth);
+    }
+
+    /// Returns whether the path of the value being formatted is tracked.
+    pub fn get_track_path(&self) -> bool {
+        self.settings.track_path
+    }
+
+    /// Sets whether the path of the value being formatted is tracked (see [`JsonFormatter::current_path()`]).
+    ///
+    /// Tracking has a small cost for each array element and object member, so it is disabled by default.
+    /// Only elements and members written while this setting is enabled appear in the path,
+    /// so it should be enabled before formatting the top-level value.
+    pub fn set_track_path(&mut self, enable: bool) {
+        self.update_settings(|s| s.track_path = enable);
+    }
+
+    /// Returns the depth beyond which arrays and objects are elided.
+    pub fn get_elide_depth(&self) -> Option<usize> {
+        self.settings.elide_depth
+    }
+
+    /// Sets the depth beyond which arrays and objects are elided.
+    ///
+    /// If `Some(depth)`, an array or object nested within `depth` other arrays or objects
+    /// is written as the string `"…"` instead of its content.
+    /// Together with [`JsonFormatter::set_elide_elements()`], this is useful for logging
+    /// large documents while keeping the output valid JSON.
+    /// If `None` (default), nothing is elided.
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.set_elide_depth(Some(2));
+    ///     f.value([[1, 2], [3, 4]])?;
+    ///     f.value([[[1]]])
+    /// });
+    /// assert_eq!(output.to_string(), r#"[[1,2],[3,4]][["…"]]"#);
+    /// ```
+    pub fn set_elide_depth(&mut self, depth: Option<usize>) {
+        self.update_settings(|s| s.elide_depth = depth);
+    }
+
+    /// Returns the number of elements and members kept in each array and object.
+    pub fn get_elide_elements(&self) -> Option<usize> {
+        self.settings.elide_elements
+    }
+
+    /// Sets the number of elements and members kept in each array and object.
+    ///
+    /// If `Some(count)`, only the first `count` elements (or members) of each array (or object)
+    /// are written, followed by the element `"…"` (or the member `"…": "…"`) if any were omitted.
+    /// Omitted values are not formatted at all.
+    /// If `None` (default), nothing is elided.
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.set_elide_elements(Some(2));
+    ///     f.object(|f| {
+    ///         f.member("ids", [1, 2, 3, 4])?;
+    ///         f.member("name", "foo")?;
+    ///         f.member("tags", ["a"])
+    ///     })
+    /// });
+    /// assert_eq!(output.to_string(), r#"{"ids":[1,2,"…"],"name":"foo","…":"…"}"#);
+    /// ```
+    pub fn set_elide_elements(&mut self, count: Option<usize>) {
+        self.update_settings(|s| s.elide_elements = count);
+    }
+
+    /// Returns the maximum number of characters written for each string value.
+    pub fn get_max_string_len(&self) -> Option<usize> {
+        self.settings.max_string_len
+    }
+
+    /// Sets the maximum number of characters written for each string value.
+    ///
+    /// If `Some(len)`, string values longer than `len` characters are cut after `len` characters
+    /// and `…` is appended to them. Escaping is applied after truncation, so the output remains
+    /// valid JSON. Member names are never truncated.
+    /// If `None` (default), strings are written in full.
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.set_max_string_len(Some(4));
+    ///     f.value(["abcd", "ab\"cdef"])
+    /// });
+    /// assert_eq!(output.to_string(), r#"["abcd","ab\"c…"]"#);
+    /// ```
+    pub fn set_max_string_len(&mut self, len: Option<usize>) {
+        self.update_settings(|s| s.max_string_len = len);
+    }
+
+    /// Returns whether object members are sorted by name.
+    pub fn get_sort_keys(&self) -> bool {
+        self.settings.sort_keys
+    }
+
+    /// Sets whether object members are sorted by name.
+    ///
+    /// Members are sorted by their names as written in the output (i.e., after
+    /// [`JsonFormatter::set_key_case()`] is applied), and members with the same name keep their order.
+    /// Since members can only be sorted once all of them have been formatted, each object is
+    /// rendered into a temporary buffer first instead of being streamed directly to the output.
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.set_sort_keys(true);
+    ///     f.object(|f| {
+    ///         f.member("b", [nojson::object(|f| {
+    ///             f.member("y", 1)?;
+    ///             f.member("x", 2)
+    ///         })])?;
+    ///         f.member("a", 3)
+    ///     })
+    /// });
+    /// assert_eq!(output.to_string(), r#"{"a":3,"b":[{"x":2,"y":1}]}"#);
+    /// ```
+    pub fn set_sort_keys(&mut self, enable: bool) {
+        self.update_settings(|s| s.sort_keys = enable);
+    }
+
+    /// Returns how nested values handle settings changes.
+    pub fn get_nested_settings(&self) -> NestedSettings {
+        self.settings.nested_settings
+    }
+
+    /// Sets how nested values handle settings changes.
+    ///
+    /// Values written by [`JsonFormatter::value()`] (including elements and members) always inherit
+    /// the current settings, but they may change them while formatting themselves,
+    /// e.g., a nested [`json()`](crate::json()) value calling [`JsonFormatter::set_indent_size()`].
+    /// See [`NestedSettings`] for the available modes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nojson::NestedSettings;
+    ///
+    /// let fragment = nojson::json(|f| {
+    ///     f.set_indent_size(0);
+    ///     f.value([1])
+    /// });
+    /// let format = |mode| {
+    ///     nojson::json(|f| {
+    ///         f.set_indent_size(2);
+    ///         f.set_nested_settings(mode);
+    ///         f.array(|f| {
+    ///             f.element(&fragment)?;
+    ///             f.element([2])
+    ///         })
+    ///     })
+    ///     .to_string()
+    /// };
+    ///
+    /// // The settings changed by `fragment` apply to its following siblings.
+    /// assert_eq!(format(NestedSettings::Propagate), "[\n  [1],[2]\n]");
+    ///
+    /// // The settings changed by `fragment` only apply to `fragment` itself.
+    /// assert_eq!(format(NestedSettings::Scoped), "[\n  [1],\n  [\n    2\n  ]\n]");
+    ///
+    /// // `fragment` cannot change the settings.
+    /// assert_eq!(
+    ///     format(NestedSettings::Inherit),
+    ///     "[\n  [\n    1\n  ],\n  [\n    2\n  ]\n]"
+    /// );
+    /// ```
+    pub fn set_nested_settings(&mut self, mode: NestedSettings) {
+        self.update_settings(|s| s.nested_settings = mode);
+    }
+
+    /// Returns the JSONPath-like path (e.g., `$.users[0].name`) of the value being formatted,
+    /// or `None` if path tracking is disabled (see [`JsonFormatter::set_track_path()`]).
+    ///
+    /// Member names appear as written in the output (e.g., after [`JsonFormatter::set_key_case()`] is applied).
+    /// This is useful for reporting which part of a large document a [`DisplayJson`]
+    /// implementation was formatting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::RefCell;
+    ///
+    /// let paths = RefCell::new(Vec::new());
+    /// let leaf = nojson::json(|f| {
+    ///     paths.borrow_mut().extend(f.current_path());
+    ///     f.value(0)
+    /// });
+    /// let output = nojson::json(|f| {
+    ///     f.set_track_path(true);
+    ///     f.object(|f| f.member("users", [&leaf, &leaf]))
+    /// });
+    /// assert_eq!(output.to_string(), r#"{"users":[0,0]}"#);
+    /// assert_eq!(*paths.borrow(), ["$.users[0]", "$.users[1]"]);
+    /// ```
+    pub fn current_path(&self) -> Option<String> {
+        if !self.settings.track_path {
+            return None;
+        }
+        let mut path = String::from("$");
+        for segment in &self.path {
+            match segment {
+                PathSegment::Index(i) => path.push_str(&format!("[{i}]")),
+                PathSegment::Name(name) => crate::raw::push_member_path(&mut path, name),
+            }
+        }
+        Some(path)
+    }
+
+    /// Returns whether arrays consisting only of numbers and booleans are kept on a single line when pretty-printing.
+    pub fn get_inline_numeric_arrays(&self) -> bool {
+        self.settings.inline_numeric_arrays
+    }
+
+    /// Sets whether arrays consisting only of numbers and booleans are kept on a single line when pretty-printing.
+    ///
+    /// This is useful for compact output of data such as coordinates or vectors.
+    /// Other arrays are laid out as usual (see also [`JsonFormatter::set_max_inline_width()`]).
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.set_indent_size(2);
+    ///     f.set_spacing(true);
+    ///     f.set_inline_numeric_arrays(true);
+    ///     f.value([[1.5, 2.0], [3.0, -4.25]])
+    /// });
+    /// assert_eq!(
+    ///     format!("\n{output}"),
+    ///     r#"
+    /// [
+    ///   [1.5, 2],
+    ///   [3, -4.25]
+    /// ]"#
+    /// );
+    /// ```
+    pub fn set_inline_numeric_arrays(&mut self, enable: bool) {
+        self.update_settings(|s| s.inline_numeric_arrays = enable);
+    }
+
+    /// Returns whether non-ASCII characters in strings are escaped.
+    pub fn get_escape_non_ascii(&self) -> bool {
+        self.settings.escape_non_ascii
+    }
+
+    /// Sets whether non-ASCII characters in strings (including member names) are escaped as `\uXXXX`.
+    ///
+    /// Characters outside the Basic Multilingual Plane are escaped as UTF-16 surrogate pairs.
+    /// This is useful for consumers that can only handle 7-bit ASCII text.
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.set_escape_non_ascii(true);
+    ///     f.value("café 😀")
+    /// });
+    /// assert_eq!(output.to_string(), r#""caf\u00e9 \ud83d\ude00""#);
+    /// ```
+    pub fn set_escape_non_ascii(&mut self, enable: bool) {
+        self.update_settings(|s| s.escape_non_ascii = enable);
+    }
+
+    /// Returns whether ANSI color escape sequences are written.
+    pub fn get_ansi_colors(&self) -> bool {
+        self.settings.ansi_colors
+    }
+
+    /// Sets whether tokens are decorated with ANSI color escape sequences for terminal output.
+    ///
+    /// When enabled, member names, strings, numbers, booleans, `null`, and punctuation
+    /// are written in different colors, similar to the output of `jq`.
+    /// Text written directly through [`JsonFormatter::inner_mut()`] or
+    /// [`JsonFormatter::raw_json()`] is not colored.
+    ///
+    /// Note that the output is no longer valid JSON when this setting is enabled.
+    /// Note also that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.set_ansi_colors(true);
+    ///     f.object(|f| f.member("ok", true))
+    /// });
+    /// assert_eq!(
+    ///     output.to_string(),
+    ///     "\x1b[1m{\x1b[0m\x1b[1;34m\"ok\"\x1b[0m\x1b[1m:\x1b[0m\x1b[33mtrue\x1b[0m\x1b[1m}\x1b[0m"
+    /// );
+    /// ```
+    pub fn set_ansi_colors(&mut self, enable: bool) {
+        self.update_settings(|s| s.ansi_colors = enable);
+    }
+
+    /// Returns whether object member names that are valid identifiers are written without quotes.
+    pub fn get_unquoted_keys(&self) -> bool {
+        self.settings.unquoted_keys
+    }
+
+    /// Sets whether object member names that are valid identifiers are written without quotes (JSON5).
+    ///
+    /// A name is written unquoted only if it is a non-empty ASCII identifier
+    /// (`[A-Za-z_$][A-Za-z0-9_$]*`); other names are quoted as usual.
+    ///
+    /// Note that the output is no longer valid JSON when this setting is enabled.
+    /// Note also that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.set_unquoted_keys(true);
+    ///     f.object(|f| {
+    ///         f.member("name", "Alice")?;
+    ///         f.member("first-name", "Alice")
+    ///     })
+    /// });
+    /// assert_eq!(output.to_string(), r#"{name:"Alice","first-name":"Alice"}"#);
+    /// ```
+    pub fn set_unquoted_keys(&mut self, enable: bool) {
+        self.update_settings(|s| s.unquoted_keys = enable);
+    }
+
+    /// Returns the naming convention that object member names are converted into, if any.
+    pub fn get_key_case(&self) -> Option<JsonKeyCase> {
+        self.settings.key_case
+    }
+
+    /// Sets the naming convention that object member names are converted into (see [`JsonKeyCase::convert()`]).
+    ///
+    /// This is useful to emit Rust field names (e.g., `user_id`) as the member names
+    /// expected by an API with another convention (e.g., `userId`).
+    /// `None` (the default) writes member names as they are.
+    ///
+    /// Note that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.set_key_case(Some(nojson::JsonKeyCase::CamelCase));
+    ///     f.object(|f| {
+    ///         f.member("user_id", 1)?;
+    ///         f.member_with("last_login", |f| f.object(|f| f.member("time_zone", "UTC")))
+    ///     })
+    /// });
+    /// assert_eq!(
+    ///     output.to_string(),
+    ///     r#"{"userId":1,"lastLogin":{"timeZone":"UTC"}}"#
+    /// );
+    /// ```
+    pub fn set_key_case(&mut self, case: Option<JsonKeyCase>) {
+        self.update_settings(|s| s.key_case = case);
+    }
+
+    /// Returns whether strings are written with single quotes.
+    pub fn get_single_quoted_strings(&self) -> bool {
+        self.settings.single_quoted_strings
+    }
+
+    /// Sets whether strings (including member names) are written with single quotes (JSON5).
+    ///
+    /// When enabled, `'` in string content is escaped as `\'`, and `"` is written as-is.
+    ///
+    /// Note that the output is no longer valid JSON when this setting is enabled.
+    /// Note also that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.set_single_quoted_strings(true);
+    ///     f.object(|f| f.member("quote", r#"it's "fine""#))
+    /// });
+    /// assert_eq!(output.to_string(), r#"{'quote':'it\'s "fine"'}"#);
+    /// ```
+    pub fn set_single_quoted_strings(&mut self, enable: bool) {
+        self.update_settings(|s| s.single_quoted_strings = enable);
+    }
+
+    /// Returns whether a trailing comma is written after the last element or member.
+    pub fn get_trailing_commas(&self) -> bool {
+        self.settings.trailing_commas
+    }
+
+    /// Sets whether a trailing comma is written after the last element or member of non-empty
+    /// arrays and objects (JSON5).
+    ///
+    /// Trailing commas are only written when pretty-printing (i.e., when the indent size is non-zero).
+    ///
+    /// Note that the output is no longer valid JSON when this setting is enabled.
+    /// Note also that this setting only affects the current and higher indentation levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.set_indent_size(2);
+    ///     f.set_spacing(true);
+    ///     f.set_trailing_commas(true);
+    ///     f.value([1, 2])
+    /// });
+    /// assert_eq!(output.to_string(), "[\n  1,\n  2,\n]");
+    /// ```
+    pub fn set_trailing_commas(&mut self, enable: bool) {
+        self.update_settings(|s| s.trailing_commas = enable);
+    }
+
+    pub(crate) fn float<T: JsonFloat>(&mut self, value: T) -> core::fmt::Result {
+        if value.is_finite() {
+            return self.finite_float(value);
+        }
+        match self.settings.non_finite_float {
+            NonFiniteFloat::Null => self.literal(JsonValueKind::Null, "null"),
+            NonFiniteFloat::String if value.is_nan() => self.string("NaN"),
+            NonFiniteFloat::String if value.is_sign_negative() => self.string("-Infinity"),
+            NonFiniteFloat::String => self.string("Infinity"),
+            NonFiniteFloat::Error => Err(core::fmt::Error),
+        }
+    }
+
+    fn finite_float<T: JsonFloat>(&mut self, value: T) -> core::fmt::Result {
+        if !self.settings.force_float_decimal_point && !self.settings.ansi_colors {
+            let settings = self.settings;
+            return write_finite_float(self.out(), value, settings);
+        }
+
+        let mut text = String::new();
+        write_finite_float(&mut text, value, self.settings)?;
+        let mantissa_end = text.find('e').unwrap_or(text.len());
+        if self.settings.force_float_decimal_point && !text[..mantissa_end].contains('.') {
+            text.insert_str(mantissa_end, ".0");
+        }
+        self.literal(JsonValueKind::Float, text)
+    }
+
+    // Changes the settings unless a nested value is being formatted in the `NestedSettings::Inherit` mode.
+    fn update_settings<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut JsonFormatOptions),
+    {
+        if !self.settings_locked {
+            f(&mut self.settings);
+        }
+    }
+
+    // Runs `f` and then restores the settings changed by it.
+    pub(crate) fn scoped<F>(&mut self, f: F) -> core::fmt::Result
+    where
+        F: FnOnce(&mut Self) -> core::fmt::Result,
+    {
+        let settings = self.settings;
+        let result = f(self);
+        self.settings = settings;
+        result
+    }
+
+    fn is_elided_depth(&self) -> bool {
+        self.settings
+            .elide_depth
+            .is_some_and(|depth| self.level >= depth)
+    }
+
+    fn check_depth(&self) -> core::fmt::Result {
+        if self.settings.max_depth.is_some_and(|max| self.level >= max) {
+            return Err(core::fmt::Error);
+        }
+        Ok(())
+    }
+
+    fn inline_width(&self) -> Option<usize> {
+        self.settings
+            .max_inline_width
+            .filter(|_| self.settings.indent_size > 0)
+    }
+
+    // Settings for rendering a value as compact, standard JSON
+    // so that it can be parsed back by `JsonObjectFormatter::flatten()`.
+    fn plain_compact_settings(&self) -> JsonFormatOptions {
+        JsonFormatOptions {
+            indent_size: 0,
+            unquoted_keys: false,
+            single_quoted_strings: false,
+            ansi_colors: false,
+            // The temporary formatter starts at level zero.
+            max_depth: self
+                .settings
+                .max_depth
+                .map(|max| max.saturating_sub(self.level)),
+            elide_depth: self
+                .settings
+                .elide_depth
+                .map(|depth| depth.saturating_sub(self.level)),
+            ..self.settings
+        }
+    }
+
+    // Formats a container whose layout depends on its content
+    // (e.g., whether its compact form fits in the maximum inline width) by recording it first.
+    fn record<F>(&mut self, f: F) -> core::fmt::Result
+    where
+        F: FnOnce(&mut Self) -> core::fmt::Result,
+    {
+        self.recording = Some(Recording::default());
+        let result = f(self);
+        let recording = self.recording.take().expect("infallible");
+        result?;
+
+        let mut marks = recording.marks.iter();
+        match marks.next() {
+            Some((start, Mark::Open(opening))) => {
+                let (container, _) =
+                    RecordedContainer::build(&recording.text, *opening, *start, &mut marks);
+                self.write_recorded(&container, false)
+            }
+            // The container has been elided.
+            _ => self.out().write_str(&recording.text),
+        }
+    }
+
+    // Writes a recorded container, on one line if `inline` is set or its own settings allow it.
+    fn write_recorded(
+        &mut self,
+        container: &RecordedContainer<'_>,
+        inline: bool,
+    ) -> core::fmt::Result {
+        let (settings, level) = (self.settings, self.level);
+        let opening = container.opening;
+        let inline = inline || container.is_inline();
+        self.settings = opening.settings;
+        if inline {
+            self.settings.indent_size = 0;
+        }
+        self.level = opening.level;
+
+        self.punctuation(if opening.object { "{" } else { "[" })?;
+        self.level += 1;
+        for (i, item) in container.items.iter().enumerate() {
+            self.separator(i, opening.object)?;
+            for part in &item.parts {
+                match part {
+                    RecordedPart::Text(text) => self.out().write_str(text)?,
+                    RecordedPart::Container(c) => self.write_recorded(c, inline)?,
+                }
+            }
+        }
+        self.level -= 1;
+        self.closing(opening.object, container.items.is_empty())?;
+
+        self.settings = settings;
+        self.level = level;
+        Ok(())
+    }
+
+    fn open_container(&mut self, object: bool) -> core::fmt::Result {
+        let prefix_width = core::mem::take(&mut self.prefix_width);
+        if let Some(recording) = &mut self.recording {
+            recording.push(Mark::Open(Opening {
+                object,
+                settings: self.settings,
+                level: self.level,
+                prefix_width,
+            }));
+            return Ok(());
+        }
+        self.punctuation(if object { "{" } else { "[" })
+    }
+
+    fn close_container(&mut self, object: bool, empty: bool) -> core::fmt::Result {
+        if let Some(recording) = &mut self.recording {
+            recording.push(Mark::Close);
+            return Ok(());
+        }
+        self.closing(object, empty)
+    }
+
+    // Starts the `index`-th element or member of the current container.
+    // `name` is the member name used for sorting.
+    fn begin_item(
+        &mut self,
+        index: usize,
+        object: bool,
+        name: Option<String>,
+    ) -> core::fmt::Result {
+        if let Some(recording) = &mut self.recording {
+            recording.push(Mark::Item(name));
+            return Ok(());
+        }
+        self.separator(index, object)
+    }
+
+    // Writes the comma, space, and line break preceding the `index`-th element or member.
+    fn separator(&mut self, index: usize, object: bool) -> core::fmt::Result {
+        let compact_spacing = self.settings.spacing && self.settings.indent_size == 0;
+        if index > 0 {
+            self.punctuation(",")?;
+            if compact_spacing {
+                write!(self.out(), " ")?;
+            }
+        } else if object && compact_spacing {
+            write!(self.out(), " ")?;
+        }
+        self.indent()
+    }
+
+    // Writes the end of a container, including its closing bracket or brace.
+    fn closing(&mut self, object: bool, empty: bool) -> core::fmt::Result {
+        if !empty {
+            if self.settings.indent_size > 0 {
+                self.trailing_comma()?;
+                self.indent()?;
+            } else if object && self.settings.spacing {
+                write!(self.out(), " ")?;
+            }
+        }
+        self.punctuation(if object { "}" } else { "]" })
+    }
+
+    fn trailing_comma(&mut self) -> core::fmt::Result {
+        if self.settings.trailing_commas && self.settings.indent_size > 0 {
+            self.punctuation(",")?;
+        }
+        Ok(())
+    }
+
+    // Writes a null, boolean, or number literal.
+    pub(crate) fn literal<T: Display>(
+        &mut self,
+        kind: JsonValueKind,
+        text: T,
+    ) -> core::fmt::Result {
+        let color = match kind {
+            JsonValueKind::Null => color::NULL,
+            JsonValueKind::Boolean => color::BOOLEAN,
+            _ => color::NUMBER,
+        };
+        self.start_color(color)?;
+        write!(self.out(), "{text}")?;
+        self.end_color()
+    }
+
+    fn punctuation(&mut self, s: &str) -> core::fmt::Result {
+        self.start_color(color::PUNCTUATION)?;
+        self.out().write_str(s)?;
+        self.end_color()
+    }
+
+    fn start_color(&mut self, color: &str) -> core::fmt::Result {
+        if self.settings.ansi_colors {
+            write!(self.out(), "\x1b[{color}m")?;
+        }
+        Ok(())
+    }
+
+    fn end_color(&mut self) -> core::fmt::Result {
+        if self.settings.ansi_colors {
+            write!(self.out(), "\x1b[0m")?;
+        }
+        Ok(())
+    }
+
+    // The destination of the output (see `JsonFormatter::record()`).
+    fn out(&mut self) -> &mut dyn Write {
+        match &mut self.recording {
+            Some(recording) => &mut recording.text,
+            None => self.inner,
+        }
+    }
+
+    fn indent(&mut self) -> core::fmt::Result {
+        if self.settings.indent_size > 0 {
+            let total = self.settings.indent_size * self.level;
+            let newline = if self.settings.crlf { "\r\n" } else { "\n" };
+            write!(self.out(), "{newline}{:total$}", "", total = total)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Debug for JsonFormatter<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("JsonFormatter")
+            .field("level", &self.level)
+            .field("indent_size", &self.settings.indent_size)
+            .field("spacing", &self.settings.spacing)
+            .field("non_finite_float", &self.settings.non_finite_float)
+            .field("float_precision", &self.settings.float_precision)
+            .field(
+                "float_positional_range",
+                &self.settings.float_positional_range,
+            )
+            .field(
+                "force_float_decimal_point",
+                &self.settings.force_float_decimal_point,
+            )
+            .field("unquoted_keys", &self.settings.unquoted_keys)
+            .field("key_case", &self.settings.key_case)
+            .field(
+                "single_quoted_strings",
+                &self.settings.single_quoted_strings,
+            )
+            .field("trailing_commas", &self.settings.trailing_commas)
+            .field("crlf", &self.settings.crlf)
+            .field("max_inline_width", &self.settings.max_inline_width)
+            .field("max_depth", &self.settings.max_depth)
+            .field("track_path", &self.settings.track_path)
+            .field("elide_depth", &self.settings.elide_depth)
+            .field("elide_elements", &self.settings.elide_elements)
+            .field("max_string_len", &self.settings.max_string_len)
+            .field(
+                "inline_numeric_arrays",
+                &self.settings.inline_numeric_arrays,
+            )
+            .field("escape_non_ascii", &self.settings.escape_non_ascii)
+            .field("ansi_colors", &self.settings.ansi_colors)
+            .field("sort_keys", &self.settings.sort_keys)
+            .field("nested_settings", &self.settings.nested_settings)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Policy for formatting non-finite floating-point numbers (NaN, `inf` and `-inf`).
+///
+/// JSON has no literal for these values, so [`JsonFormatter`] needs to choose a substitute.
+/// The policy is set via [`JsonFormatter::set_non_finite_float()`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NonFiniteFloat {
+    /// Emits `null` (default).
+    #[default]
+    Null,
+
+    /// Emits a JSON string: `"NaN"`, `"Infinity"`, or `"-Infinity"`.
+    ///
+    /// These are the names used by JavaScript and JSON5.
+    String,
+
+    /// Fails with [`core::fmt::Error`].
+    ///
+    /// Note that [`ToString::to_string()`] panics when formatting fails,
+    /// so use [`core::fmt::write()`] (or `write!`) if you need to handle this error.
+    Error,
+}
+
+/// How nested values handle settings changes.
+///
+/// The mode is set via [`JsonFormatter::set_nested_settings()`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NestedSettings {
+    /// Settings changed by a nested value remain in effect for the following values
+    /// in the enclosing container (default).
+    #[default]
+    Propagate,
+
+    /// Settings changed by a nested value are restored once it has been written.
+    Scoped,
+
+    /// Nested values cannot change the settings, so that they are formatted exactly like
+    /// the enclosing value: the setters and [`JsonFormatter::apply()`] have no effect while they are written.
+    ///
+    /// This also applies to adapters such as [`PrettyJson`](crate::PrettyJson) and [`CompactJson`](crate::CompactJson).
+    Inherit,
+}
+
+/// Precision used when formatting floating-point numbers.
+///
+/// The precision is set via [`JsonFormatter::set_float_precision()`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FloatPrecision {
+    /// The shortest representation that round-trips to the same value (default).
+    #[default]
+    Shortest,
+
+    /// A fixed number of digits after the decimal point (e.g., `1.50` for `Decimals(2)`).
+    Decimals(usize),
+
+    /// At most the given number of significant digits, rounding as needed.
+    ///
+    /// Trailing zeros after the decimal point are omitted, and `0` is treated as `1`.
+    SignificantDigits(usize),
+}
+
+// Formats JSON into a string using the given settings.
+fn render<F>(
+    settings: JsonFormatOptions,
+    path: &[PathSegment],
+    f: F,
+) -> Result<String, core::fmt::Error>
+where
+    F: FnOnce(&mut JsonFormatter<'_, '_>) -> core::fmt::Result,
+{
+    let mut text = String::new();
+    JsonFormatter::write_to(&mut text, |fmt| {
+        fmt.apply(&settings);
+        fmt.path.extend_from_slice(path);
+        f(fmt)
+    })?;
+    Ok(text)
+}
+
+// Output of a container formatted by `JsonFormatter::record()`.
+//
+// Containers are written to `text` without brackets, separators, or line breaks.
+// Their boundaries are kept in `marks` instead, so that their layout can be decided
+// once all of their content is known.
+#[derive(Debug, Default)]
+struct Recording {
+    text: String,
+    marks: Vec<(usize, Mark)>,
+}
+
+impl Recording {
+    fn push(&mut self, mark: Mark) {
+        self.marks.push((self.text.len(), mark));
+    }
+}
+
+#[derive(Debug)]
+enum Mark {
+    Open(Opening),
+    // The start of an element or member, with the member name if the object is sorted.
+    Item(Option<String>),
+    Close,
+}
+
+// The settings and position of a recorded container when it was opened.
+#[derive(Debug, Clone, Copy)]
+struct Opening {
+    object: bool,
+    settings: JsonFormatOptions,
+    level: usize,
+    prefix_width: usize,
+}
+
+struct RecordedContainer<'t> {
+    opening: Opening,
+    items: Vec<RecordedItem<'t>>,
+
+    // Display width of the container written on one line.
+    width: usize,
+}
+
+impl<'t> RecordedContainer<'t> {
+    // Builds the container opened at `start` from the marks following its `Open` mark,
+    // and returns it along with the position where it ends.
+    fn build(
+        text: &'t str,
+        opening: Opening,
+        start: usize,
+        marks: &mut core::slice::Iter<'_, (usize, Mark)>,
+    ) -> (Self, usize) {
+        let mut items: Vec<RecordedItem<'t>> = Vec::new();
+        let mut position = start;
+        let mut end = text.len();
+        while let Some((at, mark)) = marks.next() {
+            if position < *at {
+                current_item(&mut items).push_text(&text[position..*at]);
+            }
+            position = *at;
+            match mark {
+                Mark::Open(child) => {
+                    let (child, child_end) = Self::build(text, *child, *at, marks);
+                    let item = current_item(&mut items);
+                    item.width += child.width;
+                    item.parts.push(RecordedPart::Container(child));
+                    position = child_end;
+                }
+                Mark::Item(name) => items.push(RecordedItem {
+                    name: name.clone(),
+                    parts: Vec::new(),
+                    width: 0,
+                }),
+                Mark::Close => {
+                    end = *at;
+                    break;
+                }
+            }
+        }
+
+        let settings = opening.settings;
+        if opening.object && settings.sort_keys {
+            // The elision member has been recorded after the others (see `expanded_object()`).
+            let count = settings.elide_elements.filter(|&count| items.len() > count);
+            let elision = count.and_then(|_| items.pop());
+            items.sort_by(|a, b| a.name.cmp(&b.name));
+            if let Some(count) = count {
+                items.truncate(count);
+                items.extend(elision);
+            }
+        }
+
+        let spacing = usize::from(settings.spacing);
+        let mut width = 2 + items.iter().map(|item| item.width).sum::<usize>();
+        width += items.len().saturating_sub(1) * (1 + spacing);
+        if opening.object && !items.is_empty() {
+            width += 2 * spacing;
+        }
+        let container = Self {
+            opening,
+            items,
+            width,
+        };
+        (container, end)
+    }
+
+    fn is_inline(&self) -> bool {
+        let Opening {
+            object,
+            settings,
+            level,
+            prefix_width,
+        } = self.opening;
+        settings.indent_size == 0
+            || (settings.inline_numeric_arrays
+                && !object
+                && self.items.iter().all(RecordedItem::is_number_or_bool))
+            || settings
+                .max_inline_width
+                .is_some_and(|max| settings.indent_size * level + prefix_width + self.width <= max)
+    }
+}
+
+fn current_item<'a, 't>(items: &'a mut Vec<RecordedItem<'t>>) -> &'a mut RecordedItem<'t> {
+    if items.is_empty() {
+        items.push(RecordedItem {
+            name: None,
+            parts: Vec::new(),
+            width: 0,
+        });
+    }
+    items.last_mut().expect("infallible")
+}
+
+// An element or member of a recorded container (including the member name).
+struct RecordedItem<'t> {
+    name: Option<String>,
+    parts: Vec<RecordedPart<'t>>,
+    width: usize,
+}
+
+impl<'t> RecordedItem<'t> {
+    fn push_text(&mut self, text: &'t str) {
+        self.width += visible_chars(text).count();
+        self.parts.push(RecordedPart::Text(text));
+    }
+
+    fn is_number_or_bool(&self) -> bool {
+        let [RecordedPart::Text(text)] = self.parts.as_slice() else {
+            return false;
+        };
+        visible_chars(text)
+            .next()
+            .is_some_and(|c| c == '-' || c == 't' || c == 'f' || c.is_ascii_digit())
+    }
+}
 
-        if !empty {
-            self.indent()?;
-        }
-        write!(self.inner, "]")?;
+enum RecordedPart<'t> {
+    Text(&'t str),
+    Container(RecordedContainer<'t>),
+}
 
-        Ok(())
-    }
+// Iterates over the characters of `text` excluding ANSI escape sequences.
+fn visible_chars(text: &str) -> impl Iterator<Item = char> + '_ {
+    let mut chars = text.chars();
+    core::iter::from_fn(move || {
+        loop {
+            match chars.next()? {
+                '\x1b' => {
+                    chars.by_ref().find(|&c| c == 'm');
+                }
+                c => return Some(c),
+            }
+        }
+    })
+}
 
-    /// Creates a JSON object with the provided formatting function.
-    ///
-    /// This method starts a new JSON object and provides a [`JsonObjectFormatter`] to the callback
-    /// function for adding members to the object. It handles proper indentation, spacing, and
-    /// braces placement.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// let output = nojson::json(|f| {
-    ///     f.object(|f| {
-    ///         f.member("name", "Alice")?;
-    ///         f.member("age", 30)
-    ///     })
-    /// });
-    /// assert_eq!(output.to_string(), r#"{"name":"Alice","age":30}"#);
-    ///
-    /// // With pretty-printing
-    /// let pretty = nojson::json(|f| {
-    ///     f.set_indent_size(2);
-    ///     f.set_spacing(true);
-    ///     f.object(|f| {
-    ///         f.member("name", "Alice")?;
-    ///         f.member("age", 30)
-    ///     })
-    /// });
-    /// ```
-    pub fn object<F>(&mut self, f: F) -> core::fmt::Result
-    where
-        F: FnOnce(&mut JsonObjectFormatter<'a, 'b, '_>) -> core::fmt::Result,
-    {
-        write!(self.inner, "{{")?;
+// Splits a finite float into its sign, significant decimal digits (without trailing zeros),
+// and decimal exponent such that `|value| = d.ddd × 10^exp` where `dddd` are the digits.
+//
+// If `significant_digits` is zero, the shortest round-trip representation is used.
+fn decompose_float<T: JsonFloat>(value: T, significant_digits: usize) -> (bool, String, i32) {
+    let text = if significant_digits == 0 {
+        format!("{value:e}")
+    } else {
+        let n = significant_digits - 1;
+        format!("{value:.n$e}")
+    };
+    let (mantissa, exp) = text.split_once('e').expect("infallible");
+    let (negative, mantissa) = match mantissa.strip_prefix('-') {
+        Some(m) => (true, m),
+        None => (false, mantissa),
+    };
+    let mut digits = mantissa.replace('.', "");
+    let len = digits.trim_end_matches('0').len().max(1);
+    digits.truncate(len);
+    (negative, digits, exp.parse().expect("infallible"))
+}
 
-        let indent_size = self.indent_size;
-        let spacing = self.spacing;
-        self.level += 1;
-        let mut object = JsonObjectFormatter {
-            fmt: self,
-            empty: true,
+fn write_finite_float<W: Write + ?Sized, T: JsonFloat>(
+    w: &mut W,
+    value: T,
+    settings: JsonFormatOptions,
+) -> core::fmt::Result {
+    let precision = settings.float_precision;
+    let Some((min_exp, max_exp)) = settings.float_positional_range else {
+        return match precision {
+            FloatPrecision::Shortest => write!(w, "{value}"),
+            FloatPrecision::Decimals(n) => write!(w, "{value:.n$}"),
+            FloatPrecision::SignificantDigits(n) => {
+                let (negative, digits, exp) = decompose_float(value, n.max(1));
+                write_positional_float(w, negative, &digits, exp)
+            }
         };
-        f(&mut object)?;
-        let empty = object.empty;
-        self.level -= 1;
-        self.indent_size = indent_size;
-        self.spacing = spacing;
+    };
 
-        if !empty {
-            if self.indent_size > 0 {
-                self.indent()?;
-            } else if self.spacing {
-                write!(self.inner, " ")?;
+    let (negative, digits, exp) = match precision {
+        FloatPrecision::Shortest | FloatPrecision::Decimals(_) => decompose_float(value, 0),
+        FloatPrecision::SignificantDigits(n) => decompose_float(value, n.max(1)),
+    };
+    let positional = (min_exp..max_exp).contains(&exp);
+    match precision {
+        FloatPrecision::Decimals(n) if positional => write!(w, "{value:.n$}"),
+        FloatPrecision::Decimals(n) => write!(w, "{value:.n$e}"),
+        _ if positional => write_positional_float(w, negative, &digits, exp),
+        _ => {
+            let sign = if negative { "-" } else { "" };
+            let (head, tail) = digits.split_at(1);
+            if tail.is_empty() {
+                write!(w, "{sign}{head}e{exp}")
+            } else {
+                write!(w, "{sign}{head}.{tail}e{exp}")
             }
         }
-        write!(self.inner, "}}")?;
-
-        Ok(())
     }
+}
 
-    /// Returns a mutable reference to the inner [`core::fmt::Formatter`].
-    ///
-    /// This method provides direct access to the wrapped formatter, which can be useful
-    /// for implementing custom formatting logic for primitive types.
-    ///
-    /// # Note
-    ///
-    /// It is the responsibility of the user to ensure that the content written to the inner formatter is valid JSON.
-    pub fn inner_mut(&mut self) -> &mut core::fmt::Formatter<'b> {
-        self.inner
+fn write_positional_float<W: Write + ?Sized>(
+    w: &mut W,
+    negative: bool,
+    digits: &str,
+    exp: i32,
+) -> core::fmt::Result {
+    if negative {
+        w.write_str("-")?;
+    }
+    if exp < 0 {
+        let zeros = (-exp - 1) as usize;
+        return write!(w, "0.{:0<zeros$}{digits}", "");
     }
 
-    /// Returns the current indentation level.
-    ///
-    /// The indentation level increases when entering arrays and objects, and
-    /// decreases when exiting them.
-    pub fn get_level(&self) -> usize {
-        self.level
+    let int_len = exp as usize + 1;
+    if digits.len() <= int_len {
+        let zeros = int_len - digits.len();
+        write!(w, "{digits}{:0<zeros$}", "")
+    } else {
+        let (int_part, frac_part) = digits.split_at(int_len);
+        write!(w, "{int_part}.{frac_part}")
     }
+}
 
-    /// Returns the number of spaces used for each indentation level.
-    pub fn get_indent_size(&self) -> usize {
-        self.indent_size
+pub(crate) trait JsonFloat: Copy + Display + LowerExp {
+    fn is_finite(self) -> bool;
+    fn is_nan(self) -> bool;
+    fn is_sign_negative(self) -> bool;
+}
+
+impl JsonFloat for f32 {
+    fn is_finite(self) -> bool {
+        self.is_finite()
     }
 
-    /// Sets the number of spaces used for each indentation level.
-    ///
-    /// Note that this setting only affects the current and higher indentation levels.
-    pub fn set_indent_size(&mut self, size: usize) {
-        self.indent_size = size;
+    fn is_nan(self) -> bool {
+        self.is_nan()
     }
 
-    /// Returnes whether inserting a space after ':', ',', and '{'.
-    pub fn get_spacing(&self) -> bool {
-        self.spacing
+    fn is_sign_negative(self) -> bool {
+        self.is_sign_negative()
     }
+}
 
-    /// Sets whether inserting a space after ':', ',', and '{'.
-    ///
-    /// Note that this setting only affects the current and higher indentation levels.
-    pub fn set_spacing(&mut self, enable: bool) {
-        self.spacing = enable;
+impl JsonFloat for f64 {
+    fn is_finite(self) -> bool {
+        self.is_finite()
     }
 
-    fn indent(&mut self) -> core::fmt::Result {
-        if self.indent_size > 0 {
-            let total = self.indent_size * self.level;
-            write!(self.inner, "\n{:total$}", "", total = total)?;
-        }
-        Ok(())
+    fn is_nan(self) -> bool {
+        self.is_nan()
     }
-}
 
-impl core::fmt::Debug for JsonFormatter<'_, '_> {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("JsonFormatter")
-            .field("level", &self.level)
-            .field("indent_size", &self.indent_size)
-            .field("spacing", &self.spacing)
-            .finish_non_exhaustive()
+    fn is_sign_negative(self) -> bool {
+        self.is_sign_negative()
     }
 }
 
-struct JsonStringContentFormatter<'a, 'b> {
-    inner: &'a mut core::fmt::Formatter<'b>,
+struct JsonStringContentFormatter<'a> {
+    inner: &'a mut dyn Write,
+    single_quoted: bool,
+    escape_non_ascii: bool,
+    // Number of characters that may still be written (`None` means unlimited).
+    remaining: Option<usize>,
+    truncated: bool,
 }
 
-impl core::fmt::Write for JsonStringContentFormatter<'_, '_> {
-    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+impl core::fmt::Write for JsonStringContentFormatter<'_> {
+    fn write_str(&mut self, mut s: &str) -> core::fmt::Result {
+        if let Some(remaining) = &mut self.remaining {
+            if let Some((end, _)) = s.char_indices().nth(*remaining) {
+                s = &s[..end];
+                self.truncated = true;
+            }
+            *remaining -= s.chars().count();
+        }
         let bytes = s.as_bytes();
         let mut i = 0;
         while i < bytes.len() {
-            let skip = crate::swar::skip_plain_ascii_bytes(&bytes[i..]);
+            let mut skip = crate::swar::skip_plain_ascii_bytes(&bytes[i..]);
+            if self.single_quoted
+                && let Some(quote) = bytes[i..i + skip].iter().position(|&b| b == b'\'')
+            {
+                skip = quote;
+            }
             if skip > 0 {
                 self.inner.write_str(&s[i..i + skip])?;
                 i += skip;
@@ -286,14 +2094,24 @@ impl core::fmt::Write for JsonStringContentFormatter<'_, '_> {
             // also a UTF-8 boundary.
             if bytes[i] >= 0x80 {
                 let run = crate::swar::skip_non_ascii_bytes(&bytes[i..]);
-                self.inner.write_str(&s[i..i + run])?;
+                if self.escape_non_ascii {
+                    for c in s[i..i + run].chars() {
+                        for unit in c.encode_utf16(&mut [0; 2]) {
+                            write!(self.inner, "\\u{unit:04x}")?;
+                        }
+                    }
+                } else {
+                    self.inner.write_str(&s[i..i + run])?;
+                }
                 i += run;
                 continue;
             }
             // ASCII byte that needs escaping: ", \, or a control character.
             let b = bytes[i];
             match b {
+                b'"' if self.single_quoted => self.inner.write_str("\"")?,
                 b'"' => self.inner.write_str("\\\"")?,
+                b'\'' => self.inner.write_str("\\'")?,
                 b'\\' => self.inner.write_str("\\\\")?,
                 b'\n' => self.inner.write_str("\\n")?,
                 b'\r' => self.inner.write_str("\\r")?,
@@ -337,7 +2155,7 @@ impl core::fmt::Write for JsonStringContentFormatter<'_, '_> {
 /// ```
 pub struct JsonArrayFormatter<'a, 'b, 'c> {
     fmt: &'c mut JsonFormatter<'a, 'b>,
-    empty: bool,
+    len: usize,
 }
 
 impl JsonArrayFormatter<'_, '_, '_> {
@@ -358,15 +2176,87 @@ impl JsonArrayFormatter<'_, '_, '_> {
     /// });
     /// ```
     pub fn element<T: DisplayJson>(&mut self, element: T) -> core::fmt::Result {
-        if !self.empty {
-            write!(self.fmt.inner, ",")?;
-            if self.fmt.spacing && self.fmt.indent_size == 0 {
-                write!(self.fmt.inner, " ")?;
+        if let Some(count) = self.fmt.settings.elide_elements
+            && self.len >= count
+        {
+            if self.len > count {
+                self.len += 1;
+                return Ok(());
             }
+            return self.push_element(ELISION_MARKER);
+        }
+        self.push_element(element)
+    }
+
+    fn push_element<T: DisplayJson>(&mut self, element: T) -> core::fmt::Result {
+        self.fmt.begin_item(self.len, false, None)?;
+        let track_path = self.fmt.settings.track_path;
+        if track_path {
+            self.fmt.path.push(PathSegment::Index(self.len));
         }
-        self.fmt.indent()?;
         self.fmt.value(element)?;
-        self.empty = false;
+        if track_path {
+            self.fmt.path.pop();
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Adds an element to the JSON array that is written by the given formatting function.
+    ///
+    /// This is shorthand for `element(nojson::json_once(f))`, which allows nested
+    /// structures to be emitted inline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.array(|f| {
+    ///         f.element(1)?;
+    ///         f.element_with(|f| f.object(|f| f.member("a", 2)))
+    ///     })
+    /// });
+    /// assert_eq!(output.to_string(), r#"[1,{"a":2}]"#);
+    /// ```
+    pub fn element_with<F>(&mut self, f: F) -> core::fmt::Result
+    where
+        F: FnOnce(&mut JsonFormatter<'_, '_>) -> core::fmt::Result,
+    {
+        self.element(crate::json_once(f))
+    }
+
+    /// Adds an already-serialized JSON text as an element of the JSON array.
+    ///
+    /// See [`JsonFormatter::raw_json()`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| f.array(|f| f.raw_element(r#"{"a":1}"#)));
+    /// assert_eq!(output.to_string(), r#"[{"a":1}]"#);
+    /// ```
+    pub fn raw_element(&mut self, text: &str) -> core::fmt::Result {
+        self.element(crate::json(|f| f.raw_json(text)))
+    }
+
+    /// Adds a single element to the JSON array only if `condition` is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.array(|f| {
+    ///         f.element_if(false, 0)?;
+    ///         f.element(1)?;
+    ///         f.element_if(true, 2)
+    ///     })
+    /// });
+    /// assert_eq!(output.to_string(), "[1,2]");
+    /// ```
+    pub fn element_if<T: DisplayJson>(&mut self, condition: bool, element: T) -> core::fmt::Result {
+        if condition {
+            self.element(element)?;
+        }
         Ok(())
     }
 
@@ -399,6 +2289,48 @@ impl JsonArrayFormatter<'_, '_, '_> {
         }
         Ok(())
     }
+
+    /// Adds multiple elements to the JSON array from an iterator of [`Result`]s.
+    ///
+    /// If the iterator yields an error, formatting is aborted: the error is stored in `error`
+    /// and [`core::fmt::Error`] is returned, since [`core::fmt::Error`] cannot carry details.
+    /// The caller can then inspect `error` to find out why formatting failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    ///
+    /// let rows = ["1", "2", "x"];
+    /// let error = Cell::new(None);
+    /// let output = nojson::json(|f| {
+    ///     f.array(|f| f.try_elements(rows.iter().map(|s| s.parse::<u32>()), &error))
+    /// });
+    ///
+    /// let mut text = String::new();
+    /// assert!(std::fmt::write(&mut text, format_args!("{output}")).is_err());
+    /// assert!(error.take().is_some());
+    /// ```
+    pub fn try_elements<I, T, E>(
+        &mut self,
+        elements: I,
+        error: &Cell<Option<E>>,
+    ) -> core::fmt::Result
+    where
+        I: IntoIterator<Item = Result<T, E>>,
+        T: DisplayJson,
+    {
+        for element in elements {
+            match element {
+                Ok(element) => self.element(element)?,
+                Err(e) => {
+                    error.set(Some(e));
+                    return Err(core::fmt::Error);
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 /// A formatter for JSON objects.
@@ -430,7 +2362,10 @@ impl JsonArrayFormatter<'_, '_, '_> {
 /// ```
 pub struct JsonObjectFormatter<'a, 'b, 'c> {
     fmt: &'c mut JsonFormatter<'a, 'b>,
-    empty: bool,
+    len: usize,
+
+    // Whether the members are sorted (and elided) once they have all been recorded.
+    sorted: bool,
 }
 
 impl JsonObjectFormatter<'_, '_, '_> {
@@ -455,23 +2390,200 @@ impl JsonObjectFormatter<'_, '_, '_> {
         N: Display,
         V: DisplayJson,
     {
-        if !self.empty {
-            write!(self.fmt.inner, ",")?;
-            if self.fmt.spacing && self.fmt.indent_size == 0 {
-                write!(self.fmt.inner, " ")?;
+        if !self.sorted
+            && let Some(count) = self.fmt.settings.elide_elements
+            && self.len >= count
+        {
+            if self.len > count {
+                self.len += 1;
+                return Ok(());
             }
-        } else if self.fmt.spacing && self.fmt.indent_size == 0 {
-            write!(self.fmt.inner, " ")?;
+            return self.push_member(ELISION_MARKER, ELISION_MARKER);
         }
+        self.push_member(name, value)
+    }
 
-        self.fmt.indent()?;
-        self.fmt.string(name)?;
-        write!(self.fmt.inner, ":")?;
-        if self.fmt.spacing {
-            write!(self.fmt.inner, " ")?;
+    fn push_member<N, V>(&mut self, name: N, value: V) -> core::fmt::Result
+    where
+        N: Display,
+        V: DisplayJson,
+    {
+        let mut prefix_width = 0;
+        let track_path = self.fmt.settings.track_path;
+        if self.fmt.settings.unquoted_keys
+            || self.fmt.inline_width().is_some()
+            || self.fmt.settings.key_case.is_some()
+            || track_path
+            || self.fmt.recording.is_some()
+        {
+            let mut name = format!("{name}");
+            if let Some(case) = self.fmt.settings.key_case {
+                name = case.convert(&name);
+            }
+            let sort_name = self.sorted.then(|| name.clone());
+            self.fmt.begin_item(self.len, true, sort_name)?;
+            if track_path {
+                self.fmt.path.push(PathSegment::Name(name.clone()));
+            }
+            let unquoted = self.fmt.settings.unquoted_keys && is_identifier(&name);
+            prefix_width = name.chars().count() + if unquoted { 1 } else { 3 };
+            if unquoted {
+                self.fmt.start_color(color::KEY)?;
+                self.fmt.out().write_str(&name)?;
+                self.fmt.end_color()?;
+            } else {
+                self.fmt.quoted(name, color::KEY)?;
+            }
+        } else {
+            self.fmt.begin_item(self.len, true, None)?;
+            self.fmt.quoted(name, color::KEY)?;
+        }
+        self.fmt.punctuation(":")?;
+        if self.fmt.settings.spacing {
+            write!(self.fmt.out(), " ")?;
+            prefix_width += 1;
         }
+        self.fmt.prefix_width = prefix_width;
         self.fmt.value(value)?;
-        self.empty = false;
+        self.fmt.prefix_width = 0;
+        if track_path {
+            self.fmt.path.pop();
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Adds a member to the JSON object whose value is written by the given formatting function.
+    ///
+    /// This is shorthand for `member(name, nojson::json_once(f))`, which allows nested
+    /// structures to be emitted inline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| {
+    ///     f.object(|f| {
+    ///         f.member_with("user", |f| {
+    ///             f.object(|f| f.member("name", "Alice"))
+    ///         })
+    ///     })
+    /// });
+    /// assert_eq!(output.to_string(), r#"{"user":{"name":"Alice"}}"#);
+    /// ```
+    pub fn member_with<N, F>(&mut self, name: N, f: F) -> core::fmt::Result
+    where
+        N: Display,
+        F: FnOnce(&mut JsonFormatter<'_, '_>) -> core::fmt::Result,
+    {
+        self.member(name, crate::json_once(f))
+    }
+
+    /// Adds a member whose value is an already-serialized JSON text.
+    ///
+    /// See [`JsonFormatter::raw_json()`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = nojson::json(|f| f.object(|f| f.raw_member("cached", "[1,2]")));
+    /// assert_eq!(output.to_string(), r#"{"cached":[1,2]}"#);
+    /// ```
+    pub fn raw_member<N: Display>(&mut self, name: N, text: &str) -> core::fmt::Result {
+        self.member(name, crate::json(|f| f.raw_json(text)))
+    }
+
+    /// Adds a name-value pair (member) to the JSON object only if `condition` is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let verbose = false;
+    /// let output = nojson::json(|f| {
+    ///     f.object(|f| {
+    ///         f.member("name", "Alice")?;
+    ///         f.member_if(verbose, "details", [1, 2, 3])
+    ///     })
+    /// });
+    /// assert_eq!(output.to_string(), r#"{"name":"Alice"}"#);
+    /// ```
+    pub fn member_if<N, V>(&mut self, condition: bool, name: N, value: V) -> core::fmt::Result
+    where
+        N: Display,
+        V: DisplayJson,
+    {
+        if condition {
+            self.member(name, value)?;
+        }
+        Ok(())
+    }
+
+    /// Adds a name-value pair (member) to the JSON object only if the value is `Some`.
+    ///
+    /// Unlike [`JsonObjectFormatter::member()`] with an `Option` value, which writes `null` for `None`,
+    /// this method omits the member entirely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let email: Option<String> = None;
+    /// let nickname = Some("Al");
+    /// let output = nojson::json(|f| {
+    ///     f.object(|f| {
+    ///         f.member("name", "Alice")?;
+    ///         f.member_if_some("email", email.as_ref())?;
+    ///         f.member_if_some("nickname", nickname)
+    ///     })
+    /// });
+    /// assert_eq!(output.to_string(), r#"{"name":"Alice","nickname":"Al"}"#);
+    /// ```
+    pub fn member_if_some<N, V>(&mut self, name: N, value: Option<V>) -> core::fmt::Result
+    where
+        N: Display,
+        V: DisplayJson,
+    {
+        if let Some(value) = value {
+            self.member(name, value)?;
+        }
+        Ok(())
+    }
+
+    /// Adds all members of another JSON object to this object.
+    ///
+    /// `value` is formatted (using the current float-related settings) and its members are
+    /// spliced into this object, as if each of them had been added with [`JsonObjectFormatter::member()`].
+    /// If `value` is not formatted as a JSON object, [`core::fmt::Error`] is returned.
+    ///
+    /// Note that members are not deduplicated, so a name that appears both in `value` and
+    /// in this object is written twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let header = nojson::object(|f| {
+    ///     f.member("version", 1)?;
+    ///     f.member("status", "ok")
+    /// });
+    /// let output = nojson::json(|f| {
+    ///     f.object(|f| {
+    ///         f.flatten(&header)?;
+    ///         f.member("data", [1, 2])
+    ///     })
+    /// });
+    /// assert_eq!(output.to_string(), r#"{"version":1,"status":"ok","data":[1,2]}"#);
+    ///
+    /// // Non-object values cannot be flattened.
+    /// let output = nojson::json(|f| f.object(|f| f.flatten([1, 2])));
+    /// assert!(std::fmt::write(&mut String::new(), format_args!("{output}")).is_err());
+    /// ```
+    pub fn flatten<T: DisplayJson>(&mut self, value: T) -> core::fmt::Result {
+        let settings = self.fmt.plain_compact_settings();
+        let text = render(settings, &self.fmt.path, |f| value.fmt(f))?;
+        let json = crate::RawJson::parse(&text).map_err(|_| core::fmt::Error)?;
+        let members = json.value().to_object().map_err(|_| core::fmt::Error)?;
+        for (name, value) in members {
+            let name = name.to_unquoted_string_str().expect("infallible");
+            self.member(name, value)?;
+        }
         Ok(())
     }
 
@@ -505,3 +2617,11 @@ impl JsonObjectFormatter<'_, '_, '_> {
         Ok(())
     }
 }
+
+pub(crate) fn is_identifier(s: &str) -> bool {
+    let mut bytes = s.bytes();
+    bytes
+        .next()
+        .is_some_and(|b| b.is_ascii_alphabetic() || b == b'_' || b == b'$')
+        && bytes.all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'$')
+}
@@ -0,0 +1,128 @@
+use alloc::vec::Vec;
+use core::{num::NonZeroUsize, ops::Range};
+
+use crate::{JsonParseError, RawJson};
+
+/// A parsed JSONC (JSON with Comments) document.
+///
+/// This is returned by [`RawJson::parse_jsonc_document()`] and provides the parsed JSON
+/// together with detailed information about each comment in the original text.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{JsoncCommentKind, RawJson};
+///
+/// let text = r#"{
+///     "name": "John", // The user name
+///     /* Age in years */ "age": 30
+/// }"#;
+/// let doc = RawJson::parse_jsonc_document(text)?;
+///
+/// let age: u32 = doc.json().value().to_member("age")?.required()?.try_into()?;
+/// assert_eq!(age, 30);
+///
+/// let comments: Vec<_> = doc.comments().collect();
+/// assert_eq!(comments[0].kind(), JsoncCommentKind::Line);
+/// assert_eq!(comments[0].content(), " The user name");
+/// assert_eq!(comments[0].line().get(), 2);
+/// assert_eq!(comments[1].kind(), JsoncCommentKind::Block);
+/// assert_eq!(comments[1].text(), "/* Age in years */");
+/// assert_eq!(comments[1].line().get(), 3);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsoncDocument<'text> {
+    json: RawJson<'text>,
+    comments: Vec<Range<usize>>,
+}
+
+impl<'text> JsoncDocument<'text> {
+    pub(crate) fn parse(text: &'text str) -> Result<Self, JsonParseError> {
+        let (json, comments) = RawJson::parse_jsonc(text)?;
+        Ok(Self { json, comments })
+    }
+
+    /// Returns the parsed JSON.
+    pub fn json(&self) -> &RawJson<'text> {
+        &self.json
+    }
+
+    /// Converts this document into the parsed JSON, discarding the comment information.
+    pub fn into_json(self) -> RawJson<'text> {
+        self.json
+    }
+
+    /// Returns an iterator over the comments in the order they appear in the text.
+    pub fn comments(&self) -> impl '_ + Iterator<Item = JsoncComment<'text>> {
+        let text = self.json.text();
+        self.comments.iter().map(move |range| JsoncComment {
+            text,
+            range: range.clone(),
+        })
+    }
+
+    /// Returns the byte ranges of the comments, as returned by [`RawJson::parse_jsonc()`].
+    pub fn comment_ranges(&self) -> &[Range<usize>] {
+        &self.comments
+    }
+}
+
+/// The kind of a comment in a JSONC document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsoncCommentKind {
+    /// A line comment (`// ...`).
+    Line,
+
+    /// A block comment (`/* ... */`).
+    Block,
+}
+
+/// A comment in a JSONC document (see [`JsoncDocument::comments()`]).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JsoncComment<'text> {
+    text: &'text str,
+    range: Range<usize>,
+}
+
+impl<'text> JsoncComment<'text> {
+    /// Returns the kind of this comment.
+    pub fn kind(&self) -> JsoncCommentKind {
+        if self.text().starts_with("//") {
+            JsoncCommentKind::Line
+        } else {
+            JsoncCommentKind::Block
+        }
+    }
+
+    /// Returns the text of this comment, including the comment delimiters.
+    ///
+    /// The trailing newline of a line comment is not included.
+    pub fn text(&self) -> &'text str {
+        &self.text[self.range.clone()]
+    }
+
+    /// Returns the text of this comment, excluding the comment delimiters.
+    ///
+    /// For a line comment ending with `\r\n`, the trailing `\r` is also excluded.
+    pub fn content(&self) -> &'text str {
+        let text = self.text();
+        match self.kind() {
+            JsoncCommentKind::Line => text[2..].strip_suffix('\r').unwrap_or(&text[2..]),
+            JsoncCommentKind::Block => &text[2..text.len() - 2],
+        }
+    }
+
+    /// Returns the byte range of this comment in the original text.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the (1-based) line number where this comment starts.
+    pub fn line(&self) -> NonZeroUsize {
+        let line = self.text[..self.range.start].matches('\n').count();
+        NonZeroUsize::MIN.saturating_add(line)
+    }
+}
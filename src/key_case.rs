@@ -0,0 +1,122 @@
+use alloc::string::String;
+
+/// A naming convention for object member names.
+///
+/// [`RawJsonValue::to_member_as()`](crate::RawJsonValue::to_member_as) uses a naming convention
+/// to look up members, so Rust field names (e.g., `user_id`) can be used with JSON objects that
+/// follow another convention (e.g., `userId`) without spelling out every JSON name.
+/// [`JsonKeyCase::convert()`] maps names between conventions explicitly.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{JsonKeyCase, RawJson};
+///
+/// let json = RawJson::parse(r#"{"userId": 42, "displayName": "Alice"}"#)?;
+/// let user = json.value();
+///
+/// let user_id: u32 = user.to_member_as("user_id", JsonKeyCase::CamelCase)?.required()?.try_into()?;
+/// assert_eq!(user_id, 42);
+///
+/// // Names that do not follow the convention can be looked up with `to_member()`.
+/// let name: String = user.to_member("displayName")?.required()?.try_into()?;
+/// assert_eq!(name, "Alice");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum JsonKeyCase {
+    /// `camelCase`.
+    CamelCase,
+
+    /// `PascalCase`.
+    PascalCase,
+
+    /// `snake_case`.
+    SnakeCase,
+
+    /// `kebab-case`.
+    KebabCase,
+}
+
+impl JsonKeyCase {
+    /// Converts `name` into this naming convention.
+    ///
+    /// The name is split into words at `_` and `-` characters and before each uppercase letter
+    /// that follows a lowercase letter or a digit, so names in any of the supported conventions
+    /// can be converted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nojson::JsonKeyCase;
+    ///
+    /// assert_eq!(JsonKeyCase::CamelCase.convert("max_retry_count"), "maxRetryCount");
+    /// assert_eq!(JsonKeyCase::PascalCase.convert("max_retry_count"), "MaxRetryCount");
+    /// assert_eq!(JsonKeyCase::SnakeCase.convert("maxRetryCount"), "max_retry_count");
+    /// assert_eq!(JsonKeyCase::KebabCase.convert("MaxRetryCount"), "max-retry-count");
+    /// ```
+    pub fn convert(self, name: &str) -> String {
+        self.convert_chars(name).collect()
+    }
+
+    // Yields the characters of `convert(name)` without allocating.
+    pub(crate) fn convert_chars(self, name: &str) -> impl Iterator<Item = char> + '_ {
+        split_words(name).enumerate().flat_map(move |(i, word)| {
+            let separator = match self {
+                Self::SnakeCase if i > 0 => Some('_'),
+                Self::KebabCase if i > 0 => Some('-'),
+                _ => None,
+            };
+            let upper_first = match self {
+                Self::SnakeCase | Self::KebabCase => false,
+                Self::CamelCase => i > 0,
+                Self::PascalCase => true,
+            };
+            separator
+                .into_iter()
+                .chain(word.chars().enumerate().flat_map(move |(j, c)| {
+                    let (upper, lower) = if j == 0 && upper_first {
+                        (Some(c.to_uppercase()), None)
+                    } else {
+                        (None, Some(c.to_lowercase()))
+                    };
+                    upper
+                        .into_iter()
+                        .flatten()
+                        .chain(lower.into_iter().flatten())
+                }))
+        })
+    }
+}
+
+fn split_words(name: &str) -> impl Iterator<Item = &str> {
+    let mut chars = name.char_indices().peekable();
+    let mut prev: Option<char> = None;
+    core::iter::from_fn(move || {
+        // Skip separators.
+        while let Some(&(_, c)) = chars.peek() {
+            if c != '_' && c != '-' {
+                break;
+            }
+            prev = Some(c);
+            chars.next();
+        }
+        let &(start, _) = chars.peek()?;
+        while let Some(&(i, c)) = chars.peek() {
+            if c == '_' || c == '-' {
+                return Some(&name[start..i]);
+            }
+            if c.is_uppercase()
+                && prev.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit())
+                && start < i
+            {
+                return Some(&name[start..i]);
+            }
+            prev = Some(c);
+            chars.next();
+        }
+        Some(&name[start..])
+    })
+}
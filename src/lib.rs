@@ -220,22 +220,68 @@
 
 extern crate alloc;
 
+mod adapters;
+mod arena;
+mod compare;
 mod display_json;
+#[cfg(feature = "std")]
+mod file;
 mod format;
+mod jsonc;
+mod key_case;
 mod kind;
+#[cfg(feature = "std")]
+mod minify;
 mod parse;
 mod parse_error;
 mod raw;
+#[cfg(feature = "schema")]
+mod schema;
+mod shape;
+#[cfg(target_has_atomic = "ptr")]
+mod shared;
+#[cfg(feature = "std")]
+mod stream;
 mod swar;
+#[cfg(feature = "std")]
+mod time;
 mod try_from_impls;
+mod unflatten;
+pub mod validate;
 
 use core::{fmt::Display, str::FromStr};
 
+#[cfg(feature = "std")]
+pub use adapters::EnvExpanded;
+pub use adapters::{
+    Base64, EmptyStringAsNone, HexBytes, JsonStringOf, LazyArray, Lenient, NullAsDefault,
+    NumberAsString, OneOrMany, Redacted, StringOrNumber,
+};
+pub use arena::JsonArena;
+pub use compare::{assert_json_eq, json_eq};
 pub use display_json::DisplayJson;
-pub use format::{JsonArrayFormatter, JsonFormatter, JsonObjectFormatter};
+#[cfg(feature = "std")]
+pub use file::{JsonFileError, JsonIncludeResolver};
+pub use format::{
+    FloatPrecision, JsonArrayFormatter, JsonFormatOptions, JsonFormatter, JsonObjectFormatter,
+    NestedSettings, NonFiniteFloat,
+};
+pub use jsonc::{JsoncComment, JsoncCommentKind, JsoncDocument};
+pub use key_case::JsonKeyCase;
 pub use kind::JsonValueKind;
-pub use raw::RawJsonMember;
+#[cfg(feature = "std")]
+pub use minify::minify_stream;
+pub use parse_error::JsonErrorCode;
+pub use raw::{DuplicateMemberPolicy, RawJsonMember, RawJsonObjectIndex};
 pub use raw::{JsonParseError, RawJson, RawJsonOwned, RawJsonValue};
+#[cfg(feature = "schema")]
+pub use schema::JsonSchema;
+pub use shape::JsonShape;
+#[cfg(target_has_atomic = "ptr")]
+pub use shared::{RawJsonShared, RawJsonSharedValue};
+#[cfg(feature = "std")]
+pub use time::{EpochMillis, EpochSeconds, Iso8601};
+pub use unflatten::{UnflattenError, unflatten};
 
 /// A marker struct that enables JSON parsing and generation through the [`FromStr`] and [`Display`] traits.
 ///
@@ -290,6 +336,102 @@ where
     }
 }
 
+/// Similar to [`Json`], but always generates pretty-printed JSON.
+///
+/// By default (see [`PrettyJson::new()`]), the value is written with 2-space indentation and spacing,
+/// and all other settings are inherited from the enclosing formatter.
+/// [`PrettyJson::with_options()`] uses the given options instead.
+///
+/// Unlike [`json()`], this wrapper can be easily stored in structs or passed to APIs that accept
+/// `impl Display`. When used as a [`DisplayJson`] value inside other JSON, the settings are applied
+/// only to the wrapped value.
+///
+/// # Examples
+///
+/// ```
+/// let value = nojson::PrettyJson::new([1, 2]);
+/// assert_eq!(value.to_string(), "[\n  1,\n  2\n]");
+///
+/// let nested = nojson::Json([nojson::PrettyJson::new([1]), nojson::PrettyJson::new([2])]);
+/// assert_eq!(nested.to_string(), "[[\n    1\n  ],[\n    2\n  ]]");
+///
+/// let options = nojson::JsonFormatOptions::new().indent_size(4).spacing(true);
+/// let value = nojson::PrettyJson::with_options([1], options);
+/// assert_eq!(value.to_string(), "[\n    1\n]");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PrettyJson<T>(
+    /// The value to format.
+    pub T,
+    /// The options to format the value with (`None` for 2-space indentation with spacing).
+    pub Option<JsonFormatOptions>,
+);
+
+impl<T> PrettyJson<T> {
+    /// Makes a new [`PrettyJson`] that writes `value` with 2-space indentation and spacing.
+    pub const fn new(value: T) -> Self {
+        Self(value, None)
+    }
+
+    /// Makes a new [`PrettyJson`] that writes `value` with `options`.
+    ///
+    /// Note that `options` replace all the settings of the enclosing formatter (see [`JsonFormatter::apply()`]).
+    pub const fn with_options(value: T, options: JsonFormatOptions) -> Self {
+        Self(value, Some(options))
+    }
+}
+
+impl<T: DisplayJson> Display for PrettyJson<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", Json(self))
+    }
+}
+
+impl<T: DisplayJson> DisplayJson for PrettyJson<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        f.scoped(|f| {
+            if let Some(options) = &self.1 {
+                f.apply(options);
+            } else {
+                f.set_indent_size(2);
+                f.set_spacing(true);
+            }
+            self.0.fmt(f)
+        })
+    }
+}
+
+/// Similar to [`Json`], but always generates compact JSON, even when nested in pretty-printed JSON.
+///
+/// # Examples
+///
+/// ```
+/// let value = nojson::json(|f| {
+///     f.set_indent_size(2);
+///     f.set_spacing(true);
+///     f.value([nojson::CompactJson([1, 2])])
+/// });
+/// assert_eq!(value.to_string(), "[\n  [1,2]\n]");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CompactJson<T>(#[allow(missing_docs)] pub T);
+
+impl<T: DisplayJson> Display for CompactJson<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", Json(self))
+    }
+}
+
+impl<T: DisplayJson> DisplayJson for CompactJson<T> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        f.scoped(|f| {
+            f.set_indent_size(0);
+            f.set_spacing(false);
+            self.0.fmt(f)
+        })
+    }
+}
+
 /// Similiar to [`Json`], but can be used for pretty-printing and in-place JSON generation purposes.
 ///
 /// # Examples
@@ -377,6 +519,78 @@ where
     }
 }
 
+/// Similar to [`json()`], but applies `options` to the formatter before calling `f`.
+///
+/// This is shorthand for calling [`JsonFormatter::apply()`] at the start of the closure.
+///
+/// # Examples
+///
+/// ```
+/// let pretty = nojson::JsonFormatOptions::new()
+///     .indent_size(2)
+///     .spacing(true)
+///     .sort_keys(true);
+/// let output = nojson::json_with(&pretty, |f| {
+///     f.object(|f| {
+///         f.member("b", 1)?;
+///         f.member("a", [2])
+///     })
+/// });
+/// assert_eq!(output.to_string(), "{\n  \"a\": [\n    2\n  ],\n  \"b\": 1\n}");
+/// ```
+pub fn json_with<F>(options: &JsonFormatOptions, f: F) -> impl DisplayJson + Display + use<F>
+where
+    F: Fn(&mut JsonFormatter<'_, '_>) -> core::fmt::Result,
+{
+    let options = *options;
+    json(move |fmt| {
+        fmt.apply(&options);
+        f(fmt)
+    })
+}
+
+/// Similar to [`json()`], but accepts an [`FnOnce`] closure.
+///
+/// This is useful when the closure needs to consume captured values, such as an iterator.
+/// Since the closure can only be called once, the returned value can be formatted only once;
+/// subsequent attempts return [`core::fmt::Error`].
+///
+/// # Examples
+///
+/// ```
+/// let names = vec!["foo".to_owned(), "bar".to_owned()];
+/// let output = nojson::json_once(move |f| {
+///     f.array(|f| f.elements(names.into_iter().map(|name| name.to_uppercase())))
+/// });
+/// assert_eq!(output.to_string(), r#"["FOO","BAR"]"#);
+/// ```
+pub fn json_once<F>(f: F) -> impl DisplayJson + Display
+where
+    F: FnOnce(&mut JsonFormatter<'_, '_>) -> core::fmt::Result,
+{
+    InplaceJsonOnce(core::cell::Cell::new(Some(f)))
+}
+
+struct InplaceJsonOnce<F>(core::cell::Cell<Option<F>>);
+
+impl<F> Display for InplaceJsonOnce<F>
+where
+    F: FnOnce(&mut JsonFormatter<'_, '_>) -> core::fmt::Result,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", Json(self))
+    }
+}
+
+impl<F> DisplayJson for InplaceJsonOnce<F>
+where
+    F: FnOnce(&mut JsonFormatter<'_, '_>) -> core::fmt::Result,
+{
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        self.0.take().ok_or(core::fmt::Error)?(f)
+    }
+}
+
 /// A convenience function for creating JSON objects.
 ///
 /// This is shorthand for `json(|f| f.object(|f| fmt(f)))`, providing a more direct way
@@ -419,3 +633,44 @@ where
 {
     json(move |f| f.array(|f| fmt(f)))
 }
+
+/// Creates a JSON array from the items of an iterator.
+///
+/// This is shorthand for `array(|f| f.elements(iter.clone()))`.
+/// Note that the iterator is cloned each time the returned value is formatted.
+///
+/// # Examples
+///
+/// ```
+/// let values = [1, 2, 3];
+/// let arr = nojson::array_from_iter(values.iter().map(|v| v * 10));
+/// assert_eq!(arr.to_string(), "[10,20,30]");
+/// ```
+pub fn array_from_iter<I>(iter: I) -> impl DisplayJson + Display
+where
+    I: IntoIterator + Clone,
+    I::Item: DisplayJson,
+{
+    array(move |f| f.elements(iter.clone()))
+}
+
+/// Creates a JSON object from an iterator of name-value pairs.
+///
+/// This is shorthand for `object(|f| f.members(pairs.clone()))`.
+/// Note that the iterator is cloned each time the returned value is formatted.
+///
+/// # Examples
+///
+/// ```
+/// let names = ["foo", "bar"];
+/// let obj = nojson::object_from_pairs(names.iter().map(|name| (name, name.len())));
+/// assert_eq!(obj.to_string(), r#"{"foo":3,"bar":3}"#);
+/// ```
+pub fn object_from_pairs<I, N, V>(pairs: I) -> impl DisplayJson + Display
+where
+    I: IntoIterator<Item = (N, V)> + Clone,
+    N: Display,
+    V: DisplayJson,
+{
+    object(move |f| f.members(pairs.clone()))
+}
@@ -0,0 +1,419 @@
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+
+use crate::{JsonParseError, JsonValueKind};
+
+/// Minifies JSON (or JSONC) text read from `reader` and writes the result to `writer`.
+///
+/// Whitespace and comments (`//` and `/* */`) are removed, and trailing commas are dropped,
+/// so the output is always compact, standard JSON. The syntax is validated while streaming
+/// (with the same rules as [`RawJson::parse_jsonc()`](crate::RawJson::parse_jsonc)), but no index is built,
+/// so arbitrarily large inputs can be processed with constant memory
+/// (apart from the nesting depth of arrays and objects).
+/// String contents, including escape sequences, are copied as is.
+///
+/// Both `reader` and `writer` are buffered internally.
+///
+/// # Errors
+///
+/// I/O errors are returned as is. Syntax errors are returned as [`io::Error`]s of kind
+/// [`io::ErrorKind::InvalidData`] that wrap a [`JsonParseError`] (whose positions are byte
+/// offsets from the start of the stream). Note that a part of the output may have been
+/// written when an error occurs.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// let input = r#"{
+///     "name": "John", // comment
+///     "tags": [ "a", "b", ],
+/// }"#;
+/// let mut output = Vec::new();
+/// nojson::minify_stream(input.as_bytes(), &mut output)?;
+/// assert_eq!(output, br#"{"name":"John","tags":["a","b"]}"#);
+///
+/// let e = nojson::minify_stream(&b"[1, 2"[..], &mut Vec::new()).expect_err("unterminated");
+/// assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+/// let e = e.into_inner().and_then(|e| e.downcast::<nojson::JsonParseError>().ok());
+/// assert_eq!(e.map(|e| e.position()), Some(5));
+/// # Ok(())
+/// # }
+/// ```
+pub fn minify_stream<R: Read, W: Write>(reader: R, writer: W) -> io::Result<()> {
+    let mut minifier = Minifier {
+        reader: BufReader::new(reader),
+        writer: BufWriter::new(writer),
+        position: 0,
+        kind: None,
+    };
+    minifier.skip_whitespaces_and_comments()?;
+    minifier.value("a JSON value")?;
+    minifier.skip_whitespaces_and_comments()?;
+    if minifier.peek()?.is_some() {
+        let e = JsonParseError::UnexpectedTrailingChar {
+            kind: minifier.kind.expect("infallible"),
+            position: minifier.position,
+            end: minifier.char_end(),
+        };
+        return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+    }
+    minifier.writer.flush()
+}
+
+#[derive(Debug)]
+struct Minifier<R, W: Write> {
+    reader: BufReader<R>,
+    writer: BufWriter<W>,
+    position: usize,
+    kind: Option<JsonValueKind>,
+}
+
+impl<R: Read, W: Write> Minifier<R, W> {
+    fn peek(&mut self) -> io::Result<Option<u8>> {
+        loop {
+            match self.reader.fill_buf() {
+                Ok(buf) => return Ok(buf.first().copied()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn bump(&mut self) {
+        self.reader.consume(1);
+        self.position += 1;
+    }
+
+    // Consumes the next byte and writes it to the output.
+    fn copy(&mut self, b: u8) -> io::Result<()> {
+        self.bump();
+        self.writer.write_all(&[b])
+    }
+
+    fn skip_whitespaces_and_comments(&mut self) -> io::Result<()> {
+        while let Some(b) = self.peek()? {
+            match b {
+                b' ' | b'\t' | b'\n' | b'\r' => self.bump(),
+                b'/' => {
+                    self.bump();
+                    match self.peek()? {
+                        Some(b'/') => {
+                            while self.peek()?.is_some_and(|b| b != b'\n') {
+                                self.bump();
+                            }
+                        }
+                        Some(b'*') => {
+                            self.bump();
+                            let mut star = false;
+                            loop {
+                                let Some(b) = self.peek()? else {
+                                    return Err(self.unexpected_eos("`*/`"));
+                                };
+                                self.bump();
+                                if star && b == b'/' {
+                                    break;
+                                }
+                                star = b == b'*';
+                            }
+                        }
+                        _ => return Err(self.unexpected_char("`/` or `*`")),
+                    }
+                }
+                _ => break,
+            }
+        }
+        Ok(())
+    }
+
+    fn value(&mut self, expected: &'static str) -> io::Result<()> {
+        self.skip_whitespaces_and_comments()?;
+        match self.peek()? {
+            Some(b'n') => self.literal(JsonValueKind::Null, b"null", "`null`"),
+            Some(b't') => self.literal(JsonValueKind::Boolean, b"true", "`true`"),
+            Some(b'f') => self.literal(JsonValueKind::Boolean, b"false", "`false`"),
+            Some(b'"') => self.string(),
+            Some(b'[') => self.array(),
+            Some(b'{') => self.object(),
+            Some(b'0'..=b'9' | b'-') => self.number(),
+            Some(_) => Err(self.unexpected_char(expected)),
+            None => Err(self.unexpected_eos(expected)),
+        }
+    }
+
+    fn literal(
+        &mut self,
+        kind: JsonValueKind,
+        literal: &[u8],
+        expected: &'static str,
+    ) -> io::Result<()> {
+        self.kind = Some(kind);
+        for &c in literal {
+            self.expect_byte(c, expected)?;
+        }
+        Ok(())
+    }
+
+    // number = [ minus ] int [ frac ] [ exp ]
+    fn number(&mut self) -> io::Result<()> {
+        self.kind = Some(JsonValueKind::Integer);
+
+        // [ minus ]
+        if self.peek()? == Some(b'-') {
+            self.copy(b'-')?;
+        }
+
+        // int
+        if self.peek()? == Some(b'0') {
+            self.copy(b'0')?;
+        } else {
+            self.one_or_more_digits()?;
+        }
+
+        // [ frac ]
+        if self.peek()? == Some(b'.') {
+            self.kind = Some(JsonValueKind::Float);
+            self.copy(b'.')?;
+            self.one_or_more_digits()?;
+        }
+
+        // [ exp ]
+        if let Some(b @ (b'e' | b'E')) = self.peek()? {
+            self.kind = Some(JsonValueKind::Float);
+            self.copy(b)?;
+            if let Some(b @ (b'-' | b'+')) = self.peek()? {
+                self.copy(b)?;
+            }
+            self.one_or_more_digits()?;
+        }
+        Ok(())
+    }
+
+    fn one_or_more_digits(&mut self) -> io::Result<()> {
+        let mut n = 0;
+        while let Some(b @ b'0'..=b'9') = self.peek()? {
+            self.copy(b)?;
+            n += 1;
+        }
+        if n == 0 {
+            return Err(self.unexpected_char("a digit"));
+        }
+        Ok(())
+    }
+
+    fn string(&mut self) -> io::Result<()> {
+        self.kind = Some(JsonValueKind::String);
+        self.copy(b'"')?;
+        loop {
+            let Some(b) = self.peek()? else {
+                return Err(self.unexpected_eos("`\"`"));
+            };
+            match b {
+                b'"' => return self.copy(b),
+                b'\\' => {
+                    self.copy(b)?;
+                    match self.peek()? {
+                        Some(b @ (b'"' | b'\\' | b'/' | b'n' | b't' | b'r' | b'b' | b'f')) => {
+                            self.copy(b)?;
+                        }
+                        _ => self.unicode_escape()?,
+                    }
+                }
+                0x00..0x20 => return Err(self.unexpected_char("`\"` or a non-control character")),
+                0x20..0x80 => self.copy(b)?,
+                _ => self.utf8_char()?,
+            }
+        }
+    }
+
+    fn unicode_escape(&mut self) -> io::Result<()> {
+        self.expect_byte(b'u', "an escape character")?;
+        let code = self.hex_code("4 hexadecimal digits", |code| {
+            !(0xDC00..0xE000).contains(&code)
+        })?;
+        if (0xD800..0xDC00).contains(&code) {
+            // A high surrogate must be followed by an escaped low surrogate.
+            let expected = "a low surrogate escape (`\\uDC00`-`\\uDFFF`)";
+            self.expect_byte(b'\\', expected)?;
+            self.expect_byte(b'u', expected)?;
+            self.hex_code(expected, |code| (0xDC00..0xE000).contains(&code))?;
+        }
+        Ok(())
+    }
+
+    fn hex_code<F>(&mut self, expected: &'static str, is_valid: F) -> io::Result<u32>
+    where
+        F: FnOnce(u32) -> bool,
+    {
+        let start = self.position;
+        let mut digits = [0; 4];
+        for digit in &mut digits {
+            let Some(b) = self.peek()? else {
+                return Err(self.unexpected_eos(expected));
+            };
+            *digit = b;
+            self.bump();
+        }
+        let code = core::str::from_utf8(&digits)
+            .ok()
+            .and_then(crate::parse::decode_hex_code)
+            .filter(|&code| is_valid(code));
+        let Some(code) = code else {
+            let e = JsonParseError::UnexpectedValueChar {
+                kind: self.kind,
+                position: start,
+                end: start + 1,
+                expected,
+            };
+            return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+        };
+        self.writer.write_all(&digits)?;
+        Ok(code)
+    }
+
+    fn utf8_char(&mut self) -> io::Result<()> {
+        let expected = "valid UTF-8";
+        let start = self.position;
+        let mut bytes = [0; 4];
+        let len = utf8_char_width(self.peek()?.expect("infallible"));
+        for b in bytes.iter_mut().take(len) {
+            let Some(c) = self.peek()? else {
+                return Err(self.unexpected_eos(expected));
+            };
+            *b = c;
+            self.bump();
+        }
+        if core::str::from_utf8(&bytes[..len]).is_err() {
+            let e = JsonParseError::UnexpectedValueChar {
+                kind: self.kind,
+                position: start,
+                end: start + 1,
+                expected,
+            };
+            return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+        }
+        self.writer.write_all(&bytes[..len])
+    }
+
+    fn array(&mut self) -> io::Result<()> {
+        self.kind = Some(JsonValueKind::Array);
+        self.copy(b'[')?;
+        self.skip_whitespaces_and_comments()?;
+        if self.peek()? == Some(b']') {
+            return self.copy(b']');
+        }
+
+        loop {
+            self.value("a JSON value or `]`")?;
+            self.kind = Some(JsonValueKind::Array);
+
+            self.skip_whitespaces_and_comments()?;
+            if self.peek()? == Some(b']') {
+                return self.copy(b']');
+            }
+            self.expect_separator("`,` or `]`")?;
+            if self.peek()? == Some(b']') {
+                return self.copy(b']');
+            }
+            self.writer.write_all(b",")?;
+        }
+    }
+
+    fn object(&mut self) -> io::Result<()> {
+        self.kind = Some(JsonValueKind::Object);
+        self.copy(b'{')?;
+        self.skip_whitespaces_and_comments()?;
+        if self.peek()? == Some(b'}') {
+            return self.copy(b'}');
+        }
+
+        loop {
+            // Key.
+            if self.peek()? != Some(b'"') {
+                return Err(self.unexpected_char("a string or `}`"));
+            }
+            self.string()?;
+            self.kind = Some(JsonValueKind::Object);
+
+            // Value.
+            self.skip_whitespaces_and_comments()?;
+            self.expect_byte(b':', "`:`")?;
+            self.value("a JSON value")?;
+            self.kind = Some(JsonValueKind::Object);
+
+            self.skip_whitespaces_and_comments()?;
+            if self.peek()? == Some(b'}') {
+                return self.copy(b'}');
+            }
+            self.expect_separator("`,` or `}`")?;
+            if self.peek()? == Some(b'}') {
+                return self.copy(b'}');
+            }
+            self.writer.write_all(b",")?;
+        }
+    }
+
+    // Consumes a `,` (without writing it, since it may be a trailing comma) and the following whitespace.
+    fn expect_separator(&mut self, expected: &'static str) -> io::Result<()> {
+        if self.peek()? != Some(b',') {
+            return Err(self.unexpected_char(expected));
+        }
+        self.bump();
+        self.skip_whitespaces_and_comments()
+    }
+
+    fn expect_byte(&mut self, b: u8, expected: &'static str) -> io::Result<()> {
+        if self.peek()? != Some(b) {
+            return Err(self.unexpected_char(expected));
+        }
+        self.copy(b)
+    }
+
+    // Returns the end position of the next (possibly multi-byte) character.
+    //
+    // This must be called after `peek()`, so that the character (if any) is already buffered.
+    fn char_end(&self) -> usize {
+        let width = self
+            .reader
+            .buffer()
+            .first()
+            .copied()
+            .map_or(0, utf8_char_width);
+        self.position + width
+    }
+
+    // Makes an error for the next character (or the end of the stream).
+    //
+    // This must be called after `peek()`, so that the character (if any) is already buffered.
+    fn unexpected_char(&self, expected: &'static str) -> io::Error {
+        if self.reader.buffer().is_empty() {
+            return self.unexpected_eos(expected);
+        }
+        let e = JsonParseError::UnexpectedValueChar {
+            kind: self.kind,
+            position: self.position,
+            end: self.char_end(),
+            expected,
+        };
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+
+    fn unexpected_eos(&self, expected: &'static str) -> io::Error {
+        let e = JsonParseError::UnexpectedEos {
+            kind: self.kind,
+            position: self.position,
+            end: self.position,
+            expected,
+        };
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+// Returns the byte length of a UTF-8 character from its first byte (1 for invalid bytes).
+fn utf8_char_width(b: u8) -> usize {
+    match b {
+        0xC0..0xE0 => 2,
+        0xE0..0xF0 => 3,
+        0xF0..0xF8 => 4,
+        _ => 1,
+    }
+}
@@ -3,7 +3,7 @@ use core::ops::Range;
 
 use crate::{
     JsonValueKind,
-    raw::{JsonParseError, JsonValueIndexEntry},
+    raw::{JsonParseError, JsonValueIndex, JsonValueIndexEntry},
 };
 
 pub trait Extensions {
@@ -27,13 +27,17 @@ impl Extensions for Jsonc {
     const ALLOW_TRAILING_COMMAS: bool = true;
 }
 
-#[derive(Debug)]
+// Returns the byte length of a non-standard scalar token at the start of the given text.
+pub type ScanLiteral<'a> = &'a dyn Fn(&str) -> Option<usize>;
+
 pub struct JsonParser<'a, X> {
     original_text: &'a str,
     text: &'a str,
     kind: Option<JsonValueKind>,
-    values: Vec<JsonValueIndexEntry>,
+    values: JsonValueIndex,
     comments: Vec<Range<usize>>,
+    scan_literal: Option<ScanLiteral<'a>>,
+    build_index: bool,
     _extensions: core::marker::PhantomData<X>,
 }
 
@@ -43,26 +47,73 @@ impl<'a, E: Extensions> JsonParser<'a, E> {
             original_text: text,
             text,
             kind: None,
-            values: Vec::new(),
+            values: JsonValueIndex::Empty,
             comments: Vec::new(),
+            scan_literal: None,
+            build_index: true,
             _extensions: core::marker::PhantomData,
         }
     }
 
-    pub fn parse(
-        mut self,
-    ) -> Result<(Vec<JsonValueIndexEntry>, Vec<Range<usize>>), JsonParseError> {
-        self.parse_value()?;
+    // Only checks the syntax, without storing any `JsonValueIndexEntry`s.
+    pub fn without_index(mut self) -> Self {
+        self.build_index = false;
+        self
+    }
+
+    pub fn with_literals(mut self, scan_literal: ScanLiteral<'a>) -> Self {
+        self.scan_literal = Some(scan_literal);
+        self
+    }
+
+    pub fn parse(mut self) -> Result<(JsonValueIndex, Vec<Range<usize>>), JsonParseError> {
+        self.parse_value("a JSON value")?;
         self.check_trailing_char()?;
         Ok((self.values, self.comments))
     }
 
+    // Same as `parse()`, but appends the entries to `values` instead of allocating a new index.
+    // The `end_index` of each appended entry is relative to the initial length of `values`,
+    // and `values` is left unchanged on error.
+    pub fn parse_into(
+        mut self,
+        values: &mut Vec<JsonValueIndexEntry>,
+    ) -> Result<(), JsonParseError> {
+        let base = values.len();
+        self.values = JsonValueIndex::Multiple(core::mem::take(values));
+        let result = self
+            .parse_value("a JSON value")
+            .and_then(|()| self.check_trailing_char());
+        let JsonValueIndex::Multiple(entries) = core::mem::take(&mut self.values) else {
+            unreachable!();
+        };
+        *values = entries;
+        match result {
+            Ok(()) => {
+                for entry in &mut values[base..] {
+                    entry.end_index -= base;
+                }
+            }
+            Err(_) => values.truncate(base),
+        }
+        result
+    }
+
+    // Parses a single value at the start of the text (ignoring whatever follows it)
+    // and returns the byte length of the consumed text.
+    pub fn skip_value(mut self) -> Result<usize, JsonParseError> {
+        self.parse_value("a JSON value")?;
+        Ok(self.position())
+    }
+
     fn check_trailing_char(&mut self) -> Result<(), JsonParseError> {
         self.text = self.skip_whitespaces_and_comments(self.text)?;
         if !self.text.is_empty() {
+            let position = self.position();
             return Err(JsonParseError::UnexpectedTrailingChar {
                 kind: self.kind.expect("infallible"),
-                position: self.position(),
+                position,
+                end: position + self.text.trim_end().len(),
             });
         }
         Ok(())
@@ -81,7 +132,7 @@ impl<'a, E: Extensions> JsonParser<'a, E> {
                 s.trim_start_matches(|c| c != '\n')
             } else if let Some(s) = s.strip_prefix("/*") {
                 let Some(offset) = s.find("*/") else {
-                    return Err(self.unexpected_eos());
+                    return Err(self.unexpected_eos("`*/`"));
                 };
                 &s[offset + 2..]
             } else {
@@ -95,7 +146,8 @@ impl<'a, E: Extensions> JsonParser<'a, E> {
         Ok(s)
     }
 
-    fn parse_value(&mut self) -> Result<(), JsonParseError> {
+    // `expected` describes what is acceptable here, for error messages.
+    fn parse_value(&mut self, expected: &'static str) -> Result<(), JsonParseError> {
         self.text = self.skip_whitespaces_and_comments(self.text)?;
         match self.text.chars().next() {
             Some('n') => self.parse_null(&self.text[1..]),
@@ -105,27 +157,45 @@ impl<'a, E: Extensions> JsonParser<'a, E> {
             Some('[') => self.parse_array(&self.text[1..]),
             Some('{') => self.parse_object(&self.text[1..]),
             Some('0'..='9' | '-') => self.parse_number(),
-            Some(_) => Err(self.unexpected_value_char(0)),
-            None => Err(self.unexpected_eos()),
+            Some(_) => self.parse_extension_literal(expected),
+            None => Err(self.unexpected_eos(expected)),
         }
     }
 
+    fn parse_extension_literal(&mut self, expected: &'static str) -> Result<(), JsonParseError> {
+        let len = self
+            .scan_literal
+            .and_then(|scan| scan(self.text))
+            .filter(|&len| len > 0 && self.text.is_char_boundary(len));
+        let Some(len) = len else {
+            return Err(self.unexpected_value_char(0, expected));
+        };
+        // Extension tokens are indexed like `null`, but flagged so that conversions reject them.
+        self.kind = Some(JsonValueKind::Null);
+        self.push_entry(len);
+        if self.build_index {
+            self.values.last_mut().expect("infallible").extension = true;
+        }
+        Ok(())
+    }
+
     fn parse_null(&mut self, s: &'a str) -> Result<(), JsonParseError> {
-        self.parse_literal(JsonValueKind::Null, "ull", s)
+        self.parse_literal(JsonValueKind::Null, "ull", "`null`", s)
     }
 
     fn parse_true(&mut self, s: &'a str) -> Result<(), JsonParseError> {
-        self.parse_literal(JsonValueKind::Boolean, "rue", s)
+        self.parse_literal(JsonValueKind::Boolean, "rue", "`true`", s)
     }
 
     fn parse_false(&mut self, s: &'a str) -> Result<(), JsonParseError> {
-        self.parse_literal(JsonValueKind::Boolean, "alse", s)
+        self.parse_literal(JsonValueKind::Boolean, "alse", "`false`", s)
     }
 
     fn parse_literal(
         &mut self,
         kind: JsonValueKind,
         literal_suffix: &str,
+        expected: &'static str,
         s: &'a str,
     ) -> Result<(), JsonParseError> {
         self.kind = Some(kind);
@@ -135,20 +205,29 @@ impl<'a, E: Extensions> JsonParser<'a, E> {
         } else {
             for (i, (c0, c1)) in s.chars().zip(literal_suffix.chars()).enumerate() {
                 if c0 != c1 {
-                    return Err(self.unexpected_value_char(1 + i));
+                    return Err(self.unexpected_value_char(1 + i, expected));
                 }
             }
-            Err(self.unexpected_eos())
+            Err(self.unexpected_eos(expected))
         }
     }
 
-    fn unexpected_value_char(&self, offset: usize) -> JsonParseError {
+    fn unexpected_value_char(&self, offset: usize, expected: &'static str) -> JsonParseError {
         let kind = self.kind;
         let position = self.position() + offset;
-        if position == self.original_text.len() {
-            JsonParseError::UnexpectedEos { kind, position }
-        } else {
-            JsonParseError::UnexpectedValueChar { kind, position }
+        match self.original_text[position..].chars().next() {
+            None => JsonParseError::UnexpectedEos {
+                kind,
+                position,
+                end: position,
+                expected,
+            },
+            Some(c) => JsonParseError::UnexpectedValueChar {
+                kind,
+                position,
+                end: position + c.len_utf8(),
+                expected,
+            },
         }
     }
 
@@ -187,15 +266,20 @@ impl<'a, E: Extensions> JsonParser<'a, E> {
         Ok(())
     }
 
-    fn strip_char(&self, s: &'a str, c: char) -> Result<&'a str, JsonParseError> {
+    fn strip_char(
+        &self,
+        s: &'a str,
+        c: char,
+        expected: &'static str,
+    ) -> Result<&'a str, JsonParseError> {
         s.strip_prefix(c)
-            .ok_or_else(|| self.unexpected_value_char(self.offset(s)))
+            .ok_or_else(|| self.unexpected_value_char(self.offset(s), expected))
     }
 
     fn strip_one_or_more_digits(&self, s: &'a str) -> Result<&'a str, JsonParseError> {
         let n = crate::swar::skip_ascii_digits(s.as_bytes());
         if n == 0 {
-            Err(self.unexpected_value_char(self.offset(s)))
+            Err(self.unexpected_value_char(self.offset(s), "a digit"))
         } else {
             Ok(&s[n..])
         }
@@ -214,16 +298,17 @@ impl<'a, E: Extensions> JsonParser<'a, E> {
         self.push_entry(self.offset(s)); // Push a placeholder entry
         self.text = s;
 
+        let mut expected_key = "a string or `}`";
         loop {
             // Key.
-            let s = self.strip_char(self.text, '"')?;
+            let s = self.strip_char(self.text, '"', expected_key)?;
             self.parse_string(s)?;
             self.kind = Some(JsonValueKind::Object);
 
             // Value.
             self.text = self.skip_whitespaces_and_comments(self.text)?;
-            self.text = self.strip_char(self.text, ':')?;
-            self.parse_value()?;
+            self.text = self.strip_char(self.text, ':', "`:`")?;
+            self.parse_value("a JSON value")?;
             self.kind = Some(JsonValueKind::Object);
 
             self.text = self.skip_whitespaces_and_comments(self.text)?;
@@ -233,7 +318,7 @@ impl<'a, E: Extensions> JsonParser<'a, E> {
                 return Ok(());
             }
 
-            self.text = self.strip_char(self.text, ',')?;
+            self.text = self.strip_char(self.text, ',', "`,` or `}`")?;
             self.text = self.skip_whitespaces_and_comments(self.text)?;
             if E::ALLOW_TRAILING_COMMAS
                 && let Some(s) = self.text.strip_prefix('}')
@@ -242,6 +327,9 @@ impl<'a, E: Extensions> JsonParser<'a, E> {
                 self.finalize_entry(index);
                 return Ok(());
             }
+            if !E::ALLOW_TRAILING_COMMAS {
+                expected_key = "a string";
+            }
         }
     }
 
@@ -257,8 +345,9 @@ impl<'a, E: Extensions> JsonParser<'a, E> {
         let index = self.values.len();
         self.push_entry(self.offset(s)); // Push a placeholder entry
 
+        let mut expected_value = "a JSON value or `]`";
         loop {
-            self.parse_value()?;
+            self.parse_value(expected_value)?;
             self.kind = Some(JsonValueKind::Array);
 
             self.text = self.skip_whitespaces_and_comments(self.text)?;
@@ -267,7 +356,11 @@ impl<'a, E: Extensions> JsonParser<'a, E> {
                 self.finalize_entry(index);
                 return Ok(());
             } else {
-                self.text = self.strip_char(self.text, ',')?;
+                self.text = self.strip_char(self.text, ',', "`,` or `]`")?;
+            }
+
+            if !E::ALLOW_TRAILING_COMMAS {
+                expected_value = "a JSON value";
             }
 
             if E::ALLOW_TRAILING_COMMAS {
@@ -297,7 +390,9 @@ impl<'a, E: Extensions> JsonParser<'a, E> {
                 Some(b'"') => {
                     let s = &s[1..];
                     self.push_entry(self.offset(s));
-                    self.values.last_mut().expect("infallible").escaped = escaped;
+                    if let Some(entry) = self.values.last_mut() {
+                        entry.escaped = escaped;
+                    }
                     return Ok(());
                 }
                 Some(b'\\') => {
@@ -307,30 +402,56 @@ impl<'a, E: Extensions> JsonParser<'a, E> {
                     {
                         s = suffix;
                     } else {
-                        s = self.strip_char(s, 'u')?;
+                        s = self.strip_char(s, 'u', "an escape character")?;
                         if s.len() < 4 {
-                            return Err(self.unexpected_eos());
+                            return Err(self.unexpected_eos("4 hexadecimal digits"));
                         }
-                        decode_hex_char(s)
-                            .ok_or_else(|| self.unexpected_value_char(self.offset(s)))?;
+                        let code = decode_hex_code(s)
+                            .filter(|code| !(0xDC00..0xE000).contains(code))
+                            .ok_or_else(|| {
+                                let expected = "4 hexadecimal digits (not a low surrogate)";
+                                self.unexpected_value_char(self.offset(s), expected)
+                            })?;
                         s = &s[4..];
+                        if (0xD800..0xDC00).contains(&code) {
+                            // A high surrogate must be followed by an escaped low surrogate.
+                            let expected = "a low surrogate escape (`\\uDC00`-`\\uDFFF`)";
+                            s = self.strip_char(s, '\\', expected)?;
+                            s = self.strip_char(s, 'u', expected)?;
+                            if s.len() < 4 {
+                                return Err(self.unexpected_eos(expected));
+                            }
+                            decode_hex_code(s)
+                                .filter(|code| (0xDC00..0xE000).contains(code))
+                                .ok_or_else(|| {
+                                    self.unexpected_value_char(self.offset(s), expected)
+                                })?;
+                            s = &s[4..];
+                        }
                     }
                 }
                 Some(_) => {
-                    return Err(self.unexpected_value_char(self.offset(s)));
+                    let expected = "`\"` or a non-control character";
+                    return Err(self.unexpected_value_char(self.offset(s), expected));
                 }
                 None => {
-                    return Err(self.unexpected_eos());
+                    return Err(self.unexpected_eos("`\"`"));
                 }
             }
         }
     }
 
     fn push_entry(&mut self, len: usize) {
+        if !self.build_index {
+            self.text = &self.text[len..];
+            return;
+        }
+
         let position = self.position();
         let entry = JsonValueIndexEntry {
             kind: self.kind.expect("infallible"),
             escaped: false,
+            extension: false,
             text: Range {
                 start: position,
                 end: position + len,
@@ -342,6 +463,9 @@ impl<'a, E: Extensions> JsonParser<'a, E> {
     }
 
     fn finalize_entry(&mut self, index: usize) {
+        if !self.build_index {
+            return;
+        }
         self.values[index].text.end = self.position();
         self.values[index].end_index = self.values.len();
     }
@@ -354,22 +478,25 @@ impl<'a, E: Extensions> JsonParser<'a, E> {
         self.text.len() - s.len()
     }
 
-    fn unexpected_eos(&self) -> JsonParseError {
+    fn unexpected_eos(&self, expected: &'static str) -> JsonParseError {
+        let position = self.original_text.len();
         JsonParseError::UnexpectedEos {
             kind: self.kind,
-            position: self.original_text.len(),
+            position,
+            end: position,
+            expected,
         }
     }
 }
 
 #[inline(always)]
-fn decode_hex_char(s: &str) -> Option<char> {
+pub(crate) fn decode_hex_code(s: &str) -> Option<u32> {
     let bytes = s.as_bytes().get(..4)?;
     let mut code = 0u32;
     for &byte in bytes {
         code = (code << 4) | decode_hex_nibble(byte)?;
     }
-    char::from_u32(code)
+    Some(code)
 }
 
 #[inline(always)]
@@ -1,5 +1,5 @@
 use alloc::boxed::Box;
-use core::num::NonZeroUsize;
+use core::{num::NonZeroUsize, ops::Range};
 
 #[cfg(doc)]
 use crate::RawJson;
@@ -9,7 +9,7 @@ use crate::{JsonValueKind, RawJsonValue};
 ///
 /// This enum represents various errors that can occur during JSON parsing.
 /// Each variant provides specific details about the error, including the position
-/// in the input string where the error occurred.
+/// (and the end of the offending part) in the input string where the error occurred.
 ///
 /// For generating more detailed error messages, you can use these methods:
 /// - [`JsonParseError::get_line()`]
@@ -30,6 +30,14 @@ pub enum JsonParseError {
 
         /// Byte position in the input string where the unexpected end occurred.
         position: usize,
+
+        /// Byte position where the error span ends.
+        ///
+        /// This is always equal to `position`, i.e., the end of the input string.
+        end: usize,
+
+        /// Description of what the parser expected instead of the end (e.g., ``"`,` or `}`"``).
+        expected: &'static str,
     },
 
     /// Additional non-whitespace characters were found after a complete JSON value was parsed.
@@ -42,6 +50,9 @@ pub enum JsonParseError {
 
         /// Byte position in the input string where the non-whitespace trailing characters begin.
         position: usize,
+
+        /// Byte position where the non-whitespace trailing characters end.
+        end: usize,
     },
 
     /// An unexpected character was encountered while parsing a JSON value.
@@ -56,6 +67,12 @@ pub enum JsonParseError {
 
         /// Byte position in the input string where the unexpected character was found.
         position: usize,
+
+        /// Byte position just after the unexpected character.
+        end: usize,
+
+        /// Description of what the parser expected instead of the character (e.g., ``"`,` or `}`"``).
+        expected: &'static str,
     },
 
     /// A JSON value was syntactically correct, but invalid according to application-specific format rules.
@@ -68,8 +85,19 @@ pub enum JsonParseError {
         kind: JsonValueKind,
 
         /// Byte position in the input string where the invalid JSON value starts.
+        ///
+        /// For some errors about specific characters within a JSON string
+        /// (e.g., a malformed timestamp), this points at the offending character instead.
         position: usize,
 
+        /// Byte position where the invalid JSON value (or the offending character) ends.
+        end: usize,
+
+        /// Category of the failure (see [`JsonParseError::code()`]).
+        ///
+        /// This is [`JsonErrorCode::Custom`] for errors made by [`JsonParseError::invalid_value()`].
+        code: JsonErrorCode,
+
         /// Error reason that describes why the value is invalid.
         error: Box<dyn Send + Sync + core::error::Error>,
     },
@@ -81,13 +109,57 @@ impl JsonParseError {
     where
         E: Into<Box<dyn Send + Sync + core::error::Error>>,
     {
+        let start = value.position();
         JsonParseError::InvalidValue {
             kind: value.kind(),
-            position: value.position(),
+            position: start,
+            end: start + value.as_raw_str().len(),
+            code: JsonErrorCode::Custom,
             error: error.into(),
         }
     }
 
+    // Sets the code of an `InvalidValue` error (other errors are returned unchanged).
+    pub(crate) fn with_code(mut self, new_code: JsonErrorCode) -> Self {
+        if let JsonParseError::InvalidValue { code, .. } = &mut self {
+            *code = new_code;
+        }
+        self
+    }
+
+    /// Returns the stable code that identifies the category of this error.
+    ///
+    /// Unlike the [`Display`](core::fmt::Display) output, codes are suitable for
+    /// programmatic handling (e.g., mapping errors to application-specific error responses).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// use nojson::{JsonErrorCode, RawJson};
+    ///
+    /// let e = RawJson::parse("[1, 2").expect_err("unterminated");
+    /// assert_eq!(e.code(), JsonErrorCode::UnexpectedEos);
+    ///
+    /// let json = RawJson::parse(r#"{"port": 99999}"#)?;
+    /// let e = json.value().to_member("name")?.required().expect_err("missing");
+    /// assert_eq!(e.code(), JsonErrorCode::MissingMember);
+    ///
+    /// let e = u16::try_from(json.value().expect_member("port")).expect_err("too large");
+    /// assert_eq!(e.code(), JsonErrorCode::InvalidNumber);
+    /// assert_eq!(e.code().as_str(), "invalid-number");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn code(&self) -> JsonErrorCode {
+        match self {
+            JsonParseError::UnexpectedEos { .. } => JsonErrorCode::UnexpectedEos,
+            JsonParseError::UnexpectedTrailingChar { .. } => JsonErrorCode::UnexpectedTrailingChar,
+            JsonParseError::UnexpectedValueChar { .. } => JsonErrorCode::UnexpectedValueChar,
+            JsonParseError::InvalidValue { code, .. } => *code,
+        }
+    }
+
     /// Returns the kind of JSON value associated with the error.
     pub fn kind(&self) -> Option<JsonValueKind> {
         match self {
@@ -108,6 +180,56 @@ impl JsonParseError {
         }
     }
 
+    /// Returns a description of what the parser expected at the error position
+    /// (e.g., ``"`,` or `}`"``).
+    ///
+    /// This is available only for [`JsonParseError::UnexpectedEos`] and
+    /// [`JsonParseError::UnexpectedValueChar`] errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let e = nojson::RawJson::parse(r#"{"a": 1 "b": 2}"#).expect_err("missing comma");
+    /// assert_eq!(e.expected(), Some("`,` or `}`"));
+    /// assert!(e.to_string().ends_with("(expected `,` or `}`)"));
+    /// ```
+    pub fn expected(&self) -> Option<&'static str> {
+        match self {
+            JsonParseError::UnexpectedEos { expected, .. }
+            | JsonParseError::UnexpectedValueChar { expected, .. } => Some(expected),
+            JsonParseError::UnexpectedTrailingChar { .. } | JsonParseError::InvalidValue { .. } => {
+                None
+            }
+        }
+    }
+
+    /// Returns the byte range in the input string where the error occurred.
+    ///
+    /// The range covers the offending token or value
+    /// (e.g., the unexpected character, or the whole invalid value),
+    /// which is useful for underlining the problematic part of the text in diagnostics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let text = r#"{"age": "thirty"}"#;
+    /// let json = nojson::RawJson::parse(text).expect("valid JSON");
+    /// let age = json.value().expect_member("age");
+    /// let e = u8::try_from(age).expect_err("not a number");
+    /// assert_eq!(&text[e.span()], r#""thirty""#);
+    ///
+    /// let e = nojson::RawJson::parse("[1, 2 3]").expect_err("missing comma");
+    /// assert_eq!(e.span(), 6..7);
+    /// ```
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            JsonParseError::UnexpectedEos { position, end, .. }
+            | JsonParseError::UnexpectedTrailingChar { position, end, .. }
+            | JsonParseError::UnexpectedValueChar { position, end, .. }
+            | JsonParseError::InvalidValue { position, end, .. } => *position..*end,
+        }
+    }
+
     /// Returns the line and column numbers for the error position in the input text.
     ///
     /// This method calculates the line and column numbers based on the error's
@@ -201,36 +323,53 @@ impl JsonParseError {
 impl core::fmt::Display for JsonParseError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            JsonParseError::UnexpectedEos { kind, position } => {
+            JsonParseError::UnexpectedEos {
+                kind,
+                position,
+                expected,
+                ..
+            } => {
                 if let Some(kind) = kind {
                     write!(
                         f,
-                        "unexpected EOS while parsing {kind:?} at byte position {position}"
+                        "unexpected EOS while parsing {kind:?} at byte position {position} (expected {expected})"
                     )
                 } else {
-                    write!(f, "unexpected EOS at byte position {position}")
+                    write!(
+                        f,
+                        "unexpected EOS at byte position {position} (expected {expected})"
+                    )
                 }
             }
-            JsonParseError::UnexpectedTrailingChar { kind, position } => {
+            JsonParseError::UnexpectedTrailingChar { kind, position, .. } => {
                 write!(
                     f,
                     "unexpected trailing char after parsing {kind:?} at byte position {position}"
                 )
             }
-            JsonParseError::UnexpectedValueChar { kind, position } => {
+            JsonParseError::UnexpectedValueChar {
+                kind,
+                position,
+                expected,
+                ..
+            } => {
                 if let Some(kind) = kind {
                     write!(
                         f,
-                        "unexpected char while parsing {kind:?} at byte position {position}"
+                        "unexpected char while parsing {kind:?} at byte position {position} (expected {expected})"
                     )
                 } else {
-                    write!(f, "unexpected char at byte position {position}")
+                    write!(
+                        f,
+                        "unexpected char at byte position {position} (expected {expected})"
+                    )
                 }
             }
             JsonParseError::InvalidValue {
                 kind,
                 position,
                 error,
+                ..
             } => {
                 write!(
                     f,
@@ -250,3 +389,81 @@ impl core::error::Error for JsonParseError {
         }
     }
 }
+
+/// Stable identifier of the category of a [`JsonParseError`] (see [`JsonParseError::code()`]).
+///
+/// The first three codes correspond to the syntax error variants of [`JsonParseError`].
+/// The others classify [`JsonParseError::InvalidValue`] errors produced by this crate
+/// (e.g., by the [`TryFrom`] implementations, [`RawJsonMember::required()`](crate::RawJsonMember::required),
+/// or the [`validate`](crate::validate) helpers).
+///
+/// New codes may be added in future versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum JsonErrorCode {
+    /// The text ended unexpectedly ([`JsonParseError::UnexpectedEos`]).
+    UnexpectedEos,
+
+    /// Extra characters follow the JSON value ([`JsonParseError::UnexpectedTrailingChar`]).
+    UnexpectedTrailingChar,
+
+    /// An unexpected character was found ([`JsonParseError::UnexpectedValueChar`]).
+    UnexpectedValueChar,
+
+    /// The value is of a different kind than expected (e.g., a string where a number is expected).
+    UnexpectedKind,
+
+    /// A required object member is missing.
+    MissingMember,
+
+    /// An object member that is not allowed was found.
+    UnknownMember,
+
+    /// An object has multiple members with the same name.
+    DuplicateMember,
+
+    /// A number could not be converted (e.g., it is out of the range of the target type).
+    InvalidNumber,
+
+    /// The content of a string could not be converted (e.g., a malformed IP address or timestamp).
+    InvalidString,
+
+    /// A string does not match any of the allowed variant names.
+    UnknownVariant,
+
+    /// An array or object has an unexpected number of elements or members.
+    InvalidLength,
+
+    /// The value violates an additional constraint (e.g., a range or length limit).
+    ConstraintViolation,
+
+    /// Any other error, including errors made by [`JsonParseError::invalid_value()`].
+    Custom,
+}
+
+impl JsonErrorCode {
+    /// Returns the kebab-case name of this code (e.g., `"missing-member"`).
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            JsonErrorCode::UnexpectedEos => "unexpected-eos",
+            JsonErrorCode::UnexpectedTrailingChar => "unexpected-trailing-char",
+            JsonErrorCode::UnexpectedValueChar => "unexpected-value-char",
+            JsonErrorCode::UnexpectedKind => "unexpected-kind",
+            JsonErrorCode::MissingMember => "missing-member",
+            JsonErrorCode::UnknownMember => "unknown-member",
+            JsonErrorCode::DuplicateMember => "duplicate-member",
+            JsonErrorCode::InvalidNumber => "invalid-number",
+            JsonErrorCode::InvalidString => "invalid-string",
+            JsonErrorCode::UnknownVariant => "unknown-variant",
+            JsonErrorCode::InvalidLength => "invalid-length",
+            JsonErrorCode::ConstraintViolation => "constraint-violation",
+            JsonErrorCode::Custom => "custom",
+        }
+    }
+}
+
+impl core::fmt::Display for JsonErrorCode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
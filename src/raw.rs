@@ -1,10 +1,12 @@
 use alloc::{
-    borrow::Cow, borrow::ToOwned, boxed::Box, format, string::String, string::ToString, vec::Vec,
+    borrow::Cow, borrow::ToOwned, boxed::Box, collections::BTreeMap, format, string::String,
+    string::ToString, vec::Vec,
 };
-use core::{fmt::Display, hash::Hash, ops::Range};
+use core::{fmt::Display, hash::Hash, ops::Range, str::FromStr};
 
 use crate::{
-    DisplayJson, JsonArrayFormatter, JsonFormatter, JsonObjectFormatter, JsonValueKind,
+    DisplayJson, JsonArrayFormatter, JsonErrorCode, JsonFormatter, JsonKeyCase,
+    JsonObjectFormatter, JsonValueKind, JsoncDocument,
     parse::{JsonParser, Jsonc, Plain},
 };
 
@@ -14,7 +16,7 @@ pub use crate::parse_error::JsonParseError;
 #[derive(Debug, Clone)]
 pub struct RawJsonOwned {
     text: String,
-    values: Vec<JsonValueIndexEntry>,
+    values: JsonValueIndex,
 }
 
 impl RawJsonOwned {
@@ -22,7 +24,7 @@ impl RawJsonOwned {
     ///
     /// This validates the JSON syntax without converting values to Rust types.
     /// Unlike [`RawJson::parse`], this creates an owned version that doesn't
-    /// borrow from the input string.
+    /// borrow from the input string. A `String` argument is moved into the result without being copied.
     ///
     /// # Example
     ///
@@ -30,6 +32,14 @@ impl RawJsonOwned {
     /// # fn main() -> Result<(), nojson::JsonParseError> {
     /// let text = r#"{"name": "John", "age": 30}"#;
     /// let json = nojson::RawJsonOwned::parse(text)?;
+    ///
+    /// // The parsed document can outlive the text it was read from.
+    /// fn read_message() -> Result<nojson::RawJsonOwned, nojson::JsonParseError> {
+    ///     let message = String::from(r#"{"id": 1}"#);
+    ///     nojson::RawJsonOwned::parse(message)
+    /// }
+    /// let message = read_message()?;
+    /// assert_eq!(message.value().to_member("id")?.required()?.as_raw_str(), "1");
     /// # Ok(())
     /// # }
     /// ```
@@ -91,6 +101,21 @@ impl RawJsonOwned {
         Ok((Self { text, values }, comments))
     }
 
+    /// Parses a JSON string that may contain non-standard scalar tokens into a [`RawJsonOwned`] instance.
+    ///
+    /// See [`RawJson::parse_with_literals()`] for details.
+    pub fn parse_with_literals<T, F>(text: T, scan_literal: F) -> Result<Self, JsonParseError>
+    where
+        T: Into<String>,
+        F: Fn(&str) -> Option<usize>,
+    {
+        let text = text.into();
+        let (values, _) = JsonParser::<Plain>::new(&text)
+            .with_literals(&scan_literal)
+            .parse()?;
+        Ok(Self { text, values })
+    }
+
     /// Creates an owned JSON object using the in-place object formatter.
     ///
     /// This is a convenience for building a [`RawJsonOwned`] object directly
@@ -171,6 +196,14 @@ impl RawJsonOwned {
         &self.text
     }
 
+    /// Returns the approximate number of bytes consumed by this JSON,
+    /// that is, the allocated capacity of the owned text plus the heap memory allocated for the value index.
+    ///
+    /// The size of the [`RawJsonOwned`] struct itself is not included.
+    pub fn memory_usage(&self) -> usize {
+        self.text.capacity() + self.values.heap_size()
+    }
+
     /// Returns the top-level value of the JSON.
     ///
     /// This value can be used as an entry point to traverse the entire JSON structure
@@ -193,6 +226,34 @@ impl RawJsonOwned {
         }
     }
 
+    /// Converts the top-level value into `T` (see [`RawJson::decode()`]).
+    pub fn decode<T>(&self) -> Result<T, JsonParseError>
+    where
+        T: for<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError>,
+    {
+        self.value().try_into()
+    }
+
+    /// Returns the JSON text with the values at the given JSON Pointers replaced by `"***"`
+    /// (see [`RawJson::redact()`]).
+    pub fn redact<I>(&self, pointers: I) -> String
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.as_raw_json_ref().redact(pointers)
+    }
+
+    /// Compares the top-level values of two documents structurally (see [`RawJson::structural_cmp()`]).
+    pub fn structural_cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.value().structural_cmp(other.value())
+    }
+
+    /// Returns the JSON Pointer and value of every leaf in the document (see [`RawJson::flatten()`]).
+    pub fn flatten(&self) -> impl Iterator<Item = (String, RawJsonValue<'_, '_>)> {
+        flatten_value(self.value())
+    }
+
     /// Finds the JSON value at the specified byte position in the original text.
     ///
     /// This method traverses the JSON structure to find the most specific value
@@ -331,12 +392,15 @@ impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for RawJsonOwned {
 /// via [`RawJson::value()`], which returns a [`RawJsonValue`]
 /// that provides methods to explore nested elements and convert them into Rust types.
 ///
+/// To keep a parsed document beyond the lifetime of its text (e.g., to return it from a function
+/// or store it in a struct), use [`RawJsonOwned`], which takes ownership of the text.
+///
 /// Note that, for simple use cases,
 /// using [`Json`](crate::Json), which internally uses [`RawJson`], is a more convenient way to parse JSON text into Rust types.
 #[derive(Debug, Clone)]
 pub struct RawJson<'text> {
     text: &'text str,
-    values: Vec<JsonValueIndexEntry>,
+    values: JsonValueIndex,
 }
 
 impl<'text> RawJson<'text> {
@@ -344,6 +408,10 @@ impl<'text> RawJson<'text> {
     ///
     /// This validates the JSON syntax without converting values to Rust types.
     ///
+    /// Characters outside the Basic Multilingual Plane can be escaped as UTF-16 surrogate pairs
+    /// (e.g., `"\ud83d\ude00"`), as described in RFC 8259.
+    /// Lone surrogates are rejected, since they cannot be represented in a Rust string.
+    ///
     /// # Example
     ///
     /// ```
@@ -397,11 +465,212 @@ impl<'text> RawJson<'text> {
         Ok((Self { text, values }, comments))
     }
 
+    /// Checks whether a string is valid JSON, without building a [`RawJson`].
+    ///
+    /// This performs the same syntax checks as [`RawJson::parse()`], but does not allocate
+    /// or store any information about the parsed values, so it is cheaper when only
+    /// the validity of the text matters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// assert!(nojson::RawJson::validate(r#"{"name": "John", "age": 30}"#).is_ok());
+    ///
+    /// let e = nojson::RawJson::validate("[1, 2,]").expect_err("trailing comma");
+    /// assert_eq!(e.position(), 6);
+    /// ```
+    pub fn validate(text: &str) -> Result<(), JsonParseError> {
+        JsonParser::<Plain>::new(text).without_index().parse()?;
+        Ok(())
+    }
+
+    /// Checks whether a byte sequence is valid UTF-8 encoded JSON.
+    ///
+    /// This is the same as [`RawJson::validate()`] except that invalid UTF-8 sequences
+    /// are reported as [`JsonParseError::UnexpectedValueChar`] (or [`JsonParseError::UnexpectedEos`]
+    /// if the text ends in the middle of a sequence).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// assert!(nojson::RawJson::validate_bytes("[\"café\"]".as_bytes()).is_ok());
+    ///
+    /// let e = nojson::RawJson::validate_bytes(b"[\"caf\xe9\"]").expect_err("invalid UTF-8");
+    /// assert_eq!(e.position(), 5);
+    /// ```
+    pub fn validate_bytes(bytes: &[u8]) -> Result<(), JsonParseError> {
+        let utf8_error = match core::str::from_utf8(bytes) {
+            Ok(text) => return Self::validate(text),
+            Err(e) => e,
+        };
+
+        // Report syntax errors that precede the invalid sequence first.
+        let position = utf8_error.valid_up_to();
+        let valid = core::str::from_utf8(&bytes[..position]).expect("infallible");
+        let kind = match JsonParser::<Plain>::new(valid).without_index().parse() {
+            Err(e) if e.position() < position => return Err(e),
+            Err(e) => e.kind(),
+            Ok(_) => None,
+        };
+        let expected = "valid UTF-8";
+        Err(match utf8_error.error_len() {
+            Some(len) => JsonParseError::UnexpectedValueChar {
+                kind,
+                position,
+                end: position + len,
+                expected,
+            },
+            None => JsonParseError::UnexpectedEos {
+                kind,
+                position: bytes.len(),
+                end: bytes.len(),
+                expected,
+            },
+        })
+    }
+
+    /// Parses only the value at the given [JSON Pointer] (e.g., `/users/3/name`) in a JSON string.
+    ///
+    /// The whole text is checked for syntax errors as in [`RawJson::validate()`],
+    /// but only the target value and its descendants are indexed, which saves memory and time
+    /// when a small part of a large document is needed.
+    /// The returned [`RawJson`] covers just the target value's text, like [`RawJsonValue::extract()`],
+    /// so positions in it are relative to the start of that value.
+    ///
+    /// Returns `Ok(None)` if there is no value at `pointer`, or if `pointer` is not a valid JSON Pointer
+    /// (i.e., it is neither empty nor starts with `/`).
+    ///
+    /// [JSON Pointer]: https://www.rfc-editor.org/rfc/rfc6901
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let text = r#"{"users": [{"name": "Alice"}, {"name": "Bob"}], "count": 2}"#;
+    ///
+    /// let json = nojson::RawJson::parse_pointer(text, "/users/1")?.expect("exists");
+    /// assert_eq!(json.text(), r#"{"name": "Bob"}"#);
+    ///
+    /// let name: String = json.value().to_member("name")?.required()?.try_into()?;
+    /// assert_eq!(name, "Bob");
+    ///
+    /// assert!(nojson::RawJson::parse_pointer(text, "/users/2")?.is_none());
+    /// assert!(nojson::RawJson::parse_pointer("[1, 2", "/0").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse_pointer(text: &'text str, pointer: &str) -> Result<Option<Self>, JsonParseError> {
+        Self::validate(text)?;
+        let Some(range) = find_pointer_target(text, pointer) else {
+            return Ok(None);
+        };
+        Self::parse(&text[range]).map(Some)
+    }
+
+    /// Parses a JSON string that may contain non-standard scalar tokens (e.g., `undefined` or `@date(...)`).
+    ///
+    /// Whenever a value is expected but the next character cannot start a standard JSON value,
+    /// `scan_literal` is called with the remaining text. If it returns `Some(len)`,
+    /// the first `len` bytes are accepted as an extension value, for which [`RawJsonValue::is_extension()`]
+    /// returns `true` and whose span can be obtained via [`RawJsonValue::position()`]
+    /// and [`RawJsonValue::as_raw_str()`].
+    /// Returning `None` (or a length that is zero or not on a character boundary)
+    /// makes the parser report the usual syntax error.
+    ///
+    /// Standard JSON values are always parsed as usual, so tokens starting with
+    /// `n`, `t`, `f`, `"`, `[`, `{`, `-`, or a digit cannot be handled by `scan_literal`.
+    ///
+    /// Note that extension values have no dedicated [`JsonValueKind`]:
+    /// [`RawJsonValue::kind()`] reports them as [`JsonValueKind::Null`],
+    /// so check [`RawJsonValue::is_extension()`] to tell them apart from a real `null`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// use nojson::RawJson;
+    ///
+    /// fn scan_literal(text: &str) -> Option<usize> {
+    ///     if text.starts_with("undefined") {
+    ///         Some("undefined".len())
+    ///     } else if text.starts_with("@date(") {
+    ///         text.find(')').map(|i| i + 1)
+    ///     } else {
+    ///         None
+    ///     }
+    /// }
+    ///
+    /// let text = r#"{"a": undefined, "b": [@date(2024-01-01), 1]}"#;
+    /// let json = RawJson::parse_with_literals(text, scan_literal)?;
+    ///
+    /// let a = json.value().to_member("a")?.required()?;
+    /// assert!(a.is_extension());
+    /// assert_eq!(a.as_raw_str(), "undefined");
+    /// assert!(a.as_null().is_err());
+    ///
+    /// let date = json.value().to_path_member(&["b"])?.required()?.expect_element(0);
+    /// assert_eq!(date.as_raw_str(), "@date(2024-01-01)");
+    /// assert_eq!(date.position(), 23);
+    ///
+    /// assert!(RawJson::parse_with_literals("[nothing]", scan_literal).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse_with_literals<F>(text: &'text str, scan_literal: F) -> Result<Self, JsonParseError>
+    where
+        F: Fn(&str) -> Option<usize>,
+    {
+        let (values, _) = JsonParser::<Plain>::new(text)
+            .with_literals(&scan_literal)
+            .parse()?;
+        Ok(Self { text, values })
+    }
+
+    /// Parses a JSONC (JSON with Comments) string into a [`JsoncDocument`].
+    ///
+    /// This is similar to [`RawJson::parse_jsonc()`], but the returned document exposes
+    /// each comment's kind, text, and line number instead of bare byte ranges.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let text = "[1, /* two */ 2] // numbers";
+    /// let doc = nojson::RawJson::parse_jsonc_document(text)?;
+    /// let contents: Vec<_> = doc.comments().map(|c| c.content()).collect();
+    /// assert_eq!(contents, [" two ", " numbers"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse_jsonc_document(text: &'text str) -> Result<JsoncDocument<'text>, JsonParseError> {
+        JsoncDocument::parse(text)
+    }
+
     /// Returns the original JSON text.
     pub fn text(&self) -> &'text str {
         self.text
     }
 
+    /// Returns the approximate number of bytes consumed by this JSON,
+    /// that is, the length of the referenced text plus the heap memory allocated for the value index.
+    ///
+    /// The size of the [`RawJson`] struct itself is not included.
+    /// This is useful for enforcing memory budgets on parsed documents.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let text = r#"{"name": "John", "tags": ["a", "b"]}"#;
+    /// let json = nojson::RawJson::parse(text)?;
+    /// assert!(json.memory_usage() > text.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn memory_usage(&self) -> usize {
+        self.text.len() + self.values.heap_size()
+    }
+
     /// Returns the top-level value of the JSON.
     ///
     /// This value can be used as an entry point to traverse the entire JSON structure
@@ -424,6 +693,122 @@ impl<'text> RawJson<'text> {
         }
     }
 
+    /// Converts the top-level value into `T`.
+    ///
+    /// This is a shorthand for `self.value().try_into()` that spares type annotations
+    /// when the document is held by reference.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"["foo", "bar"]"#)?;
+    /// let names = json.decode::<Vec<&str>>()?;
+    /// assert_eq!(names, ["foo", "bar"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn decode<T>(&self) -> Result<T, JsonParseError>
+    where
+        T: for<'raw> TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError>,
+    {
+        self.value().try_into()
+    }
+
+    /// Returns the JSON text with the values at the given [JSON Pointers] replaced by `"***"`.
+    ///
+    /// Everything else, including whitespace and comments, is kept byte-identical,
+    /// which makes this suitable for storing inbound payloads with personal data scrubbed.
+    /// Pointers that don't refer to any value (or are not valid JSON Pointers) are ignored.
+    /// If an object has duplicate member names, only the first one is matched.
+    ///
+    /// [JSON Pointers]: https://www.rfc-editor.org/rfc/rfc6901
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let text = r#"{"user": {"name": "Alice", "email": "a@example.com"}, "items": [1, 2]}"#;
+    /// let json = nojson::RawJson::parse(text)?;
+    ///
+    /// assert_eq!(
+    ///     json.redact(["/user/email", "/items/1", "/missing"]),
+    ///     r#"{"user": {"name": "Alice", "email": "***"}, "items": [1, "***"]}"#
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn redact<I>(&self, pointers: I) -> String
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.as_raw_json_ref().redact(pointers)
+    }
+
+    /// Compares the top-level values of two documents structurally.
+    ///
+    /// See [`RawJsonValue::structural_cmp()`] for the definition of the order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// use nojson::RawJson;
+    ///
+    /// let mut docs = ["[2]", r#"{"b": 1, "a": 2}"#, "[10]", r#"{"a":2,"b":1.0}"#, "[1, 2]"]
+    ///     .into_iter()
+    ///     .map(RawJson::parse)
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// docs.sort_by(|a, b| a.structural_cmp(b));
+    /// docs.dedup_by(|a, b| a.structural_cmp(b).is_eq());
+    ///
+    /// let texts = docs.iter().map(|doc| doc.text()).collect::<Vec<_>>();
+    /// assert_eq!(texts, ["[1, 2]", "[2]", "[10]", r#"{"b": 1, "a": 2}"#]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn structural_cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.value().structural_cmp(other.value())
+    }
+
+    /// Returns the [JSON Pointer] and value of every leaf in the document, in document order.
+    ///
+    /// Leaves are scalar values (strings, numbers, booleans, `null`) and empty arrays or objects,
+    /// so the document can be reconstructed from the result.
+    /// The pointer of the top-level value is the empty string.
+    /// If an object has duplicate member names, each of them is yielded with the same pointer.
+    ///
+    /// This is useful for exporting documents into key/value stores or columnar systems.
+    ///
+    /// [JSON Pointer]: https://www.rfc-editor.org/rfc/rfc6901
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"{"user": {"name": "Alice", "tags": ["a", "b"]}, "a/b": {}}"#)?;
+    ///
+    /// let leaves = json
+    ///     .flatten()
+    ///     .map(|(pointer, value)| (pointer, value.as_raw_str()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     leaves,
+    ///     [
+    ///         ("/user/name".to_owned(), r#""Alice""#),
+    ///         ("/user/tags/0".to_owned(), r#""a""#),
+    ///         ("/user/tags/1".to_owned(), r#""b""#),
+    ///         ("/a~1b".to_owned(), "{}"),
+    ///     ]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn flatten(&self) -> impl Iterator<Item = (String, RawJsonValue<'text, '_>)> {
+        flatten_value(self.value())
+    }
+
     /// Finds the JSON value at the specified byte position in the original text.
     ///
     /// This method traverses the JSON structure to find the most specific value
@@ -569,14 +954,153 @@ impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for RawJson<'text> {
     }
 }
 
+// Returns the byte range of the value at `pointer` in `text`, which must be valid JSON.
+fn find_pointer_target(text: &str, pointer: &str) -> Option<Range<usize>> {
+    let mut range = value_range_at(text, 0);
+    if pointer.is_empty() {
+        return Some(range);
+    }
+    for token in pointer.strip_prefix('/')?.split('/') {
+        let token = token.replace("~1", "/").replace("~0", "~");
+        range = find_pointer_child(text, range.start, &token)?;
+    }
+    Some(range)
+}
+
+// Returns the byte range of the child named `token` of the array or object starting at `start`.
+fn find_pointer_child(text: &str, start: usize, token: &str) -> Option<Range<usize>> {
+    let is_object = match text.as_bytes()[start] {
+        b'{' => true,
+        b'[' => false,
+        _ => return None,
+    };
+    let target_index = if is_object {
+        None
+    } else {
+        Some(parse_pointer_index(token)?)
+    };
+
+    let mut position = skip_whitespaces(text, start + 1);
+    let mut index = 0;
+    while !matches!(text.as_bytes()[position], b']' | b'}') {
+        let mut value = value_range_at(text, position);
+        let matched = if is_object {
+            let name = RawJson::parse(&text[value.clone()]).expect("infallible");
+            let colon = skip_whitespaces(text, value.end);
+            value = value_range_at(text, colon + 1);
+            name.value().is_string_eq(token)
+        } else {
+            target_index == Some(index)
+        };
+        if matched {
+            return Some(value);
+        }
+
+        index += 1;
+        position = skip_whitespaces(text, value.end);
+        if text.as_bytes()[position] == b',' {
+            position = skip_whitespaces(text, position + 1);
+        }
+    }
+    None
+}
+
+// Returns the byte range of the value starting at or after `position` in `text`, which must be valid JSON.
+fn value_range_at(text: &str, position: usize) -> Range<usize> {
+    let start = skip_whitespaces(text, position);
+    let len = JsonParser::<Plain>::new(&text[start..])
+        .without_index()
+        .skip_value()
+        .expect("infallible");
+    start..start + len
+}
+
+fn skip_whitespaces(text: &str, position: usize) -> usize {
+    position + crate::swar::skip_json_whitespace(&text.as_bytes()[position..])
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct JsonValueIndexEntry {
     pub kind: JsonValueKind,
     pub escaped: bool,
+    // Set for non-standard tokens accepted by `RawJson::parse_with_literals()` (indexed as `Null`).
+    pub extension: bool,
     pub text: Range<usize>,
     pub end_index: usize,
 }
 
+// The entries of all values in a JSON text, in document order.
+//
+// A document consisting of a single scalar value is stored inline, without any heap allocation.
+#[derive(Debug, Default, Clone)]
+pub(crate) enum JsonValueIndex {
+    #[default]
+    Empty,
+    Single(JsonValueIndexEntry),
+    Multiple(Vec<JsonValueIndexEntry>),
+}
+
+impl JsonValueIndex {
+    #[inline]
+    pub fn push(&mut self, entry: JsonValueIndexEntry) {
+        // This is on the hot path of parsing, so push in place in the common case.
+        if let Self::Multiple(entries) = self {
+            entries.push(entry);
+            return;
+        }
+        *self = match core::mem::take(self) {
+            Self::Empty => Self::Single(entry),
+            Self::Single(first) => Self::Multiple(alloc::vec![first, entry]),
+            Self::Multiple(mut entries) => {
+                entries.push(entry);
+                Self::Multiple(entries)
+            }
+        };
+    }
+
+    // Returns the number of heap-allocated bytes.
+    fn heap_size(&self) -> usize {
+        match self {
+            Self::Empty | Self::Single(_) => 0,
+            Self::Multiple(entries) => {
+                entries.capacity() * core::mem::size_of::<JsonValueIndexEntry>()
+            }
+        }
+    }
+}
+
+impl core::ops::Deref for JsonValueIndex {
+    type Target = [JsonValueIndexEntry];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Empty => &[],
+            Self::Single(entry) => core::slice::from_ref(entry),
+            Self::Multiple(entries) => entries,
+        }
+    }
+}
+
+impl core::ops::DerefMut for JsonValueIndex {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Self::Empty => &mut [],
+            Self::Single(entry) => core::slice::from_mut(entry),
+            Self::Multiple(entries) => entries,
+        }
+    }
+}
+
+impl FromIterator<JsonValueIndexEntry> for JsonValueIndex {
+    fn from_iter<I: IntoIterator<Item = JsonValueIndexEntry>>(iter: I) -> Self {
+        let mut index = Self::Empty;
+        for entry in iter {
+            index.push(entry);
+        }
+        index
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct RawJsonRef<'text, 'raw> {
     text: &'text str,
@@ -601,6 +1125,91 @@ impl<'text, 'raw> RawJsonRef<'text, 'raw> {
             index: 0,
         }
     }
+
+    fn redact<I>(self, pointers: I) -> String
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut ranges = pointers
+            .into_iter()
+            .filter_map(|pointer| find_pointer_value(self.value(), pointer.as_ref()))
+            .map(|value| value.entry().text.clone())
+            .collect::<Vec<_>>();
+        // Outer values come before the values nested in them.
+        ranges.sort_by_key(|range| (range.start, core::cmp::Reverse(range.end)));
+
+        let mut redacted = String::with_capacity(self.text.len());
+        let mut position = 0;
+        for range in ranges {
+            if range.start < position {
+                continue;
+            }
+            redacted.push_str(&self.text[position..range.start]);
+            redacted.push_str("\"***\"");
+            position = range.end;
+        }
+        redacted.push_str(&self.text[position..]);
+        redacted
+    }
+}
+
+// Returns the value at `pointer` within `value`.
+fn find_pointer_value<'text, 'raw>(
+    mut value: RawJsonValue<'text, 'raw>,
+    pointer: &str,
+) -> Option<RawJsonValue<'text, 'raw>> {
+    if pointer.is_empty() {
+        return Some(value);
+    }
+    for token in pointer.strip_prefix('/')?.split('/') {
+        let token = token.replace("~1", "/").replace("~0", "~");
+        value = match value.kind() {
+            JsonValueKind::Object => value.to_member(&token).ok()?.optional()?,
+            JsonValueKind::Array => value.to_array().ok()?.nth(parse_pointer_index(&token)?)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+// Returns the JSON Pointer and value of every leaf within `value`, in document order.
+fn flatten_value<'text, 'raw>(
+    value: RawJsonValue<'text, 'raw>,
+) -> impl Iterator<Item = (String, RawJsonValue<'text, 'raw>)> {
+    let mut stack = alloc::vec![(String::new(), value)];
+    core::iter::from_fn(move || {
+        loop {
+            let (pointer, value) = stack.pop()?;
+            let len = stack.len();
+            for (name, child) in value.children() {
+                let mut child_pointer = pointer.clone();
+                child_pointer.push('/');
+                match name {
+                    Some(name) => {
+                        let name = name.to_unquoted_string_str().expect("infallible");
+                        child_pointer.push_str(&name.replace('~', "~0").replace('/', "~1"));
+                    }
+                    None => child_pointer.push_str(&(stack.len() - len).to_string()),
+                }
+                stack.push((child_pointer, child));
+            }
+            if stack.len() == len {
+                return Some((pointer, value));
+            }
+            // Visit the children in document order.
+            stack[len..].reverse();
+        }
+    })
+}
+
+// Array indices are non-negative decimal integers without leading zeros.
+pub(crate) fn parse_pointer_index(token: &str) -> Option<usize> {
+    if token.bytes().all(|b| b.is_ascii_digit()) && (token == "0" || !token.starts_with('0')) {
+        token.parse().ok()
+    } else {
+        None
+    }
 }
 
 impl PartialEq for RawJsonRef<'_, '_> {
@@ -672,7 +1281,26 @@ impl DisplayJson for RawJsonRef<'_, '_> {
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// A value can also be compared directly with a `&str`, `i64`, `f64`, or `bool`.
+/// The comparison is made against the decoded content and is `false` if the kinds don't match
+/// (e.g., the integer `1` is not equal to the string `"1"` nor to `true`,
+/// but `1.0` is equal to `1.0f64` and, like any JSON number, to `1f64`):
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// let json = nojson::RawJson::parse(r#"{"mode": "en\u0061bled", "retries": 3, "debug": false}"#)?;
+/// let obj = json.value();
+///
+/// assert!(obj.to_member("mode")?.required()? == "enabled");
+/// assert!(obj.to_member("retries")?.required()? == 3);
+/// assert!(obj.to_member("retries")?.required()? == 3.0);
+/// assert!(obj.to_member("debug")?.required()? == false);
+/// assert!(obj.to_member("retries")?.required()? != "3");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct RawJsonValue<'text, 'raw> {
     index: usize,
     json: RawJsonRef<'text, 'raw>,
@@ -684,6 +1312,17 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
         self.json.values[self.index].kind
     }
 
+    /// Returns `true` if this value is a non-standard token accepted by the scanner passed to
+    /// [`RawJson::parse_with_literals()`] (e.g., `undefined`).
+    ///
+    /// Such values never appear in documents parsed by other methods.
+    /// Their [`RawJsonValue::kind()`] is [`JsonValueKind::Null`] so that existing `match`es
+    /// on the kind keep working, but they are rejected by [`RawJsonValue::expect_kind()`] and
+    /// thus by all conversions (including into `Option<T>`), and are written as is when formatted.
+    pub fn is_extension(self) -> bool {
+        self.entry().extension
+    }
+
     /// Returns the byte position where this value begins in the JSON text (`self.json().text()`).
     pub fn position(self) -> usize {
         self.json.values[self.index].text.start
@@ -767,20 +1406,98 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
         }
     }
 
-    /// Returns the raw JSON text of this value as-is.
-    pub fn as_raw_str(self) -> &'text str {
-        let text = &self.json.values[self.index].text;
-        &self.json.text[text.start..text.end]
+    /// Returns the nesting level of this value within its JSON document.
+    ///
+    /// The root value has depth `0`, and each enclosing array or object adds one level.
+    /// Object member names have the same depth as their corresponding values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"{"user": {"tags": ["admin"]}}"#)?;
+    /// let user = json.value().to_member("user")?.required()?;
+    /// let tag = user.to_member("tags")?.required()?.to_array()?.next().expect("non-empty");
+    ///
+    /// assert_eq!(json.value().depth(), 0);
+    /// assert_eq!(user.depth(), 1);
+    /// assert_eq!(tag.depth(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn depth(self) -> usize {
+        let mut value = self.root();
+        let mut depth = 0;
+        while value.index != self.index {
+            value = Children::new(value)
+                .find(|c| c.index <= self.index && self.index < c.entry().end_index)
+                .expect("infallible");
+            depth += 1;
+        }
+        depth
     }
 
-    /// Converts this value to a borrowed [`RawJson`] containing just this value and its children.
-    ///
-    /// This method creates a borrowed view of this specific JSON value and its text,
-    /// including all nested children if the value is an object or array. The resulting
-    /// [`RawJson`] contains only this value and its descendants as its root,
-    /// not the entire original JSON text.
+    /// Returns the (0-based) position of this value within its parent array.
     ///
-    /// If you need an owned version, you can call `.into_owned()` on the result.
+    /// Returns `None` if this value is not an array element.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"{"tags": ["a", "b"]}"#)?;
+    /// let tags = json.value().to_member("tags")?.required()?;
+    /// let b = tags.to_array()?.nth(1).expect("exists");
+    ///
+    /// assert_eq!(b.index_in_parent(), Some(1));
+    /// assert_eq!(tags.index_in_parent(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn index_in_parent(self) -> Option<usize> {
+        let parent = self.parent().filter(|p| p.kind().is_array())?;
+        Children::new(parent).position(|v| v.index == self.index)
+    }
+
+    /// Returns the name of the object member whose value is this value.
+    ///
+    /// Returns `None` if this value is not an object member value
+    /// (including when this value is itself a member name).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"{"user": {"age": 30}}"#)?;
+    /// let age = json.value().to_member("user")?.required()?.to_member("age")?.required()?;
+    ///
+    /// let name = age.member_name().expect("member value");
+    /// assert_eq!(name.to_unquoted_string_str()?, "age");
+    /// assert_eq!(json.value().member_name(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn member_name(self) -> Option<Self> {
+        let parent = self.parent().filter(|p| p.kind().is_object())?;
+        JsonKeyValuePairs::new(parent)
+            .find(|(_, v)| v.index == self.index)
+            .map(|(name, _)| name)
+    }
+
+    /// Returns the raw JSON text of this value as-is.
+    pub fn as_raw_str(self) -> &'text str {
+        let text = &self.json.values[self.index].text;
+        &self.json.text[text.start..text.end]
+    }
+
+    /// Converts this value to a borrowed [`RawJson`] containing just this value and its children.
+    ///
+    /// This method creates a borrowed view of this specific JSON value and its text,
+    /// including all nested children if the value is an object or array. The resulting
+    /// [`RawJson`] contains only this value and its descendants as its root,
+    /// not the entire original JSON text.
+    ///
+    /// If you need an owned version, you can call `.into_owned()` on the result.
     ///
     /// # Example
     ///
@@ -821,6 +1538,7 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
             .map(|entry| JsonValueIndexEntry {
                 kind: entry.kind,
                 escaped: entry.escaped,
+                extension: entry.extension,
                 text: (entry.text.start - start_pos)..(entry.text.end - start_pos),
                 end_index: entry.end_index - start_index,
             })
@@ -832,6 +1550,181 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
         }
     }
 
+    /// Feeds the structural content of this value into `hasher`.
+    ///
+    /// Values that are equal according to [`json_eq()`](crate::json_eq) produce the same hash,
+    /// regardless of whitespace, member order, string escapes, or number notation.
+    /// As in [`json_eq()`](crate::json_eq), the last member wins if an object has duplicate names.
+    /// This is useful as a key for caches that deduplicate semantically equal documents.
+    ///
+    /// Note that [`Hash`] for [`RawJsonValue`] is different: it hashes the raw document text
+    /// and the index of the value, so it is sensitive to formatting.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// use std::hash::{DefaultHasher, Hasher};
+    ///
+    /// fn hash(text: &str) -> Result<u64, nojson::JsonParseError> {
+    ///     let mut hasher = DefaultHasher::new();
+    ///     nojson::RawJson::parse(text)?.value().canonical_hash(&mut hasher);
+    ///     Ok(hasher.finish())
+    /// }
+    ///
+    /// assert_eq!(hash(r#"{"a": 1, "b": "x"}"#)?, hash(r#"{"b":"\u0078","a":1.0}"#)?);
+    /// assert_ne!(hash("[1, 2]")?, hash("[2, 1]")?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn canonical_hash<H: core::hash::Hasher>(self, hasher: &mut H) {
+        crate::compare::hash_value(self, hasher);
+    }
+
+    /// Compares this value with another by content, defining a total order over JSON values.
+    ///
+    /// Values are ordered first by kind (`null` < booleans < numbers < strings < arrays < objects,
+    /// followed by [extension](RawJsonValue::is_extension) values), then by content:
+    /// - `false` < `true`
+    /// - Numbers by their exact decimal value, regardless of notation (e.g., `1`, `1.0` and `10e-1` are equal)
+    /// - Strings by their unescaped content, in lexicographic order of code points
+    /// - Arrays lexicographically by element
+    /// - Objects as lists of members sorted by name, compared lexicographically by name and then by value
+    ///   (if an object has duplicate names, the last one wins)
+    ///
+    /// Whitespace and member order don't affect the result, so this can be used to sort,
+    /// deduplicate, or binary-search documents deterministically.
+    /// Values that compare as equal are also equal according to [`json_eq()`](crate::json_eq).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// use std::cmp::Ordering;
+    ///
+    /// let json = nojson::RawJson::parse(r#"[null, 10, 9.5, "a", {"x": 1, "y": 2}, {"y": 2, "x": 1e0}]"#)?;
+    /// let values = json.value().to_array()?.collect::<Vec<_>>();
+    ///
+    /// assert_eq!(values[0].structural_cmp(values[1]), Ordering::Less);
+    /// assert_eq!(values[1].structural_cmp(values[2]), Ordering::Greater);
+    /// assert_eq!(values[3].structural_cmp(values[4]), Ordering::Less);
+    /// assert_eq!(values[4].structural_cmp(values[5]), Ordering::Equal);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn structural_cmp(self, other: RawJsonValue<'_, '_>) -> core::cmp::Ordering {
+        crate::compare::cmp_values(self, other)
+    }
+
+    /// Treats this value as a template and replaces placeholder strings with the values returned by `lookup`.
+    ///
+    /// A placeholder is a string value consisting of a name enclosed in double braces
+    /// (e.g., `"{{name}}"` or `"{{ name }}"`).
+    /// It is replaced by `lookup(name)` formatted as JSON, so numbers, booleans, arrays, and objects
+    /// keep their types and strings are properly escaped.
+    /// Placeholders for which `lookup` returns `None`, placeholders embedded in longer strings,
+    /// and member names are written unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// use std::collections::BTreeMap;
+    ///
+    /// let template = nojson::RawJson::parse(
+    ///     r#"{"user": "{{name}}", "age": "{{ age }}", "tags": ["{{tag}}", "{{unknown}}"], "note": "hi {{name}}"}"#,
+    /// )?;
+    /// let values = BTreeMap::from([
+    ///     ("name", &"Alice \"A\"" as &dyn nojson::DisplayJson),
+    ///     ("age", &30),
+    ///     ("tag", &["x", "y"]),
+    /// ]);
+    ///
+    /// let output = template.value().fill_template(|name| values.get(name));
+    /// assert_eq!(
+    ///     output.to_string(),
+    ///     r#"{"user":"Alice \"A\"","age":30,"tags":[["x","y"],"{{unknown}}"],"note":"hi {{name}}"}"#
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fill_template<F, V>(self, lookup: F) -> impl DisplayJson + Display
+    where
+        F: Fn(&str) -> Option<V>,
+        V: DisplayJson,
+    {
+        crate::json(move |f| fmt_template(self, &lookup, f))
+    }
+
+    /// Verifies that the kind of the value is one of `kinds`.
+    ///
+    /// On mismatch, returns a [`JsonParseError::InvalidValue`] error with
+    /// [`JsonErrorCode::UnexpectedKind`] pointing at this value,
+    /// worded the same as the errors of the built-in conversions
+    /// (e.g., `expected one of [Integer, Float], but found String`).
+    /// This is useful for custom decoders that accept values of several kinds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// use nojson::JsonValueKind;
+    ///
+    /// let json = nojson::RawJson::parse(r#"[1, "1"]"#)?;
+    /// let values = json.value().to_array()?.collect::<Vec<_>>();
+    /// let numeric = [JsonValueKind::Integer, JsonValueKind::Float];
+    ///
+    /// assert_eq!(values[0].expect_kind(&numeric)?.as_raw_str(), "1");
+    ///
+    /// let e = values[1].expect_kind(&numeric).expect_err("string");
+    /// assert_eq!(e.position(), 4);
+    /// assert!(e.to_string().contains("expected one of [Integer, Float], but found String"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn expect_kind(self, kinds: &[JsonValueKind]) -> Result<Self, JsonParseError> {
+        if kinds.contains(&self.kind()) && !self.is_extension() {
+            Ok(self)
+        } else {
+            Err(self
+                .invalid(format!(
+                    "expected {}, but found {}",
+                    if kinds.len() == 1 {
+                        format!("{:?}", kinds[0])
+                    } else {
+                        format!("one of {kinds:?}")
+                    },
+                    if self.is_extension() {
+                        "a non-standard token".to_owned()
+                    } else {
+                        format!("{:?}", self.kind())
+                    }
+                ))
+                .with_code(JsonErrorCode::UnexpectedKind))
+        }
+    }
+
+    /// Verifies that the value is JSON `null`.
+    ///
+    /// This is useful for validating members that must be exactly `null`,
+    /// as the returned error points at the offending value like the other kind checks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"{"deleted_at": null, "id": 0}"#)?;
+    /// json.value().to_member("deleted_at")?.required()?.as_null()?;
+    ///
+    /// let e = json.value().to_member("id")?.required()?.as_null().expect_err("not null");
+    /// assert_eq!(e.position(), 27);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_null(self) -> Result<(), JsonParseError> {
+        self.expect_kind(&[JsonValueKind::Null]).map(|_| ())
+    }
+
     /// Similar to [`RawJsonValue::as_raw_str()`],
     /// but this method verifies whether the value is a JSON boolean.
     ///
@@ -848,7 +1741,7 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
     /// # }
     /// ```
     pub fn as_boolean_str(self) -> Result<&'text str, JsonParseError> {
-        self.expect([JsonValueKind::Boolean])
+        self.expect_kind(&[JsonValueKind::Boolean])
             .map(|v| v.as_raw_str())
     }
 
@@ -868,7 +1761,7 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
     /// # }
     /// ```
     pub fn as_integer_str(self) -> Result<&'text str, JsonParseError> {
-        self.expect([JsonValueKind::Integer])
+        self.expect_kind(&[JsonValueKind::Integer])
             .map(|v| v.as_raw_str())
     }
 
@@ -888,7 +1781,8 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
     /// # }
     /// ```
     pub fn as_float_str(self) -> Result<&'text str, JsonParseError> {
-        self.expect([JsonValueKind::Float]).map(|v| v.as_raw_str())
+        self.expect_kind(&[JsonValueKind::Float])
+            .map(|v| v.as_raw_str())
     }
 
     /// Similar to [`RawJsonValue::as_raw_str()`],
@@ -910,10 +1804,53 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
     /// # }
     /// ```
     pub fn as_number_str(self) -> Result<&'text str, JsonParseError> {
-        self.expect([JsonValueKind::Integer, JsonValueKind::Float])
+        self.expect_kind(&[JsonValueKind::Integer, JsonValueKind::Float])
             .map(|v| v.as_raw_str())
     }
 
+    /// Parses this value as an integer, also accepting JSON floating-point numbers
+    /// that represent a whole number (e.g., `1.0` or `1e3`).
+    ///
+    /// The conversion is exact: it operates on the decimal text rather than going through `f64`,
+    /// so a float with a non-zero fractional part, or a value that does not fit in `T`,
+    /// results in a [`JsonParseError::InvalidValue`] error pointing at this value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse("[1, 2.0, 3e2, 4.50e1, 1.5, 1e10]")?;
+    /// let values: Vec<_> = json.value().to_array()?.collect();
+    ///
+    /// assert_eq!(values[0].as_lossless_integer::<u32>()?, 1);
+    /// assert_eq!(values[1].as_lossless_integer::<u32>()?, 2);
+    /// assert_eq!(values[2].as_lossless_integer::<u32>()?, 300);
+    /// assert_eq!(values[3].as_lossless_integer::<u32>()?, 45);
+    ///
+    /// // Fractional part.
+    /// assert!(values[4].as_lossless_integer::<u32>().is_err());
+    ///
+    /// // Out of range.
+    /// assert!(values[5].as_lossless_integer::<u32>().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_lossless_integer<T>(self) -> Result<T, JsonParseError>
+    where
+        T: FromStr,
+        T::Err: Into<Box<dyn Send + Sync + core::error::Error>>,
+    {
+        let text = self.as_number_str()?;
+        let result = if self.kind().is_integer() {
+            text.parse().map_err(|e| self.invalid(e))
+        } else {
+            whole_number_to_integer_str(text)
+                .map_err(|e| self.invalid(e))
+                .and_then(|text| text.parse().map_err(|e| self.invalid(e)))
+        };
+        result.map_err(|e| e.with_code(JsonErrorCode::InvalidNumber))
+    }
+
     /// Similar to [`RawJsonValue::as_raw_str()`],
     /// but this method verifies whether the value is a JSON string and returns
     /// the unquoted content only if the string doesn't require unescaping.
@@ -951,9 +1888,11 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
     /// # }
     /// ```
     pub fn as_string_str(self) -> Result<&'text str, JsonParseError> {
-        self.expect([JsonValueKind::String]).and_then(|v| {
+        self.expect_kind(&[JsonValueKind::String]).and_then(|v| {
             if v.entry().escaped {
-                Err(v.invalid("string requires unescaping"))
+                Err(v
+                    .invalid("string requires unescaping")
+                    .with_code(JsonErrorCode::InvalidString))
             } else {
                 // Safe to unwrap: we know it's a valid JSON string with quotes
                 let raw = v.as_raw_str();
@@ -979,7 +1918,134 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
     /// # }
     /// ```
     pub fn to_unquoted_string_str(self) -> Result<Cow<'text, str>, JsonParseError> {
-        self.expect([JsonValueKind::String]).map(|v| v.unquote())
+        self.expect_kind(&[JsonValueKind::String])
+            .map(|v| v.unquote())
+    }
+
+    /// Verifies that the value is a JSON boolean and returns it as [`bool`].
+    ///
+    /// This is equivalent to `bool::try_from(self)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"[true, "true"]"#)?;
+    /// let values = json.value().to_array()?.collect::<Vec<_>>();
+    /// assert_eq!(values[0].as_bool()?, true);
+    /// assert!(values[1].as_bool().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_bool(self) -> Result<bool, JsonParseError> {
+        self.try_into()
+    }
+
+    /// Verifies that the value is a JSON integer and returns it as [`i64`].
+    ///
+    /// This is equivalent to `i64::try_from(self)`, so out-of-range values and floats are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse("[-3, 1.5, 9223372036854775808]")?;
+    /// let values = json.value().to_array()?.collect::<Vec<_>>();
+    /// assert_eq!(values[0].as_i64()?, -3);
+    /// assert!(values[1].as_i64().is_err());
+    /// assert!(values[2].as_i64().is_err());
+    /// assert_eq!(values[2].as_u64()?, 9223372036854775808);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_i64(self) -> Result<i64, JsonParseError> {
+        self.try_into()
+    }
+
+    /// Verifies that the value is a non-negative JSON integer and returns it as [`u64`].
+    ///
+    /// This is equivalent to `u64::try_from(self)`. See also [`RawJsonValue::as_i64()`].
+    pub fn as_u64(self) -> Result<u64, JsonParseError> {
+        self.try_into()
+    }
+
+    /// Verifies that the value is a JSON number (integer or float) and returns it as [`f64`].
+    ///
+    /// This is equivalent to `f64::try_from(self)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"[1.5, 2, "3"]"#)?;
+    /// let values = json.value().to_array()?.collect::<Vec<_>>();
+    /// assert_eq!(values[0].as_f64()?, 1.5);
+    /// assert_eq!(values[1].as_f64()?, 2.0);
+    /// assert!(values[2].as_f64().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_f64(self) -> Result<f64, JsonParseError> {
+        self.try_into()
+    }
+
+    /// Verifies that the value is a JSON string and returns its unescaped content.
+    ///
+    /// This is an alias of [`RawJsonValue::to_unquoted_string_str()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"["a\nb", 1]"#)?;
+    /// let values = json.value().to_array()?.collect::<Vec<_>>();
+    /// assert_eq!(values[0].as_str()?, "a\nb");
+    /// assert!(values[1].as_str().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_str(self) -> Result<Cow<'text, str>, JsonParseError> {
+        self.to_unquoted_string_str()
+    }
+
+    /// If the value is a JSON string equal to one of `variants`, returns the index of the matching variant.
+    ///
+    /// This is useful for decoding string-valued enums.
+    /// If the string does not match any variant, the returned error lists the allowed values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// #[derive(Debug, PartialEq)]
+    /// enum Level { Debug, Info, Warn, Error }
+    ///
+    /// let json = nojson::RawJson::parse(r#""warn""#)?;
+    /// let level = match json.value().to_enum(&["debug", "info", "warn", "error"])? {
+    ///     0 => Level::Debug,
+    ///     1 => Level::Info,
+    ///     2 => Level::Warn,
+    ///     _ => Level::Error,
+    /// };
+    /// assert_eq!(level, Level::Warn);
+    ///
+    /// let json = nojson::RawJson::parse(r#""trace""#)?;
+    /// let e = json.value().to_enum(&["debug", "info"]).unwrap_err();
+    /// assert!(e.to_string().contains(r#"expected one of "debug", "info""#));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_enum(self, variants: &[&str]) -> Result<usize, JsonParseError> {
+        let s = self.to_unquoted_string_str()?;
+        variants.iter().position(|v| *v == s).ok_or_else(|| {
+            let variants = variants
+                .iter()
+                .map(|v| format!("{v:?}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            self.invalid(format!("expected one of {variants}, but got {s:?}"))
+                .with_code(JsonErrorCode::UnknownVariant)
+        })
     }
 
     /// If the value is a JSON array,
@@ -1011,7 +2077,7 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
     /// # }
     /// ```
     pub fn to_array(self) -> Result<impl Iterator<Item = Self>, JsonParseError> {
-        self.expect([JsonValueKind::Array]).map(Children::new)
+        self.expect_kind(&[JsonValueKind::Array]).map(Children::new)
     }
 
     /// If the value is a JSON object,
@@ -1034,15 +2100,134 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
     /// # }
     /// ```
     pub fn to_object(self) -> Result<impl Iterator<Item = (Self, Self)>, JsonParseError> {
-        self.expect([JsonValueKind::Object])
+        self.expect_kind(&[JsonValueKind::Object])
             .map(JsonKeyValuePairs::new)
     }
 
+    /// Returns an iterator over the direct children of this value, regardless of its kind.
+    ///
+    /// Each item is a pair of the member name (`Some` only for object members) and the child value
+    /// (an array element or an object member value).
+    /// For values other than arrays and objects, the iterator is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"{"a": 1, "b": [2, 3]}"#)?;
+    /// let mut children = json.value().children();
+    /// let (name, value) = children.next().expect("some");
+    /// assert_eq!(name.map(|n| n.as_raw_str()), Some(r#""a""#));
+    /// assert_eq!(value.as_raw_str(), "1");
+    ///
+    /// let (_, b) = children.next().expect("some");
+    /// let elements: Vec<_> = b.children().map(|(name, v)| (name, v.as_raw_str())).collect();
+    /// assert_eq!(elements, [(None, "2"), (None, "3")]);
+    ///
+    /// assert_eq!(json.value().to_member("a")?.required()?.children().count(), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn children(self) -> impl Iterator<Item = (Option<Self>, Self)> {
+        let is_object = self.kind().is_object();
+        let mut inner = Children::new(self);
+        core::iter::from_fn(move || {
+            let child = inner.next()?;
+            if is_object {
+                Some((Some(child), inner.next().expect("infallible")))
+            } else {
+                Some((None, child))
+            }
+        })
+    }
+
+    /// Returns the first child value of this array or object (see [`RawJsonValue::children()`]).
+    ///
+    /// Returns `None` if this value has no children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"[{"x": 1, "y": 2}, 3]"#)?;
+    /// let first = json.value().first_child().expect("some");
+    /// assert_eq!(first.first_child().map(|v| v.as_raw_str()), Some("1"));
+    /// assert_eq!(first.last_child().map(|v| v.as_raw_str()), Some("2"));
+    /// assert_eq!(json.value().last_child().map(|v| v.as_raw_str()), Some("3"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn first_child(self) -> Option<Self> {
+        self.children().next().map(|(_, v)| v)
+    }
+
+    /// Returns the last child value of this array or object (see [`RawJsonValue::children()`]).
+    ///
+    /// Returns `None` if this value has no children.
+    pub fn last_child(self) -> Option<Self> {
+        self.children().last().map(|(_, v)| v)
+    }
+
+    /// If the value is a JSON object, returns its members with duplicate names collapsed
+    /// according to `policy`.
+    ///
+    /// Names are compared after unescaping. Members are yielded in the order in which the kept
+    /// members appear in the text, so collecting them into a map gives the same result
+    /// regardless of the map's insertion behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// use nojson::{DuplicateMemberPolicy, RawJson};
+    ///
+    /// let json = RawJson::parse(r#"{"a": 1, "b": 2, "a": 3}"#)?;
+    /// let members = |policy| -> Result<Vec<_>, nojson::JsonParseError> {
+    ///     Ok(json
+    ///         .value()
+    ///         .to_object_unique(policy)?
+    ///         .map(|(k, v)| (k.as_raw_str(), v.as_raw_str()))
+    ///         .collect())
+    /// };
+    /// assert_eq!(members(DuplicateMemberPolicy::First)?, [(r#""a""#, "1"), (r#""b""#, "2")]);
+    /// assert_eq!(members(DuplicateMemberPolicy::Last)?, [(r#""b""#, "2"), (r#""a""#, "3")]);
+    /// assert!(members(DuplicateMemberPolicy::Reject).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_object_unique(
+        self,
+        policy: DuplicateMemberPolicy,
+    ) -> Result<impl Iterator<Item = (Self, Self)>, JsonParseError> {
+        let mut members = Vec::<Option<(Self, Self)>>::new();
+        let mut positions = BTreeMap::new();
+        for (name, value) in self.to_object()? {
+            let key = name.unquote();
+            if let Some(&prev) = positions.get(&key) {
+                match policy {
+                    DuplicateMemberPolicy::First => continue,
+                    DuplicateMemberPolicy::Last => members[prev] = None,
+                    DuplicateMemberPolicy::Reject => {
+                        return Err(name
+                            .invalid(format!("duplicate member name {}", name.as_raw_str()))
+                            .with_code(JsonErrorCode::DuplicateMember));
+                    }
+                }
+            }
+            positions.insert(key, members.len());
+            members.push(Some((name, value)));
+        }
+        Ok(members.into_iter().flatten())
+    }
+
     /// Attempts to access a member of a JSON object by name.
     ///
     /// The returned [`RawJsonMember`] lets you handle both required and optional
     /// access with [`RawJsonMember::required()`] and [`RawJsonMember::optional()`].
     ///
+    /// If the object has multiple members with the same name, the first one is returned
+    /// (use [`RawJsonValue::to_member_last()`] for the last one).
+    ///
     /// # Performance
     ///
     /// This method has O(n) complexity where n is the number of members in the object,
@@ -1074,10 +2259,118 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
         Ok(RawJsonMember {
             object: self,
             name,
+            case: None,
             member,
         })
     }
 
+    /// Similar to [`RawJsonValue::to_member()`], but looks up the member whose name is `name`
+    /// converted into the naming convention `case` (see [`JsonKeyCase::convert()`]).
+    ///
+    /// This allows Rust field names to be used as-is for objects whose member names follow
+    /// another convention. The converted name is compared with each member name on the fly,
+    /// so no string is allocated for the lookup.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// use nojson::JsonKeyCase;
+    ///
+    /// let json = nojson::RawJson::parse(r#"{"maxRetryCount": 3}"#)?;
+    /// let obj = json.value();
+    ///
+    /// let count: u32 = obj
+    ///     .to_member_as("max_retry_count", JsonKeyCase::CamelCase)?
+    ///     .required()?
+    ///     .try_into()?;
+    /// assert_eq!(count, 3);
+    ///
+    /// // Error messages refer to the converted name.
+    /// let e = obj.to_member_as("retry_delay", JsonKeyCase::CamelCase)?.required().unwrap_err();
+    /// assert!(e.to_string().contains("'retryDelay'"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_member_as<'a>(
+        self,
+        name: &'a str,
+        case: JsonKeyCase,
+    ) -> Result<RawJsonMember<'text, 'raw, 'a>, JsonParseError> {
+        let member = self
+            .to_object()?
+            .find_map(|(k, v)| k.is_string_eq_chars(case.convert_chars(name)).then_some(v));
+
+        Ok(RawJsonMember {
+            object: self,
+            name,
+            case: Some(case),
+            member,
+        })
+    }
+
+    /// Similar to [`RawJsonValue::to_member()`], but returns the last member
+    /// if the object has multiple members with the same name.
+    ///
+    /// This matches the behavior of many JSON implementations, such as JavaScript's `JSON.parse()`,
+    /// which keep the last value of duplicate names.
+    /// Unlike [`RawJsonValue::to_member()`], this method always scans all the members of the object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"{"mode": "dev", "mode": "prod"}"#)?;
+    /// let obj = json.value();
+    ///
+    /// let first: String = obj.to_member("mode")?.required()?.try_into()?;
+    /// let last: String = obj.to_member_last("mode")?.required()?.try_into()?;
+    /// assert_eq!(first, "dev");
+    /// assert_eq!(last, "prod");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_member_last<'a>(
+        self,
+        name: &'a str,
+    ) -> Result<RawJsonMember<'text, 'raw, 'a>, JsonParseError> {
+        let member = self
+            .to_object()?
+            .filter_map(|(k, v)| k.is_string_eq(name).then_some(v))
+            .last();
+        Ok(RawJsonMember {
+            object: self,
+            name,
+            case: None,
+            member,
+        })
+    }
+
+    /// If the value is a JSON object, builds a [`RawJsonObjectIndex`] for repeated member lookups.
+    ///
+    /// Building the index takes O(n log n) time, after which each lookup takes O(log n) time.
+    /// This pays off when many members are looked up by names that are only known at runtime;
+    /// for a few fixed names, [`RawJsonValue::to_member()`] is usually sufficient.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"{"en": "Hello", "fr": "Bonjour"}"#)?;
+    /// let messages = json.value().to_object_index()?;
+    ///
+    /// for lang in ["fr", "de"] {
+    ///     let message: Option<String> = messages.get(lang).try_into()?;
+    ///     println!("{lang}: {message:?}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_object_index(self) -> Result<RawJsonObjectIndex<'text, 'raw>, JsonParseError> {
+        self.expect_kind(&[JsonValueKind::Object])
+            .map(RawJsonObjectIndex::new)
+    }
+
     /// Attempts to access a nested member in a JSON object by a path of member names.
     ///
     /// All intermediate members in `path` are treated as required object members.
@@ -1136,6 +2429,159 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
         current.to_member(last)
     }
 
+    /// Decodes an internally tagged object such as `{"type": "circle", "radius": 1}`.
+    ///
+    /// Returns the value of the `tag` member (which must be a string) and the object itself,
+    /// from which the remaining members can be read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// #[derive(Debug, PartialEq)]
+    /// enum Shape { Circle { radius: u32 }, Square { side: u32 } }
+    ///
+    /// let json = nojson::RawJson::parse(r#"{"type": "circle", "radius": 1}"#)?;
+    /// let (tag, value) = json.value().to_tagged("type")?;
+    /// let shape = match tag.as_ref() {
+    ///     "circle" => Shape::Circle { radius: value.to_member("radius")?.required()?.try_into()? },
+    ///     "square" => Shape::Square { side: value.to_member("side")?.required()?.try_into()? },
+    ///     _ => return Err(value.invalid(format!("unknown shape: {tag}"))),
+    /// };
+    /// assert_eq!(shape, Shape::Circle { radius: 1 });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_tagged(self, tag: &str) -> Result<(Cow<'text, str>, Self), JsonParseError> {
+        let tag = self.to_member(tag)?.required()?.to_unquoted_string_str()?;
+        Ok((tag, self))
+    }
+
+    /// Decodes an adjacently tagged object such as `{"type": "circle", "data": {"radius": 1}}`.
+    ///
+    /// Returns the value of the `tag` member (which must be a string) and the value of the `content` member.
+    /// The `content` member may be omitted (e.g., for variants without data), in which case `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"{"type": "circle", "data": {"radius": 1}}"#)?;
+    /// let (tag, data) = json.value().to_adjacently_tagged("type", "data")?;
+    /// assert_eq!(tag, "circle");
+    /// assert_eq!(data.map(|v| v.as_raw_str()), Some(r#"{"radius": 1}"#));
+    ///
+    /// let json = nojson::RawJson::parse(r#"{"type": "empty"}"#)?;
+    /// let (tag, data) = json.value().to_adjacently_tagged("type", "data")?;
+    /// assert_eq!((tag.as_ref(), data), ("empty", None));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_adjacently_tagged(
+        self,
+        tag: &str,
+        content: &str,
+    ) -> Result<(Cow<'text, str>, Option<Self>), JsonParseError> {
+        let tag = self.to_member(tag)?.required()?.to_unquoted_string_str()?;
+        let content = self.to_member(content)?.optional();
+        Ok((tag, content))
+    }
+
+    /// Decodes an externally tagged object such as `{"circle": {"radius": 1}}`.
+    ///
+    /// The object must have exactly one member, whose name is returned as the tag along with its value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"{"circle": {"radius": 1}}"#)?;
+    /// let (tag, data) = json.value().to_externally_tagged()?;
+    /// assert_eq!(tag, "circle");
+    /// let radius: u32 = data.to_member("radius")?.required()?.try_into()?;
+    /// assert_eq!(radius, 1);
+    ///
+    /// let json = nojson::RawJson::parse(r#"{"a": 1, "b": 2}"#)?;
+    /// assert!(json.value().to_externally_tagged().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_externally_tagged(self) -> Result<(Cow<'text, str>, Self), JsonParseError> {
+        let mut members = self.to_object()?;
+        match (members.next(), members.next()) {
+            (Some((name, value)), None) => Ok((name.unquote(), value)),
+            _ => Err(self
+                .invalid("expected an object with exactly one member")
+                .with_code(JsonErrorCode::InvalidLength)),
+        }
+    }
+
+    /// Returns the member of a JSON object with the given name.
+    ///
+    /// This is a panicking shorthand for `to_member(name)?.required()?`, intended for
+    /// exploratory code and tests where error handling would only add noise.
+    /// (The [`core::ops::Index`] trait cannot be implemented for [`RawJsonValue`]
+    /// because it has to return a reference, whereas child values are created on demand.)
+    ///
+    /// # Panics
+    ///
+    /// Panics if this value is not an object or does not have a member named `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"{"users": [{"name": "Alice"}]}"#)?;
+    /// let name = json.value().expect_member("users").expect_element(0).expect_member("name");
+    /// assert_eq!(name.to_unquoted_string_str()?, "Alice");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn expect_member(self, name: &str) -> Self {
+        match self.to_member(name).map(|member| member.optional()) {
+            Ok(Some(value)) => value,
+            Ok(None) => panic!("JSON object has no member named {name:?}"),
+            Err(_) => panic!(
+                "cannot get member {name:?} of a non-object JSON value ({:?})",
+                self.kind()
+            ),
+        }
+    }
+
+    /// Returns the element of a JSON array at the given index.
+    ///
+    /// This is a panicking shorthand for `to_array()?.nth(index)`, intended for
+    /// exploratory code and tests (see [`RawJsonValue::expect_member()`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if this value is not an array or `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse("[10, [20, 30]]")?;
+    /// let value: u32 = json.value().expect_element(1).expect_element(0).try_into()?;
+    /// assert_eq!(value, 20);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn expect_element(self, index: usize) -> Self {
+        let Ok(mut elements) = self.to_array() else {
+            panic!(
+                "cannot get element {index} of a non-array JSON value ({:?})",
+                self.kind()
+            );
+        };
+        let Some(value) = elements.nth(index) else {
+            panic!("JSON array index {index} is out of bounds");
+        };
+        value
+    }
+
     /// Applies a transformation function to this JSON value.
     ///
     /// This method allows you to transform a `RawJsonValue` into any other type `T`
@@ -1191,6 +2637,54 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
         JsonParseError::invalid_value(self, error)
     }
 
+    // Returns the location of this value as a JSONPath-like string (e.g., `$.users[0].name`).
+    // For an object member name, the path of the corresponding member is returned.
+    pub(crate) fn path(self) -> String {
+        let mut segments = Vec::new();
+        let mut current = self;
+        while let Some(parent) = current.parent() {
+            let mut segment = String::new();
+            if let Some(i) = current.index_in_parent() {
+                segment = format!("[{i}]");
+            } else {
+                // `current` is either a member value or a member name.
+                let name = current.member_name().unwrap_or(current);
+                let name = name.to_unquoted_string_str().expect("infallible");
+                push_member_path(&mut segment, &name);
+            }
+            segments.push(segment);
+            current = parent;
+        }
+
+        let mut path = String::from("$");
+        path.extend(segments.into_iter().rev());
+        path
+    }
+
+    // Creates an `InvalidValue` error pointing at the character at byte `offset` within the unquoted content
+    // of this string value. Falls back to the start of the value if the string contains escapes,
+    // since offsets in the unescaped content don't map directly to the original text.
+    pub(crate) fn invalid_at<E>(self, offset: usize, error: E) -> JsonParseError
+    where
+        E: Into<Box<dyn Send + Sync + core::error::Error>>,
+    {
+        if !self.kind().is_string() || self.entry().escaped {
+            return JsonParseError::invalid_value(self, error);
+        }
+        let start = self.position() + 1 + offset;
+        let len = self.json.text[start..]
+            .chars()
+            .next()
+            .map_or(0, char::len_utf8);
+        JsonParseError::InvalidValue {
+            kind: self.kind(),
+            position: start,
+            end: start + len,
+            code: JsonErrorCode::Custom,
+            error: error.into(),
+        }
+    }
+
     fn unquote(self) -> Cow<'text, str> {
         debug_assert!(self.kind().is_string());
 
@@ -1213,17 +2707,18 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
                         'b' => unescaped.push('\u{8}'),
                         'f' => unescaped.push('\u{c}'),
                         'u' => {
-                            let c = core::str::from_utf8(&[
-                                chars.next().expect("infallible") as u8,
-                                chars.next().expect("infallible") as u8,
-                                chars.next().expect("infallible") as u8,
-                                chars.next().expect("infallible") as u8,
-                            ])
-                            .ok()
-                            .and_then(|code| u32::from_str_radix(code, 16).ok())
-                            .and_then(char::from_u32)
-                            .expect("infallible");
-                            unescaped.push(c);
+                            let s = chars.as_str();
+                            let mut code = crate::parse::decode_hex_code(s).expect("infallible");
+                            let mut len = 4;
+                            if (0xD800..0xDC00).contains(&code) {
+                                // Surrogate pair (validated by the parser): `XXXX\uYYYY`.
+                                let low =
+                                    crate::parse::decode_hex_code(&s[6..]).expect("infallible");
+                                code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                                len = 10;
+                            }
+                            unescaped.push(char::from_u32(code).expect("infallible"));
+                            chars = s[len..].chars();
                         }
                         _ => unreachable!(),
                     }
@@ -1234,26 +2729,42 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
         Cow::Owned(unescaped)
     }
 
-    fn expect<const N: usize>(self, kinds: [JsonValueKind; N]) -> Result<Self, JsonParseError> {
-        if kinds.contains(&self.kind()) {
-            Ok(self)
-        } else {
-            Err(self.invalid(format!(
-                "expected {}, but found {:?}",
-                if kinds.len() == 1 {
-                    format!("{:?}", kinds[0])
-                } else {
-                    format!("one of {kinds:?}")
-                },
-                self.kind()
-            )))
+    // Makes the root value of a document whose index entries are `values`.
+    pub(crate) fn root_of(text: &'text str, values: &'raw [JsonValueIndexEntry]) -> Self {
+        Self {
+            json: RawJsonRef { text, values },
+            index: 0,
         }
     }
 
     fn find_member_by_name(self, name: &str) -> Result<Option<Self>, JsonParseError> {
         Ok(self
             .to_object()?
-            .find_map(|(k, v)| (k.unquote() == name).then_some(v)))
+            .find_map(|(k, v)| k.is_string_eq(name).then_some(v)))
+    }
+
+    // Compares the unescaped content of this string value with `s`,
+    // avoiding the unescaping allocation whenever possible.
+    fn is_string_eq(self, s: &str) -> bool {
+        let content = &self.as_raw_str()[1..self.as_raw_str().len() - 1];
+        if !self.entry().escaped {
+            content == s
+        } else if content.len() < s.len() {
+            // Unescaping never makes a string longer.
+            false
+        } else {
+            self.unquote() == s
+        }
+    }
+
+    // Similar to `is_string_eq()`, but compares with a string given as characters.
+    fn is_string_eq_chars<I: Iterator<Item = char>>(self, chars: I) -> bool {
+        let content = &self.as_raw_str()[1..self.as_raw_str().len() - 1];
+        if !self.entry().escaped {
+            content.chars().eq(chars)
+        } else {
+            self.unquote().chars().eq(chars)
+        }
     }
 
     fn entry(&self) -> &JsonValueIndexEntry {
@@ -1261,6 +2772,50 @@ impl<'text, 'raw> RawJsonValue<'text, 'raw> {
     }
 }
 
+impl PartialEq<&str> for RawJsonValue<'_, '_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.kind() == JsonValueKind::String && self.is_string_eq(other)
+    }
+}
+
+impl PartialEq<i64> for RawJsonValue<'_, '_> {
+    fn eq(&self, other: &i64) -> bool {
+        self.kind() == JsonValueKind::Integer && self.as_raw_str().parse() == Ok(*other)
+    }
+}
+
+impl PartialEq<f64> for RawJsonValue<'_, '_> {
+    fn eq(&self, other: &f64) -> bool {
+        self.kind().is_number() && self.as_raw_str().parse() == Ok(*other)
+    }
+}
+
+impl PartialEq<bool> for RawJsonValue<'_, '_> {
+    fn eq(&self, other: &bool) -> bool {
+        self.kind() == JsonValueKind::Boolean && (self.as_raw_str() == "true") == *other
+    }
+}
+
+// Maximum number of characters of the raw text shown by `Debug` for `RawJsonValue`.
+const DEBUG_SNIPPET_LEN: usize = 40;
+
+impl core::fmt::Debug for RawJsonValue<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let text = self.as_raw_str();
+        let position = self.position();
+        let snippet = match text.char_indices().nth(DEBUG_SNIPPET_LEN) {
+            Some((end, _)) => Cow::Owned(format!("{}…", &text[..end])),
+            None => Cow::Borrowed(text),
+        };
+        f.debug_struct("RawJsonValue")
+            .field("kind", &self.kind())
+            .field("span", &(position..position + text.len()))
+            .field("path", &self.path())
+            .field("text", &snippet)
+            .finish()
+    }
+}
+
 impl Display for RawJsonValue<'_, '_> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", crate::Json(self))
@@ -1273,7 +2828,7 @@ impl DisplayJson for RawJsonValue<'_, '_> {
             JsonValueKind::Null
             | JsonValueKind::Boolean
             | JsonValueKind::Integer
-            | JsonValueKind::Float => write!(f.inner_mut(), "{}", self.as_raw_str()),
+            | JsonValueKind::Float => f.literal(self.kind(), self.as_raw_str()),
             JsonValueKind::String => f.string(self.unquote()),
             JsonValueKind::Array => f.array(|f| f.elements(self.to_array().expect("infallible"))),
             JsonValueKind::Object => f.object(|f| {
@@ -1287,6 +2842,98 @@ impl DisplayJson for RawJsonValue<'_, '_> {
     }
 }
 
+// Formats `value` with its placeholder strings replaced (see `RawJsonValue::fill_template()`).
+fn fmt_template<F, V>(
+    value: RawJsonValue<'_, '_>,
+    lookup: &F,
+    f: &mut JsonFormatter<'_, '_>,
+) -> core::fmt::Result
+where
+    F: Fn(&str) -> Option<V>,
+    V: DisplayJson,
+{
+    match value.kind() {
+        JsonValueKind::String => {
+            let content = value.unquote();
+            let replacement = content
+                .strip_prefix("{{")
+                .and_then(|s| s.strip_suffix("}}"))
+                .and_then(|name| lookup(name.trim()));
+            match replacement {
+                Some(replacement) => f.value(replacement),
+                None => f.value(value),
+            }
+        }
+        JsonValueKind::Array => f.array(|f| {
+            for element in value.to_array().expect("infallible") {
+                f.element(crate::json(|f| fmt_template(element, lookup, f)))?;
+            }
+            Ok(())
+        }),
+        JsonValueKind::Object => f.object(|f| {
+            for (name, member) in value.to_object().expect("infallible") {
+                f.member(
+                    name.unquote(),
+                    crate::json(|f| fmt_template(member, lookup, f)),
+                )?;
+            }
+            Ok(())
+        }),
+        _ => f.value(value),
+    }
+}
+
+// Appends a member access segment (`.name` or `["name"]`) to a JSONPath-like string.
+pub(crate) fn push_member_path(path: &mut String, name: &str) {
+    if crate::format::is_identifier(name) {
+        path.push('.');
+        path.push_str(name);
+    } else {
+        path.push_str(&format!("[{name:?}]"));
+    }
+}
+
+// Converts the text of a JSON float (e.g., `12.50e1`) into the text of the equivalent integer
+// (e.g., `125`), failing if the value has a non-zero fractional part.
+fn whole_number_to_integer_str(text: &str) -> Result<String, &'static str> {
+    // Long enough for any integer representable by the built-in integer types.
+    const MAX_INTEGER_DIGITS: usize = 40;
+    const OUT_OF_RANGE: &str = "number too large to fit in target type";
+
+    let (sign, unsigned) = match text.strip_prefix('-') {
+        Some(s) => ("-", s),
+        None => ("", text),
+    };
+    let (mantissa, exponent) = unsigned.split_once(['e', 'E']).unwrap_or((unsigned, "0"));
+    let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+    let digits = format!("{int_part}{frac_part}");
+    let leading_zeros = digits.len() - digits.trim_start_matches('0').len();
+    let digits = digits[leading_zeros..].trim_end_matches('0');
+    if digits.is_empty() {
+        return Ok("0".to_owned());
+    }
+
+    let Ok(exponent) = exponent.parse::<i64>() else {
+        return Err(if exponent.starts_with('-') {
+            "expected a whole number, but found a fractional part"
+        } else {
+            OUT_OF_RANGE
+        });
+    };
+    // Number of digits before the decimal point once leading zeros are removed.
+    let point = (int_part.len() as i64 - leading_zeros as i64).saturating_add(exponent);
+    if point < digits.len() as i64 {
+        return Err("expected a whole number, but found a fractional part");
+    }
+    if point > MAX_INTEGER_DIGITS as i64 {
+        return Err(OUT_OF_RANGE);
+    }
+
+    let zeros = point as usize - digits.len();
+    Ok(format!("{sign}{digits}{:0<zeros$}", ""))
+}
+
 #[derive(Debug)]
 struct Children<'text, 'raw> {
     value: RawJsonValue<'text, 'raw>,
@@ -1337,6 +2984,20 @@ impl<'text, 'raw> Iterator for JsonKeyValuePairs<'text, 'raw> {
     }
 }
 
+/// A policy for handling object members with duplicate names
+/// (see [`RawJsonValue::to_object_unique()`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DuplicateMemberPolicy {
+    /// Keeps the first member, as [`RawJsonValue::to_member()`] does.
+    First,
+
+    /// Keeps the last member, as [`RawJsonValue::to_member_last()`] does.
+    Last,
+
+    /// Fails with a [`JsonErrorCode::DuplicateMember`] error pointing at the second occurrence.
+    Reject,
+}
+
 /// Represents a member access result for a JSON object.
 ///
 /// This struct is returned by [`RawJsonValue::to_member()`] and allows you to handle
@@ -1366,6 +3027,8 @@ impl<'text, 'raw> Iterator for JsonKeyValuePairs<'text, 'raw> {
 pub struct RawJsonMember<'text, 'raw, 'a> {
     object: RawJsonValue<'text, 'raw>,
     name: &'a str,
+    // The naming convention `name` is converted into (see `RawJsonValue::to_member_as()`).
+    case: Option<JsonKeyCase>,
     member: Option<RawJsonValue<'text, 'raw>>,
 }
 
@@ -1394,8 +3057,13 @@ impl<'text, 'raw, 'a> RawJsonMember<'text, 'raw, 'a> {
     /// ```
     pub fn required(self) -> Result<RawJsonValue<'text, 'raw>, JsonParseError> {
         self.member.ok_or_else(|| {
+            let name = match self.case {
+                Some(case) => Cow::Owned(case.convert(self.name)),
+                None => Cow::Borrowed(self.name),
+            };
             self.object
-                .invalid(format!("required member '{}' is missing", self.name))
+                .invalid(format!("required member '{name}' is missing"))
+                .with_code(JsonErrorCode::MissingMember)
         })
     }
 
@@ -1445,6 +3113,41 @@ impl<'text, 'raw, 'a> RawJsonMember<'text, 'raw, 'a> {
         self.optional()
     }
 
+    /// Converts the member into a three-state value that distinguishes a missing member from an explicit `null`.
+    ///
+    /// - `None`: the member is missing
+    /// - `Some(None)`: the member is `null`
+    /// - `Some(Some(v))`: the member has a value
+    ///
+    /// This is useful for PATCH-style updates where omitting a field (leave unchanged)
+    /// and setting it to `null` (clear the field) have different meanings.
+    /// Converting via `TryFrom<RawJsonMember>` into `Option<Option<T>>` gives the same result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), nojson::JsonParseError> {
+    /// let json = nojson::RawJson::parse(r#"{"name": "Alice", "email": null}"#)?;
+    /// let obj = json.value();
+    ///
+    /// let name = obj.to_member("name")?.into_option_option::<String>()?;
+    /// assert_eq!(name, Some(Some("Alice".to_owned())));
+    ///
+    /// let email = obj.to_member("email")?.into_option_option::<String>()?;
+    /// assert_eq!(email, Some(None));
+    ///
+    /// let phone: Option<Option<String>> = obj.to_member("phone")?.try_into()?;
+    /// assert_eq!(phone, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_option_option<T>(self) -> Result<Option<Option<T>>, JsonParseError>
+    where
+        T: TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError>,
+    {
+        self.try_into()
+    }
+
     /// Applies a transformation function to the member value if it exists.
     ///
     /// This method is similar to [`Option::map`], but designed for transformations
@@ -1507,3 +3210,68 @@ where
         value.member.map(T::try_from).transpose()
     }
 }
+
+/// A lookup table of the members of a JSON object, built by [`RawJsonValue::to_object_index()`].
+///
+/// Member names are unescaped once when the index is built, so each subsequent lookup
+/// takes O(log n) time instead of scanning the object.
+/// If the object contains duplicate names, the first occurrence wins (as in [`RawJsonValue::to_member()`]).
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// let json = nojson::RawJson::parse(r#"{"host": "localhost", "port": 8080}"#)?;
+/// let index = json.value().to_object_index()?;
+///
+/// for name in ["host", "port"] {
+///     assert!(index.get(name).optional().is_some());
+/// }
+/// let port: u16 = index.get("port").required()?.try_into()?;
+/// assert_eq!(port, 8080);
+/// assert!(index.get("user").required().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RawJsonObjectIndex<'text, 'raw> {
+    object: RawJsonValue<'text, 'raw>,
+    members: BTreeMap<Cow<'text, str>, RawJsonValue<'text, 'raw>>,
+}
+
+impl<'text, 'raw> RawJsonObjectIndex<'text, 'raw> {
+    fn new(object: RawJsonValue<'text, 'raw>) -> Self {
+        let mut members = BTreeMap::new();
+        for (name, value) in JsonKeyValuePairs::new(object) {
+            members.entry(name.unquote()).or_insert(value);
+        }
+        Self { object, members }
+    }
+
+    /// Looks up a member by name.
+    ///
+    /// The result behaves the same as that of [`RawJsonValue::to_member()`].
+    pub fn get<'a>(&self, name: &'a str) -> RawJsonMember<'text, 'raw, 'a> {
+        RawJsonMember {
+            object: self.object,
+            name,
+            case: None,
+            member: self.members.get(name).copied(),
+        }
+    }
+
+    /// Returns the indexed object.
+    pub fn object(&self) -> RawJsonValue<'text, 'raw> {
+        self.object
+    }
+
+    /// Returns the number of distinct member names.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns `true` if the object has no members.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+}
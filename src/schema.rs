@@ -0,0 +1,302 @@
+use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
+use core::ops::Bound;
+
+use crate::{
+    JsonErrorCode, JsonParseError, RawJsonOwned, RawJsonValue, compare::values_eq, validate,
+};
+
+/// A validator for a subset of [JSON Schema] (draft-07).
+///
+/// A schema is parsed from a JSON document via [`TryFrom<RawJsonValue>`],
+/// and instances are checked with [`JsonSchema::validate()`].
+///
+/// The following keywords are supported; any other keywords (e.g., `$schema`, `title`,
+/// `description`, or `format`) are ignored:
+///
+/// - `type` (a single type name or an array of type names)
+/// - `enum`
+/// - `minimum`, `maximum`, `exclusiveMinimum`, `exclusiveMaximum`
+/// - `minLength`, `maxLength`
+/// - `items` (a single schema applied to all elements), `minItems`, `maxItems`
+/// - `properties`, `required`, `additionalProperties`
+///
+/// Boolean schemas (`true` and `false`) are supported as well.
+///
+/// This type is available only when the `schema` feature is enabled.
+///
+/// [JSON Schema]: https://json-schema.org/
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{JsonSchema, RawJson};
+///
+/// let schema_text = r#"{
+///     "type": "object",
+///     "properties": {
+///         "event": {"enum": ["push", "pull_request"]},
+///         "commits": {"type": "array", "items": {"type": "string"}, "maxItems": 2}
+///     },
+///     "required": ["event"]
+/// }"#;
+/// let schema: JsonSchema = RawJson::parse(schema_text)?.value().try_into()?;
+///
+/// let json = RawJson::parse(r#"{"event": "push", "commits": ["a1b2"]}"#)?;
+/// assert!(schema.validate(json.value()).is_ok());
+///
+/// let json = RawJson::parse(r#"{"event": "tag", "commits": ["a1b2", 3]}"#)?;
+/// let errors = schema.validate(json.value()).unwrap_err();
+/// assert_eq!(errors.len(), 2);
+/// assert!(errors[0].to_string().contains("$.event"));
+/// assert!(errors[1].to_string().contains("$.commits[1]"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct JsonSchema {
+    reject_all: bool,
+    types: Option<Vec<SchemaType>>,
+    enum_values: Option<Vec<RawJsonOwned>>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    exclusive_minimum: Option<f64>,
+    exclusive_maximum: Option<f64>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    items: Option<Box<JsonSchema>>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    properties: Vec<(String, JsonSchema)>,
+    required: Vec<String>,
+    additional_properties: Option<Box<JsonSchema>>,
+}
+
+impl JsonSchema {
+    /// Validates `value` against this schema.
+    ///
+    /// All violations are returned as [`JsonParseError::InvalidValue`] errors in document order,
+    /// each pointing at the offending value and mentioning its path (e.g., `$.users[0].name`).
+    pub fn validate(&self, value: RawJsonValue<'_, '_>) -> Result<(), Vec<JsonParseError>> {
+        let mut errors = Vec::new();
+        self.collect_errors(value, &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn collect_errors(&self, value: RawJsonValue<'_, '_>, errors: &mut Vec<JsonParseError>) {
+        let kind = value.kind();
+        if self.reject_all {
+            let e = value.invalid(format!("{}: no value is allowed", value.path()));
+            errors.push(e.with_code(JsonErrorCode::ConstraintViolation));
+            return;
+        }
+        let unmatched_types = self
+            .types
+            .as_ref()
+            .filter(|types| !types.iter().any(|ty| ty.matches(value)));
+        if let Some(types) = unmatched_types {
+            let expected = types
+                .iter()
+                .map(|ty| ty.name())
+                .collect::<Vec<_>>()
+                .join(" or ");
+            let e = value.invalid(format!(
+                "{}: expected {expected}, but got {kind:?}",
+                value.path()
+            ));
+            errors.push(e.with_code(JsonErrorCode::UnexpectedKind));
+            return;
+        }
+        let unmatched_values = self
+            .enum_values
+            .as_ref()
+            .filter(|values| !values.iter().any(|v| values_eq(v.value(), value)));
+        if let Some(values) = unmatched_values {
+            let values = values.iter().map(|v| v.text()).collect::<Vec<_>>();
+            let e = value.invalid(format!(
+                "{}: must be one of {}",
+                value.path(),
+                values.join(", ")
+            ));
+            errors.push(e.with_code(JsonErrorCode::ConstraintViolation));
+        }
+
+        if kind.is_number() {
+            let ranges = [
+                (self.minimum.map(Bound::Included), None),
+                (self.exclusive_minimum.map(Bound::Excluded), None),
+                (None, self.maximum.map(Bound::Included)),
+                (None, self.exclusive_maximum.map(Bound::Excluded)),
+            ];
+            for (lower, upper) in ranges {
+                if lower.is_none() && upper.is_none() {
+                    continue;
+                }
+                let range = (
+                    lower.unwrap_or(Bound::Unbounded),
+                    upper.unwrap_or(Bound::Unbounded),
+                );
+                if let Err(e) = validate::range::<f64, _>(value, range) {
+                    errors.push(e);
+                }
+            }
+        } else if kind.is_string() {
+            check_length(value, self.min_length, self.max_length, errors);
+        } else if kind.is_array() {
+            check_length(value, self.min_items, self.max_items, errors);
+            if let Some(items) = &self.items {
+                for v in value.to_array().expect("infallible") {
+                    items.collect_errors(v, errors);
+                }
+            }
+        } else if kind.is_object() {
+            for (name, v) in value.to_object().expect("infallible") {
+                let name = name.to_unquoted_string_str().expect("infallible");
+                match self.properties.iter().find(|(n, _)| *n == name) {
+                    Some((_, schema)) => schema.collect_errors(v, errors),
+                    None => {
+                        if let Some(schema) = &self.additional_properties {
+                            schema.collect_errors(v, errors);
+                        }
+                    }
+                }
+            }
+            for name in &self.required {
+                let member = value.to_member(name).expect("infallible");
+                if member.optional().is_none() {
+                    let e = value.invalid(format!(
+                        "{}: missing required member {name:?}",
+                        value.path()
+                    ));
+                    errors.push(e.with_code(JsonErrorCode::MissingMember));
+                }
+            }
+        }
+    }
+}
+
+fn check_length(
+    value: RawJsonValue<'_, '_>,
+    min: Option<usize>,
+    max: Option<usize>,
+    errors: &mut Vec<JsonParseError>,
+) {
+    if min.is_none() && max.is_none() {
+        return;
+    }
+    let range = (
+        min.map_or(Bound::Unbounded, Bound::Included),
+        max.map_or(Bound::Unbounded, Bound::Included),
+    );
+    if let Err(e) = validate::length(value, range) {
+        errors.push(e);
+    }
+}
+
+impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for JsonSchema {
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        if value.kind().is_bool() {
+            return Ok(Self {
+                reject_all: !bool::try_from(value)?,
+                ..Self::default()
+            });
+        }
+
+        let mut schema = Self::default();
+        for (name, v) in value.to_object()? {
+            match name.to_unquoted_string_str()?.as_ref() {
+                "type" if v.kind().is_array() => {
+                    schema.types = Some(
+                        v.to_array()?
+                            .map(SchemaType::parse)
+                            .collect::<Result<_, _>>()?,
+                    );
+                }
+                "type" => schema.types = Some(vec![SchemaType::parse(v)?]),
+                "enum" => {
+                    schema.enum_values =
+                        Some(v.to_array()?.map(|v| v.extract().into_owned()).collect());
+                }
+                "minimum" => schema.minimum = Some(v.try_into()?),
+                "maximum" => schema.maximum = Some(v.try_into()?),
+                "exclusiveMinimum" => schema.exclusive_minimum = Some(v.try_into()?),
+                "exclusiveMaximum" => schema.exclusive_maximum = Some(v.try_into()?),
+                "minLength" => schema.min_length = Some(v.try_into()?),
+                "maxLength" => schema.max_length = Some(v.try_into()?),
+                "items" => schema.items = Some(Box::new(v.try_into()?)),
+                "minItems" => schema.min_items = Some(v.try_into()?),
+                "maxItems" => schema.max_items = Some(v.try_into()?),
+                "properties" => {
+                    for (name, v) in v.to_object()? {
+                        let name = name.to_unquoted_string_str()?.into_owned();
+                        schema.properties.push((name, v.try_into()?));
+                    }
+                }
+                "required" => schema.required = v.try_into()?,
+                "additionalProperties" => {
+                    schema.additional_properties = Some(Box::new(v.try_into()?));
+                }
+                _ => {}
+            }
+        }
+        Ok(schema)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SchemaType {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl SchemaType {
+    const NAMES: [&str; 7] = [
+        "null", "boolean", "integer", "number", "string", "array", "object",
+    ];
+    const ALL: [Self; 7] = [
+        Self::Null,
+        Self::Boolean,
+        Self::Integer,
+        Self::Number,
+        Self::String,
+        Self::Array,
+        Self::Object,
+    ];
+
+    fn parse(value: RawJsonValue<'_, '_>) -> Result<Self, JsonParseError> {
+        value.to_enum(&Self::NAMES).map(|i| Self::ALL[i])
+    }
+
+    fn name(self) -> &'static str {
+        Self::NAMES[self as usize]
+    }
+
+    fn matches(self, value: RawJsonValue<'_, '_>) -> bool {
+        let kind = value.kind();
+        match self {
+            Self::Null => kind.is_null() && !value.is_extension(),
+            Self::Boolean => kind.is_bool(),
+            // In JSON Schema, numbers with a zero fractional part (e.g., `1.0`) are integers.
+            Self::Integer => {
+                kind.is_integer()
+                    || (kind.is_float()
+                        && f64::try_from(value).is_ok_and(|n| n.is_finite() && n.fract() == 0.0))
+            }
+            Self::Number => kind.is_number(),
+            Self::String => kind.is_string(),
+            Self::Array => kind.is_array(),
+            Self::Object => kind.is_object(),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use core::ops::Bound;
+
+use crate::{JsonErrorCode, JsonParseError, RawJsonValue, validate};
+
+/// A lightweight, declarative description of the expected structure of a JSON value.
+///
+/// [`JsonShape`] sits between ad-hoc [`TryFrom`] implementations and a full JSON Schema engine:
+/// it checks value kinds, numeric ranges, lengths, and object members,
+/// and reports every violation at once (see [`JsonShape::validate()`]).
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{JsonShape, RawJson};
+///
+/// let shape = JsonShape::object()
+///     .member("name", JsonShape::string().min_len(1))
+///     .member("age", JsonShape::integer().min(0.0))
+///     .optional_member("tags", JsonShape::array(JsonShape::string()));
+///
+/// let json = RawJson::parse(r#"{"name": "Alice", "age": 30, "tags": ["a"]}"#)?;
+/// assert!(shape.validate(json.value()).is_ok());
+///
+/// let json = RawJson::parse(r#"{"name": "", "age": -1, "tags": [1]}"#)?;
+/// let errors = shape.validate(json.value()).unwrap_err();
+/// assert_eq!(errors.len(), 3);
+/// assert!(errors[0].to_string().contains("$.name"));
+/// assert!(errors[1].to_string().contains("$.age"));
+/// assert!(errors[2].to_string().contains("$.tags[0]"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct JsonShape {
+    kind: ShapeKind,
+    nullable: bool,
+    min: Option<f64>,
+    max: Option<f64>,
+    min_len: Option<usize>,
+    max_len: Option<usize>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+enum ShapeKind {
+    #[default]
+    Any,
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array(Box<JsonShape>),
+    Object {
+        members: Vec<MemberShape>,
+        deny_unknown_members: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct MemberShape {
+    name: String,
+    shape: JsonShape,
+    required: bool,
+}
+
+impl JsonShape {
+    /// Makes a shape that accepts any value.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Makes a shape that accepts `null`.
+    pub fn null() -> Self {
+        Self::with_kind(ShapeKind::Null)
+    }
+
+    /// Makes a shape that accepts `true` or `false`.
+    pub fn boolean() -> Self {
+        Self::with_kind(ShapeKind::Boolean)
+    }
+
+    /// Makes a shape that accepts integer numbers.
+    pub fn integer() -> Self {
+        Self::with_kind(ShapeKind::Integer)
+    }
+
+    /// Makes a shape that accepts any numbers.
+    pub fn number() -> Self {
+        Self::with_kind(ShapeKind::Number)
+    }
+
+    /// Makes a shape that accepts strings.
+    pub fn string() -> Self {
+        Self::with_kind(ShapeKind::String)
+    }
+
+    /// Makes a shape that accepts arrays whose elements all match `element`.
+    pub fn array(element: JsonShape) -> Self {
+        Self::with_kind(ShapeKind::Array(Box::new(element)))
+    }
+
+    /// Makes a shape that accepts objects.
+    ///
+    /// Use [`JsonShape::member()`] and [`JsonShape::optional_member()`] to describe the members.
+    /// Members not described are allowed unless [`JsonShape::deny_unknown_members()`] is called.
+    pub fn object() -> Self {
+        Self::with_kind(ShapeKind::Object {
+            members: Vec::new(),
+            deny_unknown_members: false,
+        })
+    }
+
+    fn with_kind(kind: ShapeKind) -> Self {
+        Self {
+            kind,
+            ..Self::default()
+        }
+    }
+
+    /// Adds a required member to an object shape.
+    ///
+    /// This has no effect on shapes other than [`JsonShape::object()`].
+    pub fn member(self, name: &str, shape: JsonShape) -> Self {
+        self.add_member(name, shape, true)
+    }
+
+    /// Adds an optional member to an object shape.
+    ///
+    /// This has no effect on shapes other than [`JsonShape::object()`].
+    pub fn optional_member(self, name: &str, shape: JsonShape) -> Self {
+        self.add_member(name, shape, false)
+    }
+
+    fn add_member(mut self, name: &str, shape: JsonShape, required: bool) -> Self {
+        if let ShapeKind::Object { members, .. } = &mut self.kind {
+            members.push(MemberShape {
+                name: String::from(name),
+                shape,
+                required,
+            });
+        }
+        self
+    }
+
+    /// Rejects object members that are not described by [`JsonShape::member()`] or [`JsonShape::optional_member()`].
+    pub fn deny_unknown_members(mut self) -> Self {
+        if let ShapeKind::Object {
+            deny_unknown_members,
+            ..
+        } = &mut self.kind
+        {
+            *deny_unknown_members = true;
+        }
+        self
+    }
+
+    /// Also accepts `null` in addition to the values described by this shape.
+    pub fn nullable(mut self) -> Self {
+        self.nullable = true;
+        self
+    }
+
+    /// Sets the minimum (inclusive) of a number.
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Sets the maximum (inclusive) of a number.
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Sets the minimum length of a string (in characters), an array, or an object.
+    pub fn min_len(mut self, len: usize) -> Self {
+        self.min_len = Some(len);
+        self
+    }
+
+    /// Sets the maximum length of a string (in characters), an array, or an object.
+    pub fn max_len(mut self, len: usize) -> Self {
+        self.max_len = Some(len);
+        self
+    }
+
+    /// Validates `value` against this shape.
+    ///
+    /// Unlike typical [`TryFrom`] implementations, this does not stop at the first problem:
+    /// all violations are returned as [`JsonParseError::InvalidValue`] errors in document order,
+    /// each pointing at the offending value and mentioning its path (e.g., `$.users[0].name`).
+    pub fn validate(&self, value: RawJsonValue<'_, '_>) -> Result<(), Vec<JsonParseError>> {
+        let mut errors = Vec::new();
+        self.collect_errors(value, &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn collect_errors(&self, value: RawJsonValue<'_, '_>, errors: &mut Vec<JsonParseError>) {
+        let kind = value.kind();
+        let is_null = kind.is_null() && !value.is_extension();
+        if self.nullable && is_null {
+            return;
+        }
+
+        let (matched, expected) = match &self.kind {
+            ShapeKind::Any => (true, ""),
+            ShapeKind::Null => (is_null, "null"),
+            ShapeKind::Boolean => (kind.is_bool(), "a boolean"),
+            ShapeKind::Integer => (kind.is_integer(), "an integer"),
+            ShapeKind::Number => (kind.is_number(), "a number"),
+            ShapeKind::String => (kind.is_string(), "a string"),
+            ShapeKind::Array(_) => (kind.is_array(), "an array"),
+            ShapeKind::Object { .. } => (kind.is_object(), "an object"),
+        };
+        if !matched {
+            let e = value.invalid(format!(
+                "{}: expected {expected}, but got {kind:?}",
+                value.path()
+            ));
+            errors.push(e.with_code(JsonErrorCode::UnexpectedKind));
+            return;
+        }
+
+        if kind.is_number() && (self.min.is_some() || self.max.is_some()) {
+            let range = (bound(self.min), bound(self.max));
+            if let Err(e) = validate::range::<f64, _>(value, range) {
+                errors.push(e);
+            }
+        }
+        if (kind.is_string() || kind.is_array() || kind.is_object())
+            && (self.min_len.is_some() || self.max_len.is_some())
+        {
+            let range = (bound(self.min_len), bound(self.max_len));
+            if let Err(e) = validate::length(value, range) {
+                errors.push(e);
+            }
+        }
+
+        match &self.kind {
+            ShapeKind::Array(element) => {
+                for v in value.to_array().expect("infallible") {
+                    element.collect_errors(v, errors);
+                }
+            }
+            ShapeKind::Object {
+                members,
+                deny_unknown_members,
+            } => {
+                for (name, v) in value.to_object().expect("infallible") {
+                    let name_str = name.to_unquoted_string_str().expect("infallible");
+                    match members.iter().find(|m| m.name == name_str) {
+                        Some(m) => m.shape.collect_errors(v, errors),
+                        None if *deny_unknown_members => {
+                            let e = name.invalid(format!("{}: unknown member", name.path()));
+                            errors.push(e.with_code(JsonErrorCode::UnknownMember));
+                        }
+                        None => {}
+                    }
+                }
+                for m in members.iter().filter(|m| m.required) {
+                    let member = value.to_member(&m.name).expect("infallible");
+                    if member.optional().is_none() {
+                        let e = value.invalid(format!(
+                            "{}: missing required member {:?}",
+                            value.path(),
+                            m.name
+                        ));
+                        errors.push(e.with_code(JsonErrorCode::MissingMember));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn bound<T>(n: Option<T>) -> Bound<T> {
+    n.map_or(Bound::Unbounded, Bound::Included)
+}
@@ -0,0 +1,139 @@
+use alloc::{string::String, sync::Arc};
+use core::fmt::Display;
+
+use crate::{DisplayJson, JsonFormatter, JsonParseError, RawJsonOwned, RawJsonValue};
+
+/// A reference-counted parsed JSON document that can be cheaply cloned and shared across threads.
+///
+/// Unlike [`RawJson`](crate::RawJson), this type does not borrow the JSON text, and unlike
+/// [`RawJsonOwned`], cloning it does not copy the text or the value index.
+/// Values within the document can be handed out without lifetimes as [`RawJsonSharedValue`]s.
+///
+/// This type is available only on targets that support pointer-sized atomics
+/// (i.e., where `alloc::sync::Arc` exists).
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// let json = nojson::RawJsonShared::parse(r#"{"jobs": [{"id": 1}, {"id": 2}]}"#)?;
+/// let jobs = json.value().to_member("jobs")?.required()?;
+///
+/// let handles = jobs
+///     .to_array()?
+///     .map(|job| json.shared_value(job.index()).expect("exists"))
+///     .map(|job| {
+///         std::thread::spawn(move || -> Result<u32, nojson::JsonParseError> {
+///             job.value().to_member("id")?.required()?.try_into()
+///         })
+///     })
+///     .collect::<Vec<_>>();
+/// for (handle, expected) in handles.into_iter().zip([1, 2]) {
+///     assert_eq!(handle.join().expect("no panic")?, expected);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RawJsonShared(Arc<RawJsonOwned>);
+
+impl RawJsonShared {
+    /// Parses a JSON string into a [`RawJsonShared`] instance.
+    ///
+    /// This is equivalent to [`RawJsonOwned::parse()`] followed by [`RawJsonShared::from()`].
+    pub fn parse<T>(text: T) -> Result<Self, JsonParseError>
+    where
+        T: Into<String>,
+    {
+        RawJsonOwned::parse(text).map(Self::from)
+    }
+
+    /// Returns the original JSON text.
+    pub fn text(&self) -> &str {
+        self.0.text()
+    }
+
+    /// Returns the top-level value of the JSON.
+    pub fn value(&self) -> RawJsonValue<'_, '_> {
+        self.0.value()
+    }
+
+    /// Returns a shared handle to the value at the specified index (see [`RawJsonValue::index()`]).
+    ///
+    /// Returns `None` if the index is out of bounds.
+    pub fn shared_value(&self, index: usize) -> Option<RawJsonSharedValue> {
+        self.0.get_value_by_index(index)?;
+        Some(RawJsonSharedValue {
+            json: self.clone(),
+            index,
+        })
+    }
+
+    /// Returns a reference to the underlying [`RawJsonOwned`].
+    pub fn as_owned(&self) -> &RawJsonOwned {
+        &self.0
+    }
+}
+
+impl From<RawJsonOwned> for RawJsonShared {
+    fn from(json: RawJsonOwned) -> Self {
+        Self(Arc::new(json))
+    }
+}
+
+impl core::str::FromStr for RawJsonShared {
+    type Err = JsonParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for RawJsonShared {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl DisplayJson for RawJsonShared {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        DisplayJson::fmt(&self.value(), f)
+    }
+}
+
+/// A value within a [`RawJsonShared`] document, which keeps the document alive.
+///
+/// This is created by [`RawJsonShared::shared_value()`], and can be sent to other threads
+/// or stored in structs without lifetime parameters.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RawJsonSharedValue {
+    json: RawJsonShared,
+    index: usize,
+}
+
+impl RawJsonSharedValue {
+    /// Returns the value as a [`RawJsonValue`].
+    pub fn value(&self) -> RawJsonValue<'_, '_> {
+        self.json
+            .as_owned()
+            .get_value_by_index(self.index)
+            .expect("infallible")
+    }
+
+    /// Returns the document that contains this value.
+    pub fn document(&self) -> &RawJsonShared {
+        &self.json
+    }
+}
+
+impl Display for RawJsonSharedValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.value(), f)
+    }
+}
+
+impl DisplayJson for RawJsonSharedValue {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        DisplayJson::fmt(&self.value(), f)
+    }
+}
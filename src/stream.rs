@@ -0,0 +1,91 @@
+use alloc::string::String;
+use std::io::{self, Read, Write};
+
+use crate::{DisplayJson, Json, JsonFormatter, JsonParseError, RawJson, RawJsonValue};
+
+impl<T> Json<T>
+where
+    T: for<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError>,
+{
+    /// Reads JSON text from `reader` until EOF and converts it into `T`.
+    ///
+    /// # Errors
+    ///
+    /// I/O errors are returned as is. Invalid UTF-8, syntax errors and conversion errors
+    /// are returned as [`io::Error`]s of kind [`io::ErrorKind::InvalidData`];
+    /// for the latter two, the error wraps a [`JsonParseError`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// let nojson::Json(values) = nojson::Json::<Vec<u32>>::from_reader(&b"[1, 2, 3]"[..])?;
+    /// assert_eq!(values, [1, 2, 3]);
+    ///
+    /// let e = nojson::Json::<Vec<u32>>::from_reader(&b"[1, -2]"[..]).expect_err("negative");
+    /// assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    /// let e = e.into_inner().and_then(|e| e.downcast::<nojson::JsonParseError>().ok());
+    /// assert_eq!(e.map(|e| e.position()), Some(4));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_reader<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        RawJson::parse(&text)
+            .and_then(|json| json.value().try_into())
+            .map(Json)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl<T: DisplayJson> Json<T> {
+    /// Writes the JSON text of the wrapped value to `writer`.
+    ///
+    /// The output is the same as that of [`Json`]'s `Display` implementation.
+    /// If formatting the value fails (i.e., its [`DisplayJson::fmt()`] returns an error),
+    /// an [`io::Error`] of kind [`io::ErrorKind::InvalidData`] is returned.
+    /// `writer` is not buffered internally, so consider wrapping it with [`io::BufWriter`]
+    /// when writing to a file or a socket.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// let mut output = Vec::new();
+    /// nojson::Json([Some(1), None]).to_writer(&mut output)?;
+    /// assert_eq!(output, b"[1,null]");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_writer<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut adapter = IoWriter {
+            inner: writer,
+            error: None,
+        };
+        match JsonFormatter::write_to(&mut adapter, |f| self.0.fmt(f)) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(adapter.error.unwrap_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "failed to format the value as JSON",
+                )
+            })),
+        }
+    }
+}
+
+// Adapts an `io::Write` to `fmt::Write`, keeping the I/O error that `fmt::Error` cannot carry.
+struct IoWriter<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> core::fmt::Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            core::fmt::Error
+        })
+    }
+}
@@ -0,0 +1,435 @@
+use core::fmt::Display;
+use std::time::{Duration, SystemTime};
+
+use crate::{
+    DisplayJson, JsonErrorCode, JsonFormatter, JsonParseError, JsonValueKind, RawJsonValue,
+};
+
+/// A wrapper that formats and parses a [`SystemTime`] as an RFC 3339 (ISO 8601) timestamp string.
+///
+/// Timestamps are always written in UTC with the `Z` suffix (e.g., `"2024-01-02T03:04:05Z"`).
+/// Sub-second precision is emitted only when present, with trailing zeros omitted
+/// (e.g., `"2024-01-02T03:04:05.25Z"`).
+///
+/// When parsing, any RFC 3339 `date-time` is accepted, including numeric offsets such as
+/// `+09:00`. Fractional seconds beyond nanosecond precision are truncated.
+/// Errors point at the offending character in the JSON text.
+///
+/// RFC 3339 only supports the years 0000 to 9999. Formatting a time outside that range
+/// as JSON fails with [`core::fmt::Error`], whereas the [`Display`] implementation
+/// writes the year in the ISO 8601 expanded form instead (e.g., `+10000-01-01T00:00:00Z`).
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use std::time::{Duration, SystemTime};
+/// use nojson::{Iso8601, Json};
+///
+/// let time = SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_250);
+/// assert_eq!(Json(Iso8601(time)).to_string(), r#""2023-11-14T22:13:20.25Z""#);
+///
+/// let parsed: Json<Iso8601> = r#""2023-11-15T07:13:20.25+09:00""#.parse()?;
+/// assert_eq!(parsed.0.0, time);
+///
+/// let e = r#""2023-13-15T07:13:20Z""#.parse::<Json<Iso8601>>().expect_err("invalid month");
+/// assert_eq!(e.position(), 6);
+///
+/// let far = Iso8601(SystemTime::UNIX_EPOCH + Duration::from_secs(253_402_300_800));
+/// assert_eq!(far.to_string(), "+10000-01-01T00:00:00Z");
+/// let mut text = String::new();
+/// assert!(std::fmt::write(&mut text, format_args!("{}", Json(far))).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Iso8601(#[allow(missing_docs)] pub SystemTime);
+
+impl Display for Iso8601 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (secs, nanos) = to_unix_time(self.0);
+        let days = secs.div_euclid(86400);
+        let secs_of_day = secs.rem_euclid(86400);
+        let (year, month, day) = civil_from_days(days);
+        if (0..=9999).contains(&year) {
+            write!(f, "{year:04}")?;
+        } else {
+            write!(f, "{year:+05}")?;
+        }
+        write!(
+            f,
+            "-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+            secs_of_day / 3600,
+            secs_of_day / 60 % 60,
+            secs_of_day % 60
+        )?;
+        if nanos > 0 {
+            let mut fraction = nanos;
+            let mut width = 9;
+            while fraction % 10 == 0 {
+                fraction /= 10;
+                width -= 1;
+            }
+            write!(f, ".{fraction:0width$}")?;
+        }
+        write!(f, "Z")
+    }
+}
+
+impl DisplayJson for Iso8601 {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        let (secs, _) = to_unix_time(self.0);
+        let (year, _, _) = civil_from_days(secs.div_euclid(86400));
+        if !(0..=9999).contains(&year) {
+            // RFC 3339 only supports four-digit years.
+            return Err(core::fmt::Error);
+        }
+        f.string(self)
+    }
+}
+
+impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for Iso8601 {
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        let text = value.to_unquoted_string_str()?;
+        let (secs, nanos) = Rfc3339Parser {
+            text: text.as_bytes(),
+            offset: 0,
+        }
+        .parse()
+        .map_err(|(offset, reason)| {
+            value
+                .invalid_at(offset, reason)
+                .with_code(JsonErrorCode::InvalidString)
+        })?;
+        from_unix_time(secs, nanos).map(Self).ok_or_else(|| {
+            value
+                .invalid("timestamp out of range")
+                .with_code(JsonErrorCode::InvalidString)
+        })
+    }
+}
+
+/// A wrapper that formats and parses a [`SystemTime`] as the number of seconds since the Unix epoch.
+///
+/// Whole seconds are written as a JSON integer; sub-second precision is written as
+/// decimal fraction digits (e.g., `1700000000.25`).
+/// When parsing, both integers and floats (including exponents) are accepted.
+/// The conversion operates on the decimal text, so digits beyond nanosecond precision are truncated
+/// without floating-point rounding errors.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use std::time::{Duration, SystemTime};
+/// use nojson::{EpochSeconds, Json};
+///
+/// let time = SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_250);
+/// assert_eq!(Json(EpochSeconds(time)).to_string(), "1700000000.25");
+///
+/// let parsed: Json<EpochSeconds> = "1.70000000025e9".parse()?;
+/// assert_eq!(parsed.0.0, time);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EpochSeconds(#[allow(missing_docs)] pub SystemTime);
+
+impl DisplayJson for EpochSeconds {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        write_epoch(f, self.0, 9)
+    }
+}
+
+impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for EpochSeconds {
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        parse_epoch(value, 9).map(Self)
+    }
+}
+
+/// A wrapper that formats and parses a [`SystemTime`] as the number of milliseconds since the Unix epoch.
+///
+/// Whole milliseconds are written as a JSON integer; sub-millisecond precision is written as
+/// decimal fraction digits (e.g., `1700000000250.5`).
+/// When parsing, both integers and floats (including exponents) are accepted.
+/// The conversion operates on the decimal text, so digits beyond nanosecond precision are truncated
+/// without floating-point rounding errors.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use std::time::{Duration, SystemTime};
+/// use nojson::{EpochMillis, Json};
+///
+/// let time = SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_250);
+/// assert_eq!(Json(EpochMillis(time)).to_string(), "1700000000250");
+///
+/// let parsed: Json<EpochMillis> = "1700000000250.0".parse()?;
+/// assert_eq!(parsed.0.0, time);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EpochMillis(#[allow(missing_docs)] pub SystemTime);
+
+impl DisplayJson for EpochMillis {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        write_epoch(f, self.0, 6)
+    }
+}
+
+impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for EpochMillis {
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        parse_epoch(value, 6).map(Self)
+    }
+}
+
+// Writes `time` as a number in units of `10^scale` nanoseconds.
+fn write_epoch(f: &mut JsonFormatter<'_, '_>, time: SystemTime, scale: u32) -> core::fmt::Result {
+    let (secs, nanos) = to_unix_time(time);
+    let total = i128::from(secs) * 1_000_000_000 + i128::from(nanos);
+    let unit = 10i128.pow(scale);
+    let sign = if total < 0 { "-" } else { "" };
+    let (int_part, mut fraction) = (
+        total.unsigned_abs() / unit as u128,
+        total.unsigned_abs() % unit as u128,
+    );
+    if fraction == 0 {
+        return f.literal(JsonValueKind::Float, format_args!("{sign}{int_part}"));
+    }
+
+    let mut width = scale as usize;
+    while fraction % 10 == 0 {
+        fraction /= 10;
+        width -= 1;
+    }
+    f.literal(
+        JsonValueKind::Float,
+        format_args!("{sign}{int_part}.{fraction:0width$}"),
+    )
+}
+
+// Parses a JSON number in units of `10^scale` nanoseconds.
+fn parse_epoch(value: RawJsonValue<'_, '_>, scale: u32) -> Result<SystemTime, JsonParseError> {
+    let text = value.as_number_str()?;
+    let out_of_range = || {
+        value
+            .invalid("timestamp out of range")
+            .with_code(JsonErrorCode::InvalidNumber)
+    };
+    let total = decimal_to_nanos(text, scale).ok_or_else(out_of_range)?;
+    let secs = i64::try_from(total.div_euclid(1_000_000_000)).map_err(|_| out_of_range())?;
+    let nanos = total.rem_euclid(1_000_000_000) as u32;
+    from_unix_time(secs, nanos).ok_or_else(out_of_range)
+}
+
+// Converts the text of a JSON number into an integer multiplied by `10^scale`,
+// truncating any remaining fractional digits toward zero.
+fn decimal_to_nanos(text: &str, scale: u32) -> Option<i128> {
+    // `i128::MAX` has 39 digits.
+    const MAX_DIGITS: i64 = 38;
+
+    let (negative, unsigned) = match text.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, text),
+    };
+    let (mantissa, exponent) = unsigned.split_once(['e', 'E']).unwrap_or((unsigned, "0"));
+    let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = int_part
+        .bytes()
+        .chain(frac_part.bytes())
+        .skip_while(|&b| b == b'0');
+    let leading_zeros = (int_part.len() + frac_part.len() - digits.clone().count()) as i64;
+
+    let exponent = match exponent.parse::<i64>() {
+        Ok(exponent) => exponent,
+        Err(_) if exponent.starts_with('-') => return Some(0),
+        Err(_) => i64::MAX,
+    };
+    // Number of digits of the scaled integer.
+    let len = (int_part.len() as i64 - leading_zeros)
+        .saturating_add(exponent)
+        .saturating_add(i64::from(scale));
+    if len > MAX_DIGITS {
+        return digits.clone().all(|b| b == b'0').then_some(0);
+    }
+
+    let mut total: i128 = 0;
+    let mut digits = digits;
+    for _ in 0..len.max(0) {
+        let digit = digits.next().map_or(0, |b| b - b'0');
+        total = total * 10 + i128::from(digit);
+    }
+    Some(if negative { -total } else { total })
+}
+
+// Returns the number of seconds (and sub-second nanoseconds) elapsed since the Unix epoch.
+// Times before the epoch have negative seconds; nanoseconds are always non-negative.
+pub(crate) fn to_unix_time(time: SystemTime) -> (i64, u32) {
+    match time.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(d) => (d.as_secs() as i64, d.subsec_nanos()),
+        Err(e) => {
+            let d = e.duration();
+            let secs = -(d.as_secs() as i64);
+            match d.subsec_nanos() {
+                0 => (secs, 0),
+                nanos => (secs - 1, 1_000_000_000 - nanos),
+            }
+        }
+    }
+}
+
+pub(crate) fn from_unix_time(secs: i64, nanos: u32) -> Option<SystemTime> {
+    let time = if secs >= 0 {
+        SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(secs as u64))?
+    } else {
+        SystemTime::UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))?
+    };
+    time.checked_add(Duration::from_nanos(u64::from(nanos)))
+}
+
+// Converts days since 1970-01-01 into a (year, month, day) triple in the proleptic Gregorian
+// calendar. See http://howardhinnant.github.io/date_algorithms.html for the algorithm.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+// Inverse of `civil_from_days()`.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = i64::from(month);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Parser for RFC 3339 `date-time`. Errors carry the byte offset of the offending character.
+struct Rfc3339Parser<'a> {
+    text: &'a [u8],
+    offset: usize,
+}
+
+impl Rfc3339Parser<'_> {
+    fn parse(mut self) -> Result<(i64, u32), (usize, &'static str)> {
+        let year = self.digits(4, 0, 9999, "invalid year")?;
+        self.expect(b"-", "expected '-'")?;
+        let month = self.digits(2, 1, 12, "invalid month")?;
+        self.expect(b"-", "expected '-'")?;
+        let max_day = days_in_month(i64::from(year), month);
+        let day = self.digits(2, 1, max_day, "invalid day")?;
+        self.expect(b"Tt", "expected 'T'")?;
+        let hour = self.digits(2, 0, 23, "invalid hour")?;
+        self.expect(b":", "expected ':'")?;
+        let minute = self.digits(2, 0, 59, "invalid minute")?;
+        self.expect(b":", "expected ':'")?;
+        // `60` is allowed for leap seconds.
+        let second = self.digits(2, 0, 60, "invalid second")?;
+
+        let mut nanos = 0;
+        if self.peek() == Some(b'.') {
+            self.offset += 1;
+            let start = self.offset;
+            while let Some(b @ b'0'..=b'9') = self.peek() {
+                if self.offset - start < 9 {
+                    nanos = nanos * 10 + u32::from(b - b'0');
+                }
+                self.offset += 1;
+            }
+            let n = self.offset - start;
+            if n == 0 {
+                return Err((self.offset, "expected a digit"));
+            }
+            nanos *= 10u32.pow(9usize.saturating_sub(n) as u32);
+        }
+
+        let offset_secs = match self.peek() {
+            Some(b'Z' | b'z') => {
+                self.offset += 1;
+                0
+            }
+            Some(sign @ (b'+' | b'-')) => {
+                self.offset += 1;
+                let hour = self.digits(2, 0, 23, "invalid offset hour")?;
+                self.expect(b":", "expected ':'")?;
+                let minute = self.digits(2, 0, 59, "invalid offset minute")?;
+                let secs = i64::from(hour * 3600 + minute * 60);
+                if sign == b'+' { secs } else { -secs }
+            }
+            _ => return Err((self.offset, "expected 'Z' or a UTC offset")),
+        };
+        if self.offset != self.text.len() {
+            return Err((self.offset, "unexpected trailing characters"));
+        }
+
+        let days = days_from_civil(i64::from(year), month, day);
+        let secs = days * 86400 + i64::from(hour * 3600 + minute * 60 + second) - offset_secs;
+        Ok((secs, nanos))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.offset).copied()
+    }
+
+    fn expect(
+        &mut self,
+        candidates: &[u8],
+        reason: &'static str,
+    ) -> Result<(), (usize, &'static str)> {
+        match self.peek() {
+            Some(b) if candidates.contains(&b) => {
+                self.offset += 1;
+                Ok(())
+            }
+            _ => Err((self.offset, reason)),
+        }
+    }
+
+    fn digits(
+        &mut self,
+        n: usize,
+        min: u32,
+        max: u32,
+        reason: &'static str,
+    ) -> Result<u32, (usize, &'static str)> {
+        let start = self.offset;
+        let mut value = 0;
+        for _ in 0..n {
+            match self.peek() {
+                Some(b @ b'0'..=b'9') => value = value * 10 + u32::from(b - b'0'),
+                _ => return Err((self.offset, "expected a digit")),
+            }
+            self.offset += 1;
+        }
+        if value < min || value > max {
+            return Err((start, reason));
+        }
+        Ok(value)
+    }
+}
@@ -1,7 +1,7 @@
 use alloc::{borrow::Cow, boxed::Box, format, rc::Rc, string::String, vec::Vec};
 use core::str::FromStr;
 
-use crate::{JsonParseError, RawJsonValue};
+use crate::{JsonErrorCode, JsonParseError, RawJsonValue};
 
 impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for bool {
     type Error = JsonParseError;
@@ -22,7 +22,7 @@ where
     value
         .as_integer_str()?
         .parse()
-        .map_err(|e| value.invalid(e))
+        .map_err(|e| value.invalid(e).with_code(JsonErrorCode::InvalidNumber))
 }
 
 impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for i8 {
@@ -222,7 +222,10 @@ where
     T: FromStr,
     T::Err: Into<Box<dyn Send + Sync + core::error::Error>>,
 {
-    value.as_number_str()?.parse().map_err(|e| value.invalid(e))
+    value
+        .as_number_str()?
+        .parse()
+        .map_err(|e| value.invalid(e).with_code(JsonErrorCode::InvalidNumber))
 }
 
 impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for f32 {
@@ -249,11 +252,14 @@ impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for char {
         let mut chars = s.chars();
         match (chars.next(), chars.next()) {
             (Some(c), None) => Ok(c),
-            (Some(_), Some(_)) => Err(value.invalid(
-                "expected a string with exactly one character, but got multiple characters",
-            )),
+            (Some(_), Some(_)) => Err(value
+                .invalid(
+                    "expected a string with exactly one character, but got multiple characters",
+                )
+                .with_code(JsonErrorCode::InvalidString)),
             _ => Err(value
-                .invalid("expected a string with exactly one character, but got an empty string")),
+                .invalid("expected a string with exactly one character, but got an empty string")
+                .with_code(JsonErrorCode::InvalidString)),
         }
     }
 }
@@ -285,6 +291,33 @@ impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for Cow<'text, str> {
     }
 }
 
+impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for Box<str> {
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        value.to_unquoted_string_str().map(Box::from)
+    }
+}
+
+impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for Rc<str> {
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        value.to_unquoted_string_str().map(|s| Rc::from(s.as_ref()))
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for alloc::sync::Arc<str> {
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        value
+            .to_unquoted_string_str()
+            .map(|s| alloc::sync::Arc::from(s.as_ref()))
+    }
+}
+
 #[cfg(feature = "std")]
 impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for std::path::PathBuf {
     type Error = JsonParseError;
@@ -295,6 +328,16 @@ impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for std::path::PathBuf {
     }
 }
 
+#[cfg(feature = "std")]
+impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for std::ffi::OsString {
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        let s = value.to_unquoted_string_str()?.into_owned();
+        Ok(std::ffi::OsString::from(s))
+    }
+}
+
 #[cfg(feature = "std")]
 impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for std::net::IpAddr {
     type Error = JsonParseError;
@@ -303,7 +346,7 @@ impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for std::net::IpAddr {
         value
             .to_unquoted_string_str()?
             .parse()
-            .map_err(|e| value.invalid(e))
+            .map_err(|e| value.invalid(e).with_code(JsonErrorCode::InvalidString))
     }
 }
 
@@ -315,7 +358,7 @@ impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for std::net::Ipv4Addr {
         value
             .to_unquoted_string_str()?
             .parse()
-            .map_err(|e| value.invalid(e))
+            .map_err(|e| value.invalid(e).with_code(JsonErrorCode::InvalidString))
     }
 }
 
@@ -327,7 +370,7 @@ impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for std::net::Ipv6Addr {
         value
             .to_unquoted_string_str()?
             .parse()
-            .map_err(|e| value.invalid(e))
+            .map_err(|e| value.invalid(e).with_code(JsonErrorCode::InvalidString))
     }
 }
 
@@ -339,7 +382,7 @@ impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for std::net::SocketAddr {
         value
             .to_unquoted_string_str()?
             .parse()
-            .map_err(|e| value.invalid(e))
+            .map_err(|e| value.invalid(e).with_code(JsonErrorCode::InvalidString))
     }
 }
 
@@ -351,7 +394,7 @@ impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for std::net::SocketAddrV4
         value
             .to_unquoted_string_str()?
             .parse()
-            .map_err(|e| value.invalid(e))
+            .map_err(|e| value.invalid(e).with_code(JsonErrorCode::InvalidString))
     }
 }
 
@@ -363,7 +406,7 @@ impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for std::net::SocketAddrV6
         value
             .to_unquoted_string_str()?
             .parse()
-            .map_err(|e| value.invalid(e))
+            .map_err(|e| value.invalid(e).with_code(JsonErrorCode::InvalidString))
     }
 }
 
@@ -393,6 +436,35 @@ where
     }
 }
 
+macro_rules! impl_try_from_for_atomic {
+    ($($width:literal: $t:ident($inner:ty)),+) => {
+        $(
+            #[cfg(target_has_atomic = $width)]
+            impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for core::sync::atomic::$t {
+                type Error = JsonParseError;
+
+                fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+                    <$inner>::try_from(value).map(Self::new)
+                }
+            }
+        )+
+    };
+}
+
+impl_try_from_for_atomic!(
+    "8": AtomicBool(bool),
+    "8": AtomicI8(i8),
+    "8": AtomicU8(u8),
+    "16": AtomicI16(i16),
+    "16": AtomicU16(u16),
+    "32": AtomicI32(i32),
+    "32": AtomicU32(u32),
+    "64": AtomicI64(i64),
+    "64": AtomicU64(u64),
+    "ptr": AtomicIsize(isize),
+    "ptr": AtomicUsize(usize)
+);
+
 impl<'text, 'raw, T> TryFrom<RawJsonValue<'text, 'raw>> for Option<T>
 where
     T: TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError>,
@@ -400,7 +472,7 @@ where
     type Error = JsonParseError;
 
     fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
-        if value.kind().is_null() {
+        if value.kind().is_null() && !value.is_extension() {
             Ok(None)
         } else {
             T::try_from(value).map(Some)
@@ -427,6 +499,29 @@ where
     }
 }
 
+impl<'text, 'raw, T> TryFrom<RawJsonValue<'text, 'raw>> for Box<[T]>
+where
+    T: TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError>,
+{
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        value.to_array()?.map(|v| T::try_from(v)).collect()
+    }
+}
+
+// Decoding always produces `Cow::Owned` because the elements have to be converted.
+impl<'text, 'raw, T> TryFrom<RawJsonValue<'text, 'raw>> for Cow<'_, [T]>
+where
+    T: TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError> + Clone,
+{
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        Vec::try_from(value).map(Cow::Owned)
+    }
+}
+
 impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>>
     for alloc::collections::VecDeque<RawJsonValue<'text, 'raw>>
 {
@@ -482,18 +577,22 @@ impl<'text, 'raw, const N: usize> TryFrom<RawJsonValue<'text, 'raw>>
 
         for (i, slot) in fixed_array.iter_mut().enumerate() {
             *slot = values.next().ok_or_else(|| {
-                value.invalid(format!(
-                    "expected an array with {N} elements, but got only {i} elements"
-                ))
+                value
+                    .invalid(format!(
+                        "expected an array with {N} elements, but got only {i} elements"
+                    ))
+                    .with_code(JsonErrorCode::InvalidLength)
             })?;
         }
 
         let extra = values.count();
         if extra > 0 {
-            return Err(value.invalid(format!(
-                "expected an array with {N} elements, but got {} elements",
-                N + extra
-            )));
+            return Err(value
+                .invalid(format!(
+                    "expected an array with {N} elements, but got {} elements",
+                    N + extra
+                ))
+                .with_code(JsonErrorCode::InvalidLength));
         }
 
         Ok(fixed_array)
@@ -518,14 +617,102 @@ where
     }
 }
 
+// JSON arrays with a fixed number of elements can be converted into tuples,
+// e.g., `[1, "foo"]` into `(u32, String)`.
+macro_rules! impl_try_from_for_tuple {
+    ($n:literal; $($t:ident: $i:tt),+) => {
+        impl<'text, 'raw, $($t),+> TryFrom<RawJsonValue<'text, 'raw>> for ($($t,)+)
+        where
+            $($t: TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError>),+
+        {
+            type Error = JsonParseError;
+
+            fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+                let values: [RawJsonValue<'text, 'raw>; $n] = value.try_into()?;
+                Ok(($($t::try_from(values[$i])?,)+))
+            }
+        }
+    };
+}
+
+impl_try_from_for_tuple!(1; T0: 0);
+impl_try_from_for_tuple!(2; T0: 0, T1: 1);
+impl_try_from_for_tuple!(3; T0: 0, T1: 1, T2: 2);
+impl_try_from_for_tuple!(4; T0: 0, T1: 1, T2: 2, T3: 3);
+impl_try_from_for_tuple!(5; T0: 0, T1: 1, T2: 2, T3: 3, T4: 4);
+impl_try_from_for_tuple!(6; T0: 0, T1: 1, T2: 2, T3: 3, T4: 4, T5: 5);
+impl_try_from_for_tuple!(7; T0: 0, T1: 1, T2: 2, T3: 3, T4: 4, T5: 5, T6: 6);
+impl_try_from_for_tuple!(8; T0: 0, T1: 1, T2: 2, T3: 3, T4: 4, T5: 5, T6: 6, T7: 7);
+
 impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for () {
     type Error = JsonParseError;
 
     fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
-        if value.kind().is_null() {
-            Ok(())
-        } else {
-            Err(value.invalid(format!("expected null, but found {:?}", value.kind())))
+        value.as_null()
+    }
+}
+
+/// Converts a `{"start": ..., "end": ...}` object into a range
+/// (see the [`DisplayJson`](crate::DisplayJson) implementation for the encoding).
+impl<'text, 'raw, T> TryFrom<RawJsonValue<'text, 'raw>> for core::ops::Range<T>
+where
+    T: TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError>,
+{
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        let start = value.to_member("start")?.required()?.try_into()?;
+        let end = value.to_member("end")?.required()?.try_into()?;
+        Ok(start..end)
+    }
+}
+
+/// Converts a `{"start": ..., "end": ...}` object into an inclusive range
+/// (see the [`DisplayJson`](crate::DisplayJson) implementation for the encoding).
+impl<'text, 'raw, T> TryFrom<RawJsonValue<'text, 'raw>> for core::ops::RangeInclusive<T>
+where
+    T: TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError>,
+{
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        let start = value.to_member("start")?.required()?.try_into()?;
+        let end = value.to_member("end")?.required()?.try_into()?;
+        Ok(start..=end)
+    }
+}
+
+/// Converts `{"Included": ...}`, `{"Excluded": ...}`, or `"Unbounded"` into a bound
+/// (see the [`DisplayJson`](crate::DisplayJson) implementation for the encoding).
+impl<'text, 'raw, T> TryFrom<RawJsonValue<'text, 'raw>> for core::ops::Bound<T>
+where
+    T: TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError>,
+{
+    type Error = JsonParseError;
+
+    fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        if value.kind().is_string() {
+            return if value.to_unquoted_string_str()? == "Unbounded" {
+                Ok(core::ops::Bound::Unbounded)
+            } else {
+                Err(value
+                    .invalid("expected \"Unbounded\"")
+                    .with_code(JsonErrorCode::UnknownVariant))
+            };
+        }
+
+        let mut members = value.to_object()?;
+        let (Some((name, v)), None) = (members.next(), members.next()) else {
+            return Err(value
+                .invalid("expected an object with exactly one member")
+                .with_code(JsonErrorCode::InvalidLength));
+        };
+        match name.to_unquoted_string_str()?.as_ref() {
+            "Included" => v.try_into().map(core::ops::Bound::Included),
+            "Excluded" => v.try_into().map(core::ops::Bound::Excluded),
+            _ => Err(name
+                .invalid("expected \"Included\" or \"Excluded\"")
+                .with_code(JsonErrorCode::UnknownVariant)),
         }
     }
 }
@@ -545,7 +732,7 @@ where
                 Ok((
                     k.to_unquoted_string_str()?
                         .parse()
-                        .map_err(|e| k.invalid(e))?,
+                        .map_err(|e| k.invalid(e).with_code(JsonErrorCode::InvalidString))?,
                     v,
                 ))
             })
@@ -568,7 +755,7 @@ where
                 Ok((
                     k.to_unquoted_string_str()?
                         .parse()
-                        .map_err(|e| k.invalid(e))?,
+                        .map_err(|e| k.invalid(e).with_code(JsonErrorCode::InvalidString))?,
                     V::try_from(v)?,
                 ))
             })
@@ -592,7 +779,7 @@ where
                 Ok((
                     k.to_unquoted_string_str()?
                         .parse()
-                        .map_err(|e| k.invalid(e))?,
+                        .map_err(|e| k.invalid(e).with_code(JsonErrorCode::InvalidString))?,
                     v,
                 ))
             })
@@ -616,7 +803,7 @@ where
                 Ok((
                     k.to_unquoted_string_str()?
                         .parse()
-                        .map_err(|e| k.invalid(e))?,
+                        .map_err(|e| k.invalid(e).with_code(JsonErrorCode::InvalidString))?,
                     V::try_from(v)?,
                 ))
             })
@@ -0,0 +1,166 @@
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::fmt::Display;
+
+use crate::{DisplayJson, JsonFormatter, raw::parse_pointer_index};
+
+/// Builds a JSON document from an iterator of path-value pairs, the inverse of [`RawJson::flatten()`](crate::RawJson::flatten).
+///
+/// Each path is either a [JSON Pointer] (e.g., `/users/0/name`, or the empty string for the top-level value)
+/// or a dotted path (e.g., `users.0.name`).
+/// Missing intermediate containers are created as needed: an array if the next token is
+/// an array index (or `-`, which appends a new element), and an object otherwise.
+///
+/// If pairs conflict (e.g., a path goes through a value that has already been set as a scalar),
+/// later pairs overwrite earlier ones. Object members keep the order in which they were first set.
+///
+/// # Errors
+///
+/// Arrays must be built densely: an array index may refer to an existing element or to the end
+/// of the array (appending an element), but not beyond it.
+/// Otherwise, an [`UnflattenError`] is returned, so that untrusted paths such as `rows.4000000000`
+/// cannot make the array grow unboundedly.
+///
+/// [JSON Pointer]: https://www.rfc-editor.org/rfc/rfc6901
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::UnflattenError> {
+/// let doc = nojson::unflatten([
+///     ("/user/name", &"Alice" as &dyn nojson::DisplayJson),
+///     ("user.tags.0", &"a"),
+///     ("/user/tags/-", &"b"),
+///     ("/user/scores/0", &10),
+///     ("/a~1b", &true),
+/// ])?;
+/// assert_eq!(
+///     doc.to_string(),
+///     r#"{"user":{"name":"Alice","tags":["a","b"],"scores":[10]},"a/b":true}"#
+/// );
+///
+/// let e = nojson::unflatten([("rows.4000000000", 1)]).err().expect("sparse index");
+/// assert_eq!((e.index, e.len), (4000000000, 0));
+/// # Ok(())
+/// # }
+/// ```
+pub fn unflatten<I, P, V>(pairs: I) -> Result<impl DisplayJson + Display, UnflattenError>
+where
+    I: IntoIterator<Item = (P, V)>,
+    P: AsRef<str>,
+    V: DisplayJson,
+{
+    let mut root = Node::Null;
+    for (path, value) in pairs {
+        let path = path.as_ref();
+        let tokens: Vec<String> = if path.is_empty() {
+            Vec::new()
+        } else if let Some(pointer) = path.strip_prefix('/') {
+            pointer
+                .split('/')
+                .map(|token| token.replace("~1", "/").replace("~0", "~"))
+                .collect()
+        } else {
+            path.split('.').map(String::from).collect()
+        };
+        root.insert(&tokens, value)
+            .map_err(|(index, len)| UnflattenError {
+                path: path.to_owned(),
+                index,
+                len,
+            })?;
+    }
+    Ok(crate::json(move |f| f.value(&root)))
+}
+
+/// An error returned by [`unflatten()`] when a path refers to an array index beyond the end of the array.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnflattenError {
+    /// The path that could not be applied.
+    pub path: String,
+
+    /// The array index in `path` that is out of range.
+    pub index: usize,
+
+    /// The length of the array when `path` was applied.
+    pub len: usize,
+}
+
+impl Display for UnflattenError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "array index {} in path {:?} is beyond the end of the array (length {})",
+            self.index, self.path, self.len
+        )
+    }
+}
+
+impl core::error::Error for UnflattenError {}
+
+enum Node<V> {
+    Null,
+    Value(V),
+    Array(Vec<Node<V>>),
+    Object(Vec<(String, Node<V>)>),
+}
+
+impl<V> Node<V> {
+    // On failure, returns the out-of-range array index and the length of the array.
+    fn insert(&mut self, tokens: &[String], value: V) -> Result<(), (usize, usize)> {
+        let Some((token, rest)) = tokens.split_first() else {
+            *self = Node::Value(value);
+            return Ok(());
+        };
+        let index = if token == "-" {
+            match self {
+                Node::Array(elements) => Some(elements.len()),
+                _ => Some(0),
+            }
+        } else {
+            parse_pointer_index(token)
+        };
+
+        match (&mut *self, index) {
+            (Node::Array(elements), Some(index)) => {
+                if index > elements.len() {
+                    return Err((index, elements.len()));
+                }
+                if index == elements.len() {
+                    elements.push(Node::Null);
+                }
+                elements[index].insert(rest, value)
+            }
+            (Node::Object(members), _) => {
+                let i = match members.iter().position(|(name, _)| name == token) {
+                    Some(i) => i,
+                    None => {
+                        members.push((token.clone(), Node::Null));
+                        members.len() - 1
+                    }
+                };
+                members[i].1.insert(rest, value)
+            }
+            (_, Some(_)) => {
+                *self = Node::Array(Vec::new());
+                self.insert(tokens, value)
+            }
+            (_, None) => {
+                *self = Node::Object(Vec::new());
+                self.insert(tokens, value)
+            }
+        }
+    }
+}
+
+impl<V: DisplayJson> DisplayJson for Node<V> {
+    fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> core::fmt::Result {
+        match self {
+            Node::Null => f.value(()),
+            Node::Value(value) => f.value(value),
+            Node::Array(elements) => f.array(|f| f.elements(elements)),
+            Node::Object(members) => {
+                f.object(|f| f.members(members.iter().map(|(name, value)| (name, value))))
+            }
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//! Composable validation helpers for [`RawJsonValue`].
+//!
+//! Each check returns the validated value on success, so checks can be chained
+//! and finally converted with [`TryInto::try_into()`].
+//! On failure, a [`JsonParseError::InvalidValue`] error is returned that points at the
+//! offending value, with the value's path (e.g., `$.users[0].name`) included in the reason.
+//! Its [`code`](JsonParseError::code) is [`JsonErrorCode::ConstraintViolation`]
+//! unless the value could not be checked at all (e.g., a number was expected but a string was given).
+//!
+//! # Examples
+//!
+//! ```
+//! use nojson::{JsonParseError, RawJson, RawJsonValue, validate};
+//!
+//! struct User {
+//!     name: String,
+//!     age: u8,
+//!     role: String,
+//! }
+//!
+//! impl<'text, 'raw> TryFrom<RawJsonValue<'text, 'raw>> for User {
+//!     type Error = JsonParseError;
+//!
+//!     fn try_from(value: RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+//!         let name = value.to_member("name")?.required()?;
+//!         let name = validate::length(name, 1..=16)?;
+//!         let name = validate::chars(name, |c| c.is_ascii_alphanumeric() || c == '_')?;
+//!         let age = validate::range(value.to_member("age")?.required()?, 0..=150u8)?;
+//!         let role = validate::one_of(value.to_member("role")?.required()?, &["admin", "user"])?;
+//!         Ok(User {
+//!             name: name.try_into()?,
+//!             age: age.try_into()?,
+//!             role: role.try_into()?,
+//!         })
+//!     }
+//! }
+//!
+//! let json = RawJson::parse(r#"{"name": "alice", "age": 30, "role": "admin"}"#)?;
+//! let user: User = json.value().try_into()?;
+//! assert_eq!(user.name, "alice");
+//!
+//! let json = RawJson::parse(r#"{"name": "alice!", "age": 30, "role": "admin"}"#)?;
+//! let error = User::try_from(json.value()).err().expect("invalid name");
+//! assert_eq!(error.position(), 15); // points at "!"
+//! assert!(error.to_string().contains("$.name"));
+//! # Ok::<(), JsonParseError>(())
+//! ```
+use alloc::{format, string::String, vec::Vec};
+use core::{
+    fmt::Display,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::{JsonErrorCode, JsonParseError, JsonValueKind, RawJsonValue};
+
+/// Checks that a number is within `range`.
+///
+/// The value is converted into `T` for the comparison, so conversion errors
+/// (e.g., a string or an out-of-range integer) are reported as well.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{RawJson, validate};
+///
+/// let json = RawJson::parse("[5, 50]")?;
+/// let [a, b] = [json.value().expect_element(0), json.value().expect_element(1)];
+/// assert!(validate::range(a, 1..=10)?.as_raw_str() == "5");
+/// assert!(validate::range(b, 1..=10).is_err());
+/// assert!(validate::range(b, 0.0..).is_ok());
+/// # Ok(())
+/// # }
+/// ```
+pub fn range<'text, 'raw, T, R>(
+    value: RawJsonValue<'text, 'raw>,
+    range: R,
+) -> Result<RawJsonValue<'text, 'raw>, JsonParseError>
+where
+    T: TryFrom<RawJsonValue<'text, 'raw>, Error = JsonParseError> + PartialOrd + Display,
+    R: RangeBounds<T>,
+{
+    let n = T::try_from(value)?;
+    if !range.contains(&n) {
+        return Err(invalid(
+            value,
+            format!("must be {}", describe_range(&range)),
+        ));
+    }
+    Ok(value)
+}
+
+/// Checks the length of a string (in characters), an array (in elements),
+/// or an object (in members).
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{RawJson, validate};
+///
+/// let json = RawJson::parse(r#"["héllo", [1, 2, 3], {}]"#)?;
+/// let value = json.value();
+/// assert!(validate::length(value.expect_element(0), ..=5).is_ok());
+/// assert!(validate::length(value.expect_element(1), 4..).is_err());
+/// assert!(validate::length(value.expect_element(2), 1..).is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub fn length<'text, 'raw, R>(
+    value: RawJsonValue<'text, 'raw>,
+    range: R,
+) -> Result<RawJsonValue<'text, 'raw>, JsonParseError>
+where
+    R: RangeBounds<usize>,
+{
+    let (len, unit) = match value.kind() {
+        JsonValueKind::String => (
+            value.to_unquoted_string_str()?.chars().count(),
+            "characters",
+        ),
+        JsonValueKind::Array => (value.to_array()?.count(), "elements"),
+        JsonValueKind::Object => (value.to_object()?.count(), "members"),
+        _ => {
+            return Err(invalid(value, "expected a string, array or object")
+                .with_code(JsonErrorCode::UnexpectedKind));
+        }
+    };
+    if !range.contains(&len) {
+        return Err(invalid(
+            value,
+            format!(
+                "length must be {} {unit}, but got {len}",
+                describe_range(&range)
+            ),
+        ));
+    }
+    Ok(value)
+}
+
+/// Checks that a string is one of `candidates`.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{RawJson, validate};
+///
+/// let json = RawJson::parse(r#""blue""#)?;
+/// assert!(validate::one_of(json.value(), &["red", "green", "blue"]).is_ok());
+/// assert!(validate::one_of(json.value(), &["red", "green"]).is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub fn one_of<'text, 'raw>(
+    value: RawJsonValue<'text, 'raw>,
+    candidates: &[&str],
+) -> Result<RawJsonValue<'text, 'raw>, JsonParseError> {
+    let s = value.to_unquoted_string_str()?;
+    if !candidates.contains(&s.as_ref()) {
+        let candidates = candidates
+            .iter()
+            .map(|c| format!("{c:?}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(invalid(value, format!("must be one of {candidates}")));
+    }
+    Ok(value)
+}
+
+/// Checks that every character of a string satisfies `predicate`.
+///
+/// The returned error points at the first offending character.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{RawJson, validate};
+///
+/// let json = RawJson::parse(r#""ab-1""#)?;
+/// assert!(validate::chars(json.value(), |c| c.is_ascii_alphanumeric() || c == '-').is_ok());
+///
+/// let e = validate::chars(json.value(), |c| c.is_ascii_alphanumeric()).err().expect("-");
+/// assert_eq!(e.position(), 3);
+/// # Ok(())
+/// # }
+/// ```
+pub fn chars<'text, 'raw, F>(
+    value: RawJsonValue<'text, 'raw>,
+    predicate: F,
+) -> Result<RawJsonValue<'text, 'raw>, JsonParseError>
+where
+    F: Fn(char) -> bool,
+{
+    let s = value.to_unquoted_string_str()?;
+    if let Some((i, c)) = s.char_indices().find(|(_, c)| !predicate(*c)) {
+        let reason = format!("{}: unexpected character {c:?}", value.path());
+        return Err(value
+            .invalid_at(i, reason)
+            .with_code(JsonErrorCode::ConstraintViolation));
+    }
+    Ok(value)
+}
+
+/// Checks that a string starts with `prefix`.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{RawJson, validate};
+///
+/// let json = RawJson::parse(r#""https://example.com""#)?;
+/// assert!(validate::starts_with(json.value(), "https://").is_ok());
+/// assert!(validate::starts_with(json.value(), "ftp://").is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub fn starts_with<'text, 'raw>(
+    value: RawJsonValue<'text, 'raw>,
+    prefix: &str,
+) -> Result<RawJsonValue<'text, 'raw>, JsonParseError> {
+    if !value.to_unquoted_string_str()?.starts_with(prefix) {
+        return Err(invalid(value, format!("must start with {prefix:?}")));
+    }
+    Ok(value)
+}
+
+/// Checks that a string ends with `suffix`.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), nojson::JsonParseError> {
+/// use nojson::{RawJson, validate};
+///
+/// let json = RawJson::parse(r#""photo.png""#)?;
+/// assert!(validate::ends_with(json.value(), ".png").is_ok());
+/// assert!(validate::ends_with(json.value(), ".jpg").is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub fn ends_with<'text, 'raw>(
+    value: RawJsonValue<'text, 'raw>,
+    suffix: &str,
+) -> Result<RawJsonValue<'text, 'raw>, JsonParseError> {
+    if !value.to_unquoted_string_str()?.ends_with(suffix) {
+        return Err(invalid(value, format!("must end with {suffix:?}")));
+    }
+    Ok(value)
+}
+
+fn invalid<T: Display>(value: RawJsonValue<'_, '_>, reason: T) -> JsonParseError {
+    value
+        .invalid(format!("{}: {reason}", value.path()))
+        .with_code(JsonErrorCode::ConstraintViolation)
+}
+
+fn describe_range<T: Display>(range: &impl RangeBounds<T>) -> String {
+    let lower = match range.start_bound() {
+        Bound::Included(n) => Some(format!("at least {n}")),
+        Bound::Excluded(n) => Some(format!("greater than {n}")),
+        Bound::Unbounded => None,
+    };
+    let upper = match range.end_bound() {
+        Bound::Included(n) => Some(format!("at most {n}")),
+        Bound::Excluded(n) => Some(format!("less than {n}")),
+        Bound::Unbounded => None,
+    };
+    match (lower, upper) {
+        (Some(lower), Some(upper)) => format!("{lower} and {upper}"),
+        (Some(bound), None) | (None, Some(bound)) => bound,
+        (None, None) => String::from("anything"),
+    }
+}
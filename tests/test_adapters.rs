@@ -0,0 +1,349 @@
+#![cfg(feature = "std")]
+
+use std::time::{Duration, SystemTime};
+
+use nojson::{
+    Base64, EmptyStringAsNone, EnvExpanded, EpochMillis, EpochSeconds, HexBytes, Iso8601, Json,
+    JsonParseError, JsonStringOf, JsonValueKind, LazyArray, Lenient, NullAsDefault, NumberAsString,
+    OneOrMany, RawJson, Redacted, StringOrNumber,
+};
+
+#[test]
+fn iso8601() -> Result<(), JsonParseError> {
+    let epoch = SystemTime::UNIX_EPOCH;
+    for (time, text) in [
+        (epoch, r#""1970-01-01T00:00:00Z""#),
+        (
+            epoch + Duration::new(951_782_400, 1),
+            r#""2000-02-29T00:00:00.000000001Z""#,
+        ),
+        (
+            epoch - Duration::from_millis(1500),
+            r#""1969-12-31T23:59:58.5Z""#,
+        ),
+        (
+            epoch + Duration::from_secs(253_402_300_799),
+            r#""9999-12-31T23:59:59Z""#,
+        ),
+    ] {
+        assert_eq!(Json(Iso8601(time)).to_string(), text);
+        assert_eq!(text.parse::<Json<Iso8601>>()?.0.0, time);
+    }
+
+    // Years outside 0000..=9999 cannot be written as RFC 3339 timestamps.
+    let mut text = String::new();
+    for (time, display) in [
+        (
+            epoch + Duration::from_secs(253_402_300_800),
+            "+10000-01-01T00:00:00Z",
+        ),
+        (
+            epoch - Duration::from_secs(62_167_219_201),
+            "-0001-12-31T23:59:59Z",
+        ),
+    ] {
+        assert_eq!(Iso8601(time).to_string(), display);
+        assert!(std::fmt::write(&mut text, format_args!("{}", Json(Iso8601(time)))).is_err());
+    }
+
+    // Offsets, lowercase separators and long fractions.
+    let parsed: Json<Iso8601> = r#""1970-01-01t09:30:00.1234567891-00:30""#.parse()?;
+    assert_eq!(parsed.0.0, epoch + Duration::new(36_000, 123_456_789));
+
+    for (text, position) in [
+        (r#""2023-02-29T00:00:00Z""#, 9),
+        (r#""2023-01-01 00:00:00Z""#, 11),
+        (r#""2023-01-01T24:00:00Z""#, 12),
+        (r#""2023-01-01T00:00:00.Z""#, 21),
+        (r#""2023-01-01T00:00:00""#, 20),
+        (r#""2023-01-01T00:00:00Zx""#, 21),
+        (r#""2023-1-01T00:00:00Z""#, 7),
+    ] {
+        let e = text.parse::<Json<Iso8601>>().expect_err("error");
+        assert_eq!(e.position(), position, "{text}: {e}");
+    }
+    Ok(())
+}
+
+#[test]
+fn epoch_seconds_and_millis() -> Result<(), JsonParseError> {
+    let epoch = SystemTime::UNIX_EPOCH;
+    for (time, secs, millis) in [
+        (epoch, "0", "0"),
+        (epoch + Duration::from_secs(1), "1", "1000"),
+        (epoch + Duration::new(1, 1), "1.000000001", "1000.000001"),
+        (epoch - Duration::from_millis(1500), "-1.5", "-1500"),
+    ] {
+        assert_eq!(Json(EpochSeconds(time)).to_string(), secs);
+        assert_eq!(Json(EpochMillis(time)).to_string(), millis);
+        assert_eq!(secs.parse::<Json<EpochSeconds>>()?.0.0, time);
+        assert_eq!(millis.parse::<Json<EpochMillis>>()?.0.0, time);
+    }
+
+    // Floats and exponents are accepted; extra precision is truncated.
+    assert_eq!(
+        "2.5e-1".parse::<Json<EpochSeconds>>()?.0.0,
+        epoch + Duration::from_millis(250)
+    );
+    assert_eq!(
+        "1.0000000009".parse::<Json<EpochSeconds>>()?.0.0,
+        epoch + Duration::from_secs(1)
+    );
+    assert_eq!("0e99999999999".parse::<Json<EpochMillis>>()?.0.0, epoch);
+
+    assert!("1e100".parse::<Json<EpochSeconds>>().is_err());
+    assert!(r#""1""#.parse::<Json<EpochMillis>>().is_err());
+    Ok(())
+}
+
+#[test]
+fn json_string_of() -> Result<(), JsonParseError> {
+    let value: Json<Vec<JsonStringOf<u64>>> = r#"["1","18446744073709551615"]"#.parse()?;
+    assert_eq!(value.0, [JsonStringOf(1), JsonStringOf(u64::MAX)]);
+    assert_eq!(value.to_string(), r#"["1","18446744073709551615"]"#);
+
+    let e = r#"[ "x"]"#.parse::<Json<Vec<JsonStringOf<u64>>>>().expect_err("invalid digit");
+    assert!(matches!(e, JsonParseError::InvalidValue { .. }));
+    assert_eq!(e.position(), 2);
+    Ok(())
+}
+
+#[test]
+fn number_as_string() -> Result<(), JsonParseError> {
+    let value: Json<Vec<NumberAsString<i64>>> = r#"["-1", 2, "9223372036854775807"]"#.parse()?;
+    assert_eq!(
+        value.0,
+        [
+            NumberAsString(-1),
+            NumberAsString(2),
+            NumberAsString(i64::MAX)
+        ]
+    );
+    assert_eq!(value.to_string(), r#"["-1","2","9223372036854775807"]"#);
+
+    let value: Json<NumberAsString<f64>> = "1.5".parse()?;
+    assert_eq!(value.to_string(), r#""1.5""#);
+
+    for text in [r#""abc""#, "1.5", "null", r#""9223372036854775808""#] {
+        assert!(text.parse::<Json<NumberAsString<i64>>>().is_err(), "{text}");
+    }
+    Ok(())
+}
+
+#[test]
+fn base64() -> Result<(), JsonParseError> {
+    let cases: [(&[u8], &str); 7] = [
+        (b"", r#""""#),
+        (b"f", r#""Zg==""#),
+        (b"fo", r#""Zm8=""#),
+        (b"foo", r#""Zm9v""#),
+        (b"foob", r#""Zm9vYg==""#),
+        (b"fooba", r#""Zm9vYmE=""#),
+        (&[0xfb, 0xff, 0xfe], r#""+//+""#),
+    ];
+    for (bytes, text) in cases {
+        assert_eq!(Json(Base64(bytes)).to_string(), text);
+        assert_eq!(text.parse::<Json<Base64<Vec<u8>>>>()?.0.0, bytes);
+    }
+
+    for (text, position) in [
+        (r#""Zm9""#, 4),
+        (r#""Zm9v=g==""#, 5),
+        (r#""Z===""#, 2),
+        (r#""Zg=a""#, 3),
+        (r#""Zm 9""#, 3),
+    ] {
+        let e = text
+            .parse::<Json<Base64<Vec<u8>>>>()
+            .expect_err("invalid base64");
+        assert_eq!(e.position(), position, "{text}");
+    }
+    Ok(())
+}
+
+#[test]
+fn hex_bytes() -> Result<(), JsonParseError> {
+    assert_eq!(Json(HexBytes(b"")).to_string(), r#""""#);
+    assert_eq!(Json(HexBytes(vec![0, 15, 255])).to_string(), r#""000fff""#);
+    assert_eq!(
+        r#""000FfF""#.parse::<Json<HexBytes<Vec<u8>>>>()?.0.0,
+        [0, 15, 255]
+    );
+
+    for (text, position) in [(r#""abc""#, 4), (r#""0g""#, 2), (r#" "+0""#, 2)] {
+        let e = text
+            .parse::<Json<HexBytes<Vec<u8>>>>()
+            .expect_err("invalid hex");
+        assert_eq!(e.position(), position, "{text}");
+    }
+    Ok(())
+}
+
+#[test]
+fn one_or_many() -> Result<(), JsonParseError> {
+    let value: Json<[OneOrMany<u32>; 3]> = "[1, [], [2, 3]]".parse()?;
+    assert_eq!(
+        value.0,
+        [OneOrMany(vec![1]), OneOrMany(vec![]), OneOrMany(vec![2, 3])]
+    );
+    assert_eq!(value.to_string(), "[1,[],[2,3]]");
+
+    assert!("[1, true]".parse::<Json<OneOrMany<u32>>>().is_err());
+    assert!("null".parse::<Json<OneOrMany<u32>>>().is_err());
+    Ok(())
+}
+
+#[test]
+fn lenient() -> Result<(), JsonParseError> {
+    let value: Json<[Lenient<f64>; 3]> = r#"[1.5, "2.5", " -3 "]"#.parse()?;
+    assert_eq!(value.0, [Lenient(1.5), Lenient(2.5), Lenient(-3.0)]);
+
+    let value: Json<[Lenient<bool>; 5]> = r#"[true, 0, "1", "true", " false"]"#.parse()?;
+    assert_eq!(value.0.map(|v| v.0), [true, false, true, true, false]);
+
+    // Strings are kept as-is when the target type accepts them.
+    let value: Json<Lenient<String>> = r#""42""#.parse()?;
+    assert_eq!(value.0.0, "42");
+
+    // The original error is reported when no coercion applies.
+    let e = r#""null""#.parse::<Json<Lenient<Option<u8>>>>().expect_err("not coerced");
+    assert!(matches!(
+        e,
+        JsonParseError::InvalidValue {
+            kind: JsonValueKind::String,
+            ..
+        }
+    ));
+    for text in [r#""[1]""#, r#""256""#, r#""yes""#] {
+        assert!(text.parse::<Json<Lenient<u8>>>().is_err(), "{text}");
+    }
+    for text in ["2", r#""2""#, r#""yes""#, "null"] {
+        assert!(text.parse::<Json<Lenient<bool>>>().is_err(), "{text}");
+    }
+    Ok(())
+}
+
+#[test]
+fn empty_string_as_none() -> Result<(), JsonParseError> {
+    let value: Json<Vec<EmptyStringAsNone<String, true>>> =
+        r#"["", " \t", null, "a", " b "]"#.parse()?;
+    assert_eq!(
+        value.0.iter().map(|v| v.0.as_deref()).collect::<Vec<_>>(),
+        [None, None, None, Some("a"), Some(" b ")]
+    );
+    assert_eq!(value.to_string(), r#"[null,null,null,"a"," b "]"#);
+
+    assert!(r#"" ""#.parse::<Json<EmptyStringAsNone<u32>>>().is_err());
+    assert!(r#""1""#.parse::<Json<EmptyStringAsNone<u32>>>().is_err());
+    Ok(())
+}
+
+#[test]
+fn null_as_default() -> Result<(), JsonParseError> {
+    let value: Json<[NullAsDefault<String>; 2]> = r#"[null, "a"]"#.parse()?;
+    assert_eq!(
+        value.0,
+        [NullAsDefault(String::new()), NullAsDefault("a".to_owned())]
+    );
+    assert_eq!(value.to_string(), r#"["","a"]"#);
+
+    let json = RawJson::parse(r#"{"a": null, "b": 1}"#)?;
+    let a: NullAsDefault<u8> = json.value().to_member("a")?.try_into()?;
+    let b: NullAsDefault<u8> = json.value().to_member("b")?.try_into()?;
+    let c: NullAsDefault<u8> = json.value().to_member("c")?.try_into()?;
+    assert_eq!([a.0, b.0, c.0], [0, 1, 0]);
+
+    assert!("true".parse::<Json<NullAsDefault<u8>>>().is_err());
+    Ok(())
+}
+
+#[test]
+fn string_or_number() -> Result<(), JsonParseError> {
+    let values: Json<Vec<StringOrNumber>> = r#"[1.5e3, "a\"b", -0]"#.parse()?;
+    assert!(values.0[0].is_number());
+    assert!(values.0[1].is_string());
+    assert_eq!(values.0[0].parse::<f64>(), Ok(1500.0));
+    assert_eq!(values.0[1].as_str(), "a\"b");
+    assert_eq!(values.0[2].to_string(), "-0");
+    assert_eq!(values.to_string(), r#"[1.5e3,"a\"b",-0]"#);
+
+    assert!("[1]".parse::<Json<StringOrNumber>>().is_err());
+    Ok(())
+}
+
+#[test]
+fn lazy_array() {
+    let rows = [("a", 1), ("b", 2)];
+    let value = LazyArray(rows.iter().map(|(name, n)| {
+        nojson::object(move |f| {
+            f.member("name", name)?;
+            f.member("n", n)
+        })
+    }));
+    assert_eq!(
+        Json(&value).to_string(),
+        r#"[{"name":"a","n":1},{"name":"b","n":2}]"#
+    );
+    assert_eq!(
+        Json(&value).to_string(),
+        r#"[{"name":"a","n":1},{"name":"b","n":2}]"#
+    );
+
+    let empty = LazyArray(std::iter::empty::<u8>());
+    assert_eq!(Json(empty).to_string(), "[]");
+}
+
+#[test]
+fn env_expanded() -> Result<(), JsonParseError> {
+    // Cargo sets `CARGO_PKG_NAME` when running tests.
+    let text = r#"["${CARGO_PKG_NAME}-$HOME-${CARGO_PKG_NAME}", "$${x}", "${NOJSON_UNDEFINED_VAR}", "a${CARGO_PKG_NAME"]"#;
+    let json = RawJson::parse(text)?;
+    let values: Vec<_> = json.value().to_array()?.collect();
+
+    let expanded = EnvExpanded::<String>::try_from(values[0])?;
+    assert_eq!(expanded.0, "nojson-$HOME-nojson");
+    assert_eq!(EnvExpanded::<String>::try_from(values[1])?.0, "${x}");
+    assert_eq!(Json(&expanded).to_string(), r#""nojson-$HOME-nojson""#);
+
+    let e = EnvExpanded::<String>::try_from(values[2]).expect_err("undefined");
+    assert_eq!(e.position(), text.find("${NOJSON_UNDEFINED_VAR}").unwrap());
+    assert!(
+        e.to_string()
+            .contains("undefined environment variable: NOJSON_UNDEFINED_VAR")
+    );
+
+    let e = EnvExpanded::<String>::try_from(values[3]).expect_err("unterminated");
+    assert_eq!(e.position(), text.find("${CARGO_PKG_NAME\"").unwrap());
+
+    let json = RawJson::parse(r#""${CARGO_PKG_NAME}""#)?;
+    assert!(EnvExpanded::<u16>::try_from(json.value()).is_err());
+    Ok(())
+}
+
+#[test]
+fn redacted() -> Result<(), JsonParseError> {
+    let json = RawJson::parse(r#"{"user": "alice", "password": "hunter2", "keys": [1, 2]}"#)?;
+    let value = json.value();
+    let password: Redacted<String> = value.to_member("password")?.required()?.try_into()?;
+    let keys: Redacted<Vec<u8>> = value.to_member("keys")?.required()?.try_into()?;
+    assert_eq!(password.0, "hunter2");
+    assert_eq!(keys.0, [1, 2]);
+
+    let output = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_spacing(true);
+        f.object(|f| {
+            f.member("password", &password)?;
+            f.member("keys", &keys)?;
+            f.member("none", Redacted(None::<u8>))
+        })
+    });
+    assert_eq!(
+        output.to_string(),
+        "{\n  \"password\": \"***\",\n  \"keys\": \"***\",\n  \"none\": \"***\"\n}"
+    );
+    assert!(!format!("{password:?} {keys:?}").contains("hunter2"));
+
+    assert!(Redacted::<u8>::try_from(value.to_member("user")?.required()?).is_err());
+    Ok(())
+}
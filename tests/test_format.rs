@@ -1,6 +1,10 @@
-use std::{borrow::Cow, collections::BTreeMap};
+use std::{borrow::Cow, cell::Cell, collections::BTreeMap};
 
-use nojson::{DisplayJson, Json, JsonParseError, json};
+use nojson::{
+    DisplayJson, FloatPrecision, Json, JsonArrayFormatter, JsonFormatOptions, JsonFormatter,
+    JsonObjectFormatter, JsonParseError, NestedSettings, NonFiniteFloat, array_from_iter, json,
+    json_once, object_from_pairs,
+};
 
 #[test]
 fn float() {
@@ -13,6 +17,109 @@ fn float() {
     assert_eq!(json(|f| f.value(f64::INFINITY)).to_string(), "null");
 }
 
+#[test]
+fn non_finite_float() {
+    let values = [f64::NAN, f64::INFINITY, f64::NEG_INFINITY];
+    assert_eq!(
+        json(|f| {
+            f.set_non_finite_float(NonFiniteFloat::String);
+            f.value(values)
+        })
+        .to_string(),
+        r#"["NaN","Infinity","-Infinity"]"#
+    );
+
+    let mut buf = String::new();
+    let output = json(|f| {
+        f.set_non_finite_float(NonFiniteFloat::Error);
+        f.value([1.0, f64::INFINITY])
+    });
+    assert!(std::fmt::write(&mut buf, format_args!("{output}")).is_err());
+}
+
+#[test]
+fn float_precision() {
+    let values = [0.0, -1.5, 2.0, 1.0 / 3.0, 1e30, -2.5e-8];
+    let format = |precision, range: Option<std::ops::Range<i32>>| {
+        json(move |f| {
+            f.set_float_precision(precision);
+            f.set_float_positional_range(range.clone());
+            f.value(values)
+        })
+        .to_string()
+    };
+
+    assert_eq!(
+        format(FloatPrecision::Shortest, None),
+        format!("{}", Json(values))
+    );
+    assert_eq!(
+        format(FloatPrecision::Shortest, Some(-6..21)),
+        "[0,-1.5,2,0.3333333333333333,1e30,-2.5e-8]"
+    );
+    assert_eq!(
+        format(FloatPrecision::Decimals(1), Some(-3..3)),
+        "[0.0,-1.5,2.0,0.3,1.0e30,-2.5e-8]"
+    );
+    assert_eq!(
+        format(FloatPrecision::SignificantDigits(2), None),
+        "[0,-1.5,2,0.33,1000000000000000000000000000000,-0.000000025]"
+    );
+    assert_eq!(
+        format(FloatPrecision::SignificantDigits(1), Some(0..3)),
+        "[0,-2,2,3e-1,1e30,-2e-8]"
+    );
+    // `0` is treated as `1`.
+    for range in [None, Some(0..3)] {
+        assert_eq!(
+            format(FloatPrecision::SignificantDigits(0), range.clone()),
+            format(FloatPrecision::SignificantDigits(1), range)
+        );
+    }
+    assert_eq!(
+        json(|f| {
+            f.set_float_precision(FloatPrecision::SignificantDigits(3));
+            f.value(9.9999f32)
+        })
+        .to_string(),
+        "10"
+    );
+}
+
+#[test]
+fn force_float_decimal_point() {
+    let format = |precision, range: Option<std::ops::Range<i32>>| {
+        json(move |f| {
+            f.set_force_float_decimal_point(true);
+            f.set_float_precision(precision);
+            f.set_float_positional_range(range.clone());
+            f.value([0.0, -2.0, 2.5, 1e30])
+        })
+        .to_string()
+    };
+
+    assert_eq!(
+        format(FloatPrecision::Shortest, Some(-6..21)),
+        "[0.0,-2.0,2.5,1.0e30]"
+    );
+    assert_eq!(
+        format(FloatPrecision::Decimals(0), Some(-6..21)),
+        "[0.0,-2.0,2.0,1.0e30]"
+    );
+    assert_eq!(
+        format(FloatPrecision::SignificantDigits(2), Some(-6..21)),
+        "[0.0,-2.0,2.5,1.0e30]"
+    );
+    assert_eq!(
+        json(|f| {
+            f.set_force_float_decimal_point(true);
+            f.value([1u8, 2u8])
+        })
+        .to_string(),
+        "[1,2]"
+    );
+}
+
 #[test]
 fn string() {
     assert_eq!(
@@ -190,3 +297,976 @@ fn raw_json_owned_array() -> Result<(), JsonParseError> {
     assert_eq!(values.next(), None);
     Ok(())
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn os_string() -> Result<(), JsonParseError> {
+    let value = std::ffi::OsString::from("foo\"bar");
+    assert_eq!(Json(&value).to_string(), r#""foo\"bar""#);
+    assert_eq!(Json(value.as_os_str()).to_string(), r#""foo\"bar""#);
+
+    let parsed: Json<std::ffi::OsString> = r#""foo\"bar""#.parse()?;
+    assert_eq!(parsed.0, value);
+
+    // Non-UTF-8 strings are not converted lossily.
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        let invalid = std::ffi::OsStr::from_bytes(b"foo\xffbar");
+        let mut buf = String::new();
+        assert!(std::fmt::write(&mut buf, format_args!("{}", Json(invalid))).is_err());
+        let invalid = invalid.to_os_string();
+        assert!(std::fmt::write(&mut buf, format_args!("{}", Json(&invalid))).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn interior_mutability() {
+    use std::cell::{Cell, RefCell};
+
+    assert_eq!(Json(Cell::new(3)).to_string(), "3");
+
+    let cell = RefCell::new(vec![1, 2]);
+    assert_eq!(Json(&cell).to_string(), "[1,2]");
+
+    let _guard = cell.borrow_mut();
+    assert!(std::fmt::write(&mut String::new(), format_args!("{}", Json(&cell))).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn locks() {
+    use std::sync::{Arc, Mutex, RwLock};
+
+    let mutex = Arc::new(Mutex::new([1, 2]));
+    assert_eq!(Json(&mutex).to_string(), "[1,2]");
+    assert_eq!(Json(RwLock::new("foo")).to_string(), r#""foo""#);
+
+    // Poisoned locks are still formatted.
+    let cloned = Arc::clone(&mutex);
+    let _ = std::thread::spawn(move || {
+        let _guard = cloned.lock().unwrap();
+        panic!("poison");
+    })
+    .join();
+    assert!(mutex.is_poisoned());
+    assert_eq!(Json(&mutex).to_string(), "[1,2]");
+}
+
+#[test]
+fn member_if_some() {
+    let output = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_spacing(true);
+        f.object(|f| {
+            f.member_if_some("a", None::<u32>)?;
+            f.member_if_some("b", Some(1))?;
+            f.member_if_some("c", None::<u32>)
+        })
+    });
+    assert_eq!(output.to_string(), "{\n  \"b\": 1\n}");
+
+    let output = nojson::json(|f| f.object(|f| f.member_if_some("a", None::<u32>)));
+    assert_eq!(output.to_string(), "{}");
+}
+
+#[test]
+fn conditional_elements_and_members() {
+    let output = nojson::json(|f| {
+        f.set_spacing(true);
+        f.array(|f| {
+            f.element_if(false, 1)?;
+            f.element_if(true, 2)?;
+            f.element(nojson::json(|f| {
+                f.object(|f| {
+                    f.member_if(false, "a", 1)?;
+                    f.member_if(true, "b", 2)?;
+                    f.member_if(true, "c", 3)
+                })
+            }))
+        })
+    });
+    assert_eq!(output.to_string(), r#"[2, { "b": 2, "c": 3 }]"#);
+}
+
+#[test]
+fn raw_json_fragments() {
+    let output = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_spacing(true);
+        f.object(|f| {
+            f.raw_member("a", r#"{"x":[1,2]}"#)?;
+            f.member("b", nojson::json(|f| f.array(|f| f.raw_element(" true "))))
+        })
+    });
+    assert_eq!(
+        output.to_string(),
+        "{\n  \"a\": {\"x\":[1,2]},\n  \"b\": [\n     true \n  ]\n}"
+    );
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "invalid raw JSON text")]
+fn raw_json_validation() {
+    let _ = nojson::json(|f| f.raw_json("{")).to_string();
+}
+
+#[test]
+fn flatten() {
+    let header = nojson::object(|f| {
+        f.member("a\"b", 1.5)?;
+        f.member("nested", [1, 2])
+    });
+    let output = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_spacing(true);
+        f.set_force_float_decimal_point(true);
+        f.object(|f| {
+            f.member("x", 0)?;
+            f.flatten(&header)?;
+            f.flatten(nojson::object(|_| Ok(())))?;
+            f.member("y", 2.0)
+        })
+    });
+    assert_eq!(
+        output.to_string(),
+        "{\n  \"x\": 0,\n  \"a\\\"b\": 1.5,\n  \"nested\": [\n    1,\n    2\n  ],\n  \"y\": 2.0\n}"
+    );
+
+    // Errors from the flattened value are propagated instead of panicking.
+    let nan = nojson::object(|f| f.member("nan", f64::NAN));
+    let output = nojson::json(|f| {
+        f.set_non_finite_float(NonFiniteFloat::Error);
+        f.object(|f| f.flatten(&nan))
+    });
+    assert!(std::fmt::write(&mut String::new(), format_args!("{output}")).is_err());
+
+    let deep = nojson::object(|f| f.member("a", [[1]]));
+    let output = nojson::json(|f| {
+        f.set_max_depth(Some(2));
+        f.object(|f| f.flatten(&deep))
+    });
+    assert!(std::fmt::write(&mut String::new(), format_args!("{output}")).is_err());
+}
+
+#[test]
+fn json5_output() {
+    let output = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_spacing(true);
+        f.set_unquoted_keys(true);
+        f.set_single_quoted_strings(true);
+        f.set_trailing_commas(true);
+        f.object(|f| {
+            f.member("_id$1", "it's")?;
+            f.member("1st", "\"q\"")?;
+            f.member("", [0; 0])?;
+            f.member("list", [1, 2])
+        })
+    });
+    assert_eq!(
+        output.to_string(),
+        r#"{
+  _id$1: 'it\'s',
+  '1st': '"q"',
+  '': [],
+  list: [
+    1,
+    2,
+  ],
+}"#
+    );
+
+    // Trailing commas are not written in compact output.
+    let output = nojson::json(|f| {
+        f.set_trailing_commas(true);
+        f.value([1, 2])
+    });
+    assert_eq!(output.to_string(), "[1,2]");
+}
+
+#[test]
+fn crlf() {
+    let output = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_spacing(true);
+        f.set_crlf(true);
+        f.object(|f| {
+            f.member("a", [1])?;
+            f.member("b", "x\ny")
+        })
+    });
+    assert_eq!(
+        output.to_string(),
+        "{\r\n  \"a\": [\r\n    1\r\n  ],\r\n  \"b\": \"x\\ny\"\r\n}"
+    );
+}
+
+#[test]
+fn max_inline_width() {
+    let value = nojson::object(|f| {
+        f.member("id", 1)?;
+        f.member("tags", ["a", "b"])?;
+        f.member(
+            "nested",
+            nojson::object(|f| {
+                f.member("points", [[1, 2], [3, 4]])?;
+                f.member("empty", nojson::object(|_| Ok(())))
+            }),
+        )
+    });
+    let format = |width| {
+        nojson::json(|f| {
+            f.set_indent_size(2);
+            f.set_spacing(true);
+            f.set_max_inline_width(Some(width));
+            f.value(&value)
+        })
+        .to_string()
+    };
+
+    // Everything fits on a single line.
+    let compact = format(200);
+    assert_eq!(
+        compact,
+        r#"{ "id": 1, "tags": ["a", "b"], "nested": { "points": [[1, 2], [3, 4]], "empty": {} } }"#
+    );
+    assert_eq!(format(compact.len()), compact);
+
+    // `"nested": { ... }` (54 chars with indentation) doesn't fit, but its children do.
+    assert_eq!(
+        format(40),
+        r#"{
+  "id": 1,
+  "tags": ["a", "b"],
+  "nested": {
+    "points": [[1, 2], [3, 4]],
+    "empty": {}
+  }
+}"#
+    );
+
+    // JSON5 options are honored in both layouts.
+    let output = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_unquoted_keys(true);
+        f.set_single_quoted_strings(true);
+        f.set_trailing_commas(true);
+        f.set_max_inline_width(Some(12));
+        f.object(|f| {
+            f.member("a", ["x"])?;
+            f.member("b", ["x", "y", "z"])
+        })
+    });
+    assert_eq!(
+        output.to_string(),
+        "{\n  a:['x'],\n  b:[\n    'x',\n    'y',\n    'z',\n  ],\n}"
+    );
+
+    // Settings changed by nested values apply to the containers they write.
+    let child = nojson::json(|f| {
+        f.set_indent_size(0);
+        f.value([[1, 2, 3], [4, 5, 6]])
+    });
+    for width in [None, Some(8)] {
+        let output = nojson::json(|f| {
+            f.set_indent_size(2);
+            f.set_max_inline_width(width);
+            f.array(|f| f.element(&child))
+        });
+        assert_eq!(output.to_string(), "[\n  [[1,2,3],[4,5,6]]\n]");
+    }
+    let child = nojson::json(|f| {
+        f.set_max_inline_width(Some(20));
+        f.value([[1, 2, 3], [4, 5, 6]])
+    });
+    let output = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_max_inline_width(Some(8));
+        f.array(|f| f.element(&child))
+    });
+    assert_eq!(output.to_string(), "[\n  [[1,2,3],[4,5,6]]\n]");
+}
+
+#[test]
+fn key_case() {
+    use nojson::{JsonFormatOptions, JsonKeyCase};
+
+    let value = nojson::object(|f| {
+        f.member("user_id", 1)?;
+        f.member(
+            "PascalName",
+            nojson::object(|f| f.member("nested_key", ["a_b"])),
+        )
+    });
+    let format = |case, width| {
+        let options = JsonFormatOptions::new()
+            .indent_size(2)
+            .key_case(case)
+            .max_inline_width(width);
+        nojson::json(|f| {
+            f.apply(&options);
+            f.value(&value)
+        })
+        .to_string()
+    };
+
+    let camel = Some(JsonKeyCase::CamelCase);
+    let expected = r#"{"userId":1,"pascalName":{"nestedKey":["a_b"]}}"#;
+    assert_eq!(format(camel, Some(100)), expected);
+    assert_eq!(
+        format(camel, None),
+        "{\n  \"userId\":1,\n  \"pascalName\":{\n    \"nestedKey\":[\n      \"a_b\"\n    ]\n  }\n}"
+    );
+    assert_eq!(
+        format(Some(JsonKeyCase::PascalCase), Some(100)),
+        r#"{"UserId":1,"PascalName":{"NestedKey":["a_b"]}}"#
+    );
+    assert_eq!(
+        format(None, Some(100)),
+        r#"{"user_id":1,"PascalName":{"nested_key":["a_b"]}}"#
+    );
+}
+
+#[test]
+fn max_depth() {
+    use std::fmt::Write;
+
+    struct Recursive;
+
+    impl DisplayJson for Recursive {
+        fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> std::fmt::Result {
+            f.array(|f| f.element(Recursive))
+        }
+    }
+
+    for width in [None, Some(80)] {
+        let format = |depth, value: &dyn Fn(&mut JsonFormatter<'_, '_>) -> std::fmt::Result| {
+            let mut output = String::new();
+            write!(
+                output,
+                "{}",
+                nojson::json(|f| {
+                    f.set_indent_size(2);
+                    f.set_max_inline_width(width);
+                    f.set_max_depth(depth);
+                    value(f)
+                })
+            )
+            .map(|()| output)
+        };
+
+        assert!(format(Some(64), &|f| f.value(Recursive)).is_err());
+        assert!(format(Some(2), &|f| f.value([[1]])).is_ok());
+        assert!(format(Some(2), &|f| f.value([[[1]]])).is_err());
+        assert!(format(Some(1), &|f| f.object(|f| f.member("a", [1]))).is_err());
+        assert!(format(Some(0), &|f| f.value(1)).is_ok());
+        assert!(format(None, &|f| f.value([[[1]]])).is_ok());
+    }
+}
+
+#[test]
+fn elide() {
+    use std::cell::Cell;
+
+    struct Recursive;
+
+    impl DisplayJson for Recursive {
+        fn fmt(&self, f: &mut JsonFormatter<'_, '_>) -> std::fmt::Result {
+            f.array(|f| f.element(Recursive))
+        }
+    }
+
+    let value = nojson::json(|f| {
+        f.set_elide_depth(Some(3));
+        f.set_elide_elements(Some(2));
+        f.object(|f| {
+            f.member("a", [[1, 2, 3], [4, 5, 6]])?;
+            f.member("b", Recursive)?;
+            f.member("c", [7])?;
+            f.member("d", 8)
+        })
+    });
+    assert_eq!(
+        value.to_string(),
+        r#"{"a":[[1,2,"…"],[4,5,"…"]],"b":[["…"]],"…":"…"}"#
+    );
+
+    let value = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_spacing(true);
+        f.set_elide_elements(Some(1));
+        f.value([1, 2, 3])
+    });
+    assert_eq!(value.to_string(), "[\n  1,\n  \"…\"\n]");
+
+    let value = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_max_inline_width(Some(15));
+        f.set_elide_depth(Some(3));
+        f.object(|f| f.member("a", [[[1]], [[2]]]))
+    });
+    assert_eq!(
+        value.to_string(),
+        "{\n  \"a\":[\n    [\"…\"],\n    [\"…\"]\n  ]\n}"
+    );
+
+    // Omitted values are not formatted at all.
+    let calls = Cell::new(0);
+    let counted = nojson::json(|f| {
+        calls.set(calls.get() + 1);
+        f.value(0)
+    });
+    let value = nojson::json(|f| {
+        f.set_elide_elements(Some(1));
+        f.array(|f| {
+            for _ in 0..3 {
+                f.element(&counted)?;
+            }
+            Ok(())
+        })
+    });
+    assert_eq!(value.to_string(), r#"[0,"…"]"#);
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn max_string_len() {
+    let value = nojson::json(|f| {
+        f.set_max_string_len(Some(3));
+        f.object(|f| {
+            f.member("long_name", "abc")?;
+            f.member("b", "abcd")?;
+            f.member_with("c", |f| f.string(format_args!("{}{}", "a\n", "\u{1}z")))?;
+            f.member("d", "日本語です")
+        })
+    });
+    assert_eq!(
+        value.to_string(),
+        r#"{"long_name":"abc","b":"abc…","c":"a\n\u0001…","d":"日本語…"}"#
+    );
+    assert!(nojson::RawJson::parse(&value.to_string()).is_ok());
+
+    let value = nojson::json(|f| {
+        f.set_max_string_len(Some(2));
+        f.set_escape_non_ascii(true);
+        f.value("éèê")
+    });
+    assert_eq!(value.to_string(), r#""\u00e9\u00e8\u2026""#);
+
+    let value = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_max_inline_width(Some(80));
+        f.set_max_string_len(Some(1));
+        f.value(["ab", "cd"])
+    });
+    assert_eq!(value.to_string(), r#"["a…","c…"]"#);
+}
+
+#[test]
+fn track_path() {
+    use std::cell::RefCell;
+
+    let paths = RefCell::new(Vec::new());
+    let leaf = nojson::json(|f| {
+        paths.borrow_mut().push(f.current_path());
+        f.value(1)
+    });
+    let value = nojson::object(|f| {
+        f.member("a", [&leaf, &leaf])?;
+        f.member("b c", nojson::object(|f| f.member("d", &leaf)))?;
+        f.member("e", &leaf)
+    });
+
+    for width in [None, Some(80)] {
+        paths.borrow_mut().clear();
+        let output = nojson::json(|f| {
+            f.set_indent_size(2);
+            f.set_max_inline_width(width);
+            f.set_track_path(true);
+            f.value(&value)
+        });
+        output.to_string();
+        let paths = paths.borrow().iter().flatten().cloned().collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            ["$.a[0]", "$.a[1]", r#"$["b c"].d"#, "$.e"],
+            "{width:?}"
+        );
+    }
+
+    paths.borrow_mut().clear();
+    nojson::json(|f| f.value(&value)).to_string();
+    assert!(paths.borrow().iter().all(Option::is_none));
+}
+
+#[test]
+fn inline_numeric_arrays() {
+    let output = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_inline_numeric_arrays(true);
+        f.object(|f| {
+            f.member("coords", [[1, 2], [3, 4]])?;
+            f.member("flags", [true, false])?;
+            f.member(
+                "mixed",
+                nojson::json(|f| {
+                    f.array(|f| {
+                        f.element(1)?;
+                        f.element(nojson::json(|f| f.raw_json("null")))
+                    })
+                }),
+            )?;
+            f.member("empty", [0u8; 0])
+        })
+    });
+    assert_eq!(
+        output.to_string(),
+        "{\n  \"coords\":[\n    [1,2],\n    [3,4]\n  ],\n  \"flags\":[true,false],\n  \"mixed\":[\n    1,\n    null\n  ],\n  \"empty\":[]\n}"
+    );
+}
+
+#[test]
+fn escape_non_ascii() -> Result<(), JsonParseError> {
+    let text = "a\u{80}\u{ffff}\u{10000}\u{10ffff}\n\"ü";
+    let output = nojson::json(|f| {
+        f.set_escape_non_ascii(true);
+        f.object(|f| f.member("ключ", text))
+    })
+    .to_string();
+    assert!(output.is_ascii());
+    assert_eq!(
+        output,
+        r#"{"\u043a\u043b\u044e\u0447":"a\u0080\uffff\ud800\udc00\udbff\udfff\n\"\u00fc"}"#
+    );
+
+    let parsed: Json<std::collections::BTreeMap<String, String>> = output.parse()?;
+    assert_eq!(parsed.0["ключ"], text);
+    Ok(())
+}
+
+#[test]
+fn ansi_colors() {
+    let output = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_spacing(true);
+        f.set_ansi_colors(true);
+        f.object(|f| {
+            f.member("s", "x")?;
+            f.member("n", [1.5, f64::NAN])
+        })
+    })
+    .to_string();
+    let expected = "\x1b[1m{\x1b[0m\n  \x1b[1;34m\"s\"\x1b[0m\x1b[1m:\x1b[0m \x1b[32m\"x\"\x1b[0m\x1b[1m,\x1b[0m\n  \x1b[1;34m\"n\"\x1b[0m\x1b[1m:\x1b[0m \x1b[1m[\x1b[0m\n    \x1b[36m1.5\x1b[0m\x1b[1m,\x1b[0m\n    \x1b[1;30mnull\x1b[0m\n  \x1b[1m]\x1b[0m\n\x1b[1m}\x1b[0m";
+    assert_eq!(output, expected);
+
+    // Inline layouts are also colored.
+    let output = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_ansi_colors(true);
+        f.set_inline_numeric_arrays(true);
+        f.value([1, 2])
+    })
+    .to_string();
+    assert_eq!(
+        output,
+        "\x1b[1m[\x1b[0m\x1b[36m1\x1b[0m\x1b[1m,\x1b[0m\x1b[36m2\x1b[0m\x1b[1m]\x1b[0m"
+    );
+}
+
+#[test]
+fn pretty_and_compact_wrappers() {
+    struct Response {
+        body: nojson::PrettyJson<Vec<u32>>,
+    }
+    let response = Response {
+        body: nojson::PrettyJson::new(vec![1, 2]),
+    };
+    assert_eq!(format!("{}", response.body), "[\n  1,\n  2\n]");
+
+    // Settings other than indentation and spacing are inherited by default.
+    let output = nojson::json(|f| {
+        f.set_escape_non_ascii(true);
+        f.set_key_case(Some(nojson::JsonKeyCase::CamelCase));
+        f.value(nojson::PrettyJson::new(nojson::object(|f| {
+            f.member("a_b", "é")
+        })))
+    });
+    assert_eq!(output.to_string(), "{\n  \"aB\": \"\\u00e9\"\n}");
+
+    // Explicit options replace all settings.
+    let options = JsonFormatOptions::new().indent_size(1);
+    let output = nojson::json(|f| {
+        f.set_escape_non_ascii(true);
+        f.value(nojson::PrettyJson::with_options(["é"], options))
+    });
+    assert_eq!(output.to_string(), "[\n \"é\"\n]");
+
+    // Settings changed by the wrappers don't leak to siblings.
+    let output = nojson::json(|f| {
+        f.set_indent_size(4);
+        f.object(|f| {
+            f.member("a", nojson::CompactJson([1, 2]))?;
+            f.member("b", nojson::PrettyJson::new([3]))?;
+            f.member("c", [4])
+        })
+    });
+    assert_eq!(
+        output.to_string(),
+        "{\n    \"a\":[1,2],\n    \"b\":[\n    3\n  ],\n    \"c\":[\n        4\n    ]\n}"
+    );
+}
+
+#[test]
+fn format_options() {
+    let options = JsonFormatOptions::new()
+        .indent_size(2)
+        .spacing(true)
+        .trailing_commas(true);
+    let output = json(|f| {
+        f.apply(&options);
+        assert_eq!(f.options(), options);
+        f.object(|f| f.member("a", [1, 2]))
+    });
+    assert_eq!(output.to_string(), "{\n  \"a\": [\n    1,\n    2,\n  ],\n}");
+
+    // Applying the default options resets the formatter to compact output.
+    let output = json(|f| {
+        f.apply(&options);
+        f.apply(&JsonFormatOptions::default());
+        f.value([1, 2])
+    });
+    assert_eq!(output.to_string(), "[1,2]");
+
+    let output = nojson::json_with(&options, |f| f.value([1]));
+    assert_eq!(output.to_string(), "[\n  1,\n]");
+    assert_eq!(
+        json(|f| f.value(nojson::json_with(&options, |f| f.value([1])))).to_string(),
+        "[\n  1,\n]"
+    );
+}
+
+#[test]
+fn sort_keys() -> Result<(), JsonParseError> {
+    let value = object_from_pairs([("b", 1), ("a", 2), ("c", 3), ("a", 4)]);
+    let output = json(|f| {
+        f.set_sort_keys(true);
+        f.value(&value)
+    });
+    assert_eq!(output.to_string(), r#"{"a":2,"a":4,"b":1,"c":3}"#);
+
+    // Nested objects, including those inside arrays, are sorted as well.
+    let text = r#"{"z": [{"y": 1, "x": [{"q": 0, "p": null}]}], "é": true, "e": "s"}"#;
+    let raw = nojson::RawJson::parse(text)?;
+    let sorted = nojson::json_with(&JsonFormatOptions::new().sort_keys(true), |f| {
+        f.value(raw.value())
+    });
+    assert_eq!(
+        sorted.to_string(),
+        r#"{"e":"s","z":[{"x":[{"p":null,"q":0}],"y":1}],"é":true}"#
+    );
+
+    // Sorting uses the converted names and happens before elision.
+    let options = JsonFormatOptions::new()
+        .indent_size(2)
+        .spacing(true)
+        .sort_keys(true)
+        .key_case(Some(nojson::JsonKeyCase::SnakeCase))
+        .elide_elements(Some(2));
+    let output = nojson::json_with(&options, |f| {
+        f.object(|f| {
+            f.member("zeta", 1)?;
+            f.member("Beta", [3, 2])?;
+            f.member("alpha", 0)
+        })
+    });
+    assert_eq!(
+        output.to_string(),
+        "{\n  \"alpha\": 0,\n  \"beta\": [\n    3,\n    2\n  ],\n  \"…\": \"…\"\n}"
+    );
+
+    // Inline widths are computed from the sorted output.
+    let options = JsonFormatOptions::new()
+        .indent_size(2)
+        .spacing(true)
+        .sort_keys(true)
+        .max_inline_width(Some(30));
+    let output = nojson::json_with(&options, |f| {
+        f.object(|f| {
+            f.member("b", object_from_pairs([("d", 1), ("c", 2)]))?;
+            f.member("a", "long enough to wrap")
+        })
+    });
+    assert_eq!(
+        output.to_string(),
+        "{\n  \"a\": \"long enough to wrap\",\n  \"b\": { \"c\": 2, \"d\": 1 }\n}"
+    );
+    Ok(())
+}
+
+#[test]
+fn container_closure_lifetimes() {
+    // Closures can name the lifetimes of the formatter they are called from,
+    // including when the containers are recorded for a width-aware layout.
+    fn write<'a, 'b>(f: &mut JsonFormatter<'a, 'b>) -> std::fmt::Result {
+        f.object(|f: &mut JsonObjectFormatter<'a, 'b, '_>| {
+            f.member_with("b", |f| {
+                f.array(|f: &mut JsonArrayFormatter<'_, '_, '_>| f.element(1))
+            })?;
+            f.member("a", 2)
+        })
+    }
+    assert_eq!(json(write).to_string(), r#"{"b":[1],"a":2}"#);
+    let output = json(|f| {
+        f.set_indent_size(2);
+        f.set_sort_keys(true);
+        f.set_max_inline_width(Some(10));
+        write(f)
+    });
+    assert_eq!(output.to_string(), "{\n  \"a\":2,\n  \"b\":[1]\n}");
+}
+
+#[test]
+fn write_to() -> std::fmt::Result {
+    let mut text = String::new();
+    JsonFormatter::write_to(&mut text, |f| f.value([1, 2]))?;
+    text.push(';');
+    JsonFormatter::write_to(&mut text, |f| {
+        f.set_indent_size(2);
+        f.object(|f| f.member("a", "b"))
+    })?;
+    assert_eq!(text, "[1,2];{\n  \"a\":\"b\"\n}");
+    Ok(())
+}
+
+#[test]
+fn json_once_closures() {
+    let values = vec![String::from("a"), String::from("b")];
+    let output = json_once(move |f| f.array(|f| f.elements(values)));
+    assert_eq!(output.to_string(), r#"["a","b"]"#);
+
+    // The closure has already been consumed.
+    let mut text = String::new();
+    assert!(std::fmt::write(&mut text, format_args!("{output}")).is_err());
+
+    let output = json(|f| f.value([json_once(|f| f.value(1))]));
+    assert_eq!(output.to_string(), "[1]");
+}
+
+#[test]
+fn closure_trait_objects() {
+    let items: &dyn Fn(&mut JsonFormatter<'_, '_>) -> std::fmt::Result =
+        &|f| f.array(|f| f.elements([1, 2]));
+    let boxed: Box<dyn Fn(&mut JsonFormatter<'_, '_>) -> std::fmt::Result + Send + Sync> =
+        Box::new(|f| f.value("x"));
+    let output = json(|f| {
+        f.object(|f| {
+            f.member("items", items)?;
+            f.member("name", &boxed)
+        })
+    });
+    assert_eq!(output.to_string(), r#"{"items":[1,2],"name":"x"}"#);
+}
+
+#[test]
+fn array_and_object_from_iterators() {
+    let values = [1, 2, 3];
+    let output = array_from_iter(values.iter().filter(|v| **v != 2));
+    assert_eq!(output.to_string(), "[1,3]");
+    assert_eq!(output.to_string(), "[1,3]");
+    assert_eq!(array_from_iter(Vec::<u8>::new()).to_string(), "[]");
+
+    let output = object_from_pairs(values.iter().map(|v| (v, v * v)));
+    assert_eq!(output.to_string(), r#"{"1":1,"2":4,"3":9}"#);
+}
+
+#[test]
+fn try_elements() {
+    let error = Cell::new(None);
+    let output = json(|f| f.array(|f| f.try_elements([Ok(1), Ok(2)], &error)));
+    assert_eq!(output.to_string(), "[1,2]");
+    assert_eq!(error.take(), None::<&str>);
+
+    let mut text = String::new();
+    let result = JsonFormatter::write_to(&mut text, |f| {
+        f.array(|f| f.try_elements([Ok(1), Err("broken row"), Ok(3)], &error))
+    });
+    assert!(result.is_err());
+    assert_eq!(error.take(), Some("broken row"));
+}
+
+#[test]
+fn element_with_and_member_with() {
+    let tags = vec![String::from("x"), String::from("y")];
+    let output = json(|f| {
+        f.set_indent_size(2);
+        f.object(|f| {
+            f.member_with("tags", |f| f.array(|f| f.elements(&tags)))?;
+            f.member_with("nested", |f| {
+                f.array(|f| {
+                    f.element_with(|f| f.value(1))?;
+                    f.element_with(|f| f.object(|_| Ok(())))
+                })
+            })
+        })
+    });
+    assert_eq!(
+        output.to_string(),
+        "{\n  \"tags\":[\n    \"x\",\n    \"y\"\n  ],\n  \"nested\":[\n    1,\n    {}\n  ]\n}"
+    );
+}
+
+#[test]
+fn nested_settings() {
+    let fragment = || {
+        json(|f| {
+            f.set_indent_size(0);
+            f.set_spacing(false);
+            f.value(json(|f| f.value([1, 2])))
+        })
+    };
+    let format = |mode| {
+        json(|f| {
+            f.set_indent_size(2);
+            f.set_spacing(true);
+            f.set_nested_settings(mode);
+            f.object(|f| {
+                f.member("fragment", fragment())?;
+                f.member("pretty", [3])
+            })
+        })
+        .to_string()
+    };
+
+    // By default, the changed settings propagate to the following siblings.
+    assert_eq!(
+        format(NestedSettings::Propagate),
+        "{\n  \"fragment\": [1,2],\"pretty\":[3]\n}"
+    );
+    assert_eq!(
+        format(NestedSettings::Scoped),
+        "{\n  \"fragment\": [1,2],\n  \"pretty\": [\n    3\n  ]\n}"
+    );
+    assert_eq!(
+        format(NestedSettings::Inherit),
+        "{\n  \"fragment\": [\n    1,\n    2\n  ],\n  \"pretty\": [\n    3\n  ]\n}"
+    );
+
+    // Adapters and `apply()` are covered too, including in width-aware layouts.
+    let output = nojson::json_with(
+        &JsonFormatOptions::new()
+            .indent_size(2)
+            .max_inline_width(Some(10))
+            .nested_settings(NestedSettings::Inherit),
+        |f| {
+            f.array(|f| {
+                f.element(nojson::CompactJson([1, 2, 3, 4, 5]))?;
+                f.element(json(|f| {
+                    f.apply(&JsonFormatOptions::new());
+                    f.value([6])
+                }))
+            })
+        },
+    );
+    assert_eq!(
+        output.to_string(),
+        "[\n  [\n    1,\n    2,\n    3,\n    4,\n    5\n  ],\n  [6]\n]"
+    );
+}
+
+#[test]
+fn tagged_enums() -> Result<(), JsonParseError> {
+    enum Shape {
+        Circle { radius: u32 },
+        Line(Vec<u32>),
+        Empty,
+    }
+
+    impl DisplayJson for Shape {
+        fn fmt(&self, f: &mut nojson::JsonFormatter<'_, '_>) -> std::fmt::Result {
+            match self {
+                Shape::Circle { radius } => {
+                    f.tagged_object("type", "circle", |f| f.member("radius", radius))
+                }
+                Shape::Line(points) => f.adjacently_tagged("type", "line", "data", points),
+                Shape::Empty => f.externally_tagged("empty", nojson::object(|_| Ok(()))),
+            }
+        }
+    }
+
+    let shapes = [
+        Shape::Circle { radius: 3 },
+        Shape::Line(vec![1, 2]),
+        Shape::Empty,
+    ];
+    let text = Json(&shapes).to_string();
+    assert_eq!(
+        text,
+        r#"[{"type":"circle","radius":3},{"type":"line","data":[1,2]},{"empty":{}}]"#
+    );
+
+    // Round-trip through the decoding helpers.
+    let json = nojson::RawJson::parse(&text)?;
+    let values: Vec<_> = json.value().to_array()?.collect();
+    assert_eq!(values[0].to_tagged("type")?.0, "circle");
+    assert_eq!(values[1].to_adjacently_tagged("type", "data")?.0, "line");
+    assert_eq!(values[2].to_externally_tagged()?.0, "empty");
+    Ok(())
+}
+
+#[test]
+fn unflatten() -> Result<(), Box<dyn std::error::Error>> {
+    let text = r#"{"a": [1, [], {"x~y": null, "p/q": "s"}], "b": {}, "": {"c": 1.50}}"#;
+    let json = nojson::RawJson::parse(text)?;
+    let doc = nojson::unflatten(json.flatten())?;
+    nojson::assert_json_eq(&doc.to_string(), text);
+
+    let doc = nojson::unflatten([
+        ("a.b", 1),
+        ("a.c.0", 2),
+        ("/a/c/-", 3),
+        ("a.d", 4),
+        ("a.d.e", 5),
+        ("/a/c/x", 6),
+        ("/a/b", 7),
+    ])?;
+    assert_eq!(doc.to_string(), r#"{"a":{"b":7,"c":{"x":6},"d":{"e":5}}}"#);
+
+    let doc = nojson::unflatten([("/0", 1), ("", 2)])?;
+    assert_eq!(doc.to_string(), "2");
+
+    let doc = nojson::unflatten([("/-/-", "x"), ("/-", "y")])?;
+    assert_eq!(doc.to_string(), r#"[["x"],"y"]"#);
+
+    let doc = nojson::unflatten(std::iter::empty::<(&str, u8)>())?;
+    assert_eq!(doc.to_string(), "null");
+
+    let inner = nojson::unflatten([("a.0", 1)])?;
+    let doc = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_spacing(true);
+        f.value(&inner)
+    });
+    assert_eq!(doc.to_string(), "{\n  \"a\": [\n    1\n  ]\n}");
+
+    // Sparse array indices are rejected instead of growing the array.
+    let e = nojson::unflatten([("rows.4000000000", 1)])
+        .err()
+        .expect("sparse index");
+    assert_eq!(e.path, "rows.4000000000");
+    assert_eq!((e.index, e.len), (4000000000, 0));
+
+    let e = nojson::unflatten([("/a/0", 1), ("/a/1", 2), ("/a/3", 3)])
+        .err()
+        .expect("sparse index");
+    assert_eq!(e.path, "/a/3");
+    assert_eq!((e.index, e.len), (3, 2));
+    assert_eq!(
+        e.to_string(),
+        r#"array index 3 in path "/a/3" is beyond the end of the array (length 2)"#
+    );
+    Ok(())
+}
@@ -17,14 +17,18 @@ fn parse_empty_text() {
         "",
         JsonParseError::UnexpectedEos {
             kind: None,
-            position: 0
+            position: 0,
+            end: 0,
+            expected: "a JSON value",
         }
     );
     assert_parse_error_matches!(
         "    ",
         JsonParseError::UnexpectedEos {
             kind: None,
-            position: 4
+            position: 4,
+            end: 4,
+            expected: "a JSON value",
         }
     );
 }
@@ -41,21 +45,26 @@ fn parse_nulls() -> Result<(), JsonParseError> {
         "nuL",
         JsonParseError::UnexpectedValueChar {
             kind: Some(JsonValueKind::Null),
-            position: 2
+            position: 2,
+            end: 3,
+            expected: "`null`",
         }
     );
     assert_parse_error_matches!(
         "nul",
         JsonParseError::UnexpectedEos {
             kind: Some(JsonValueKind::Null),
-            position: 3
+            position: 3,
+            end: 3,
+            expected: "`null`",
         }
     );
     assert_parse_error_matches!(
         "nulla",
         JsonParseError::UnexpectedTrailingChar {
             kind: JsonValueKind::Null,
-            position: 4
+            position: 4,
+            end: 5
         }
     );
 
@@ -80,21 +89,26 @@ fn parse_bools() -> Result<(), JsonParseError> {
         "false true",
         JsonParseError::UnexpectedTrailingChar {
             kind: JsonValueKind::Boolean,
-            position: 6
+            position: 6,
+            end: 10
         }
     );
     assert_parse_error_matches!(
         "fale",
         JsonParseError::UnexpectedValueChar {
             kind: Some(JsonValueKind::Boolean),
-            position: 3
+            position: 3,
+            end: 4,
+            expected: "`false`",
         }
     );
     assert_parse_error_matches!(
         "tr",
         JsonParseError::UnexpectedEos {
             kind: Some(JsonValueKind::Boolean),
-            position: 2
+            position: 2,
+            end: 2,
+            expected: "`true`",
         }
     );
 
@@ -152,7 +166,9 @@ fn parse_numbers() -> Result<(), JsonParseError> {
             text,
             JsonParseError::UnexpectedValueChar {
                 kind: None,
-                position: 0
+                position: 0,
+                end: 1,
+                expected: "a JSON value",
             }
         );
     }
@@ -240,6 +256,43 @@ fn parse_strings() -> Result<(), JsonParseError> {
     Ok(())
 }
 
+#[test]
+fn parse_surrogate_pairs() -> Result<(), JsonParseError> {
+    // Characters outside the BMP are escaped as UTF-16 surrogate pairs (RFC 8259, section 7).
+    for (text, unescaped) in [
+        (r#""\ud83d\ude00""#, "\u{1f600}"),
+        (r#""\uD800\uDC00""#, "\u{10000}"),
+        (r#""a\uDBFF\uDFFFb""#, "a\u{10ffff}b"),
+        (r#""\ud83d\ude00\u00e9\ud83d\ude00""#, "\u{1f600}é\u{1f600}"),
+    ] {
+        let json = RawJson::parse(text)?;
+        assert_eq!(json.value().to_unquoted_string_str()?, unescaped);
+        assert_eq!(text.parse::<Json<String>>()?.0, unescaped);
+    }
+
+    // Lone or misordered surrogates cannot be represented in a Rust string.
+    for (text, error_position) in [
+        (r#" "\ud83d" "#, 8),
+        (r#" "\ud83dx" "#, 8),
+        (r#" "\ud83d\u0041" "#, 10),
+        (r#" "\ud83d\ud83d" "#, 10),
+        (r#" "\ude00\ud83d" "#, 4),
+    ] {
+        let e = assert_parse_error_matches!(
+            text,
+            JsonParseError::UnexpectedValueChar {
+                kind: Some(JsonValueKind::String),
+                ..
+            }
+        );
+        assert_eq!(e.position(), error_position, "{text}");
+    }
+    for text in [r#" "\ud83d\"#, r#" "\ud83d\u"#, r#" "\ud83d\ude0"#] {
+        assert_parse_error_matches!(text, JsonParseError::UnexpectedEos { .. });
+    }
+    Ok(())
+}
+
 #[test]
 fn parse_arrays() -> Result<(), JsonParseError> {
     // Arrays.
@@ -273,7 +326,9 @@ fn parse_arrays() -> Result<(), JsonParseError> {
         "]",
         JsonParseError::UnexpectedValueChar {
             kind: None,
-            position: 0
+            position: 0,
+            end: 1,
+            expected: "a JSON value",
         }
     );
 
@@ -281,7 +336,8 @@ fn parse_arrays() -> Result<(), JsonParseError> {
         "[1,2]]",
         JsonParseError::UnexpectedTrailingChar {
             kind: JsonValueKind::Array,
-            position: 5
+            position: 5,
+            end: 6
         }
     );
 
@@ -290,6 +346,8 @@ fn parse_arrays() -> Result<(), JsonParseError> {
         JsonParseError::UnexpectedValueChar {
             kind: Some(JsonValueKind::Object),
             position: 9,
+            end: 10,
+            expected: "`,` or `}`",
         }
     );
 
@@ -339,7 +397,9 @@ fn parse_objects() -> Result<(), JsonParseError> {
         "}",
         JsonParseError::UnexpectedValueChar {
             kind: None,
-            position: 0
+            position: 0,
+            end: 1,
+            expected: "a JSON value",
         }
     );
 
@@ -347,7 +407,8 @@ fn parse_objects() -> Result<(), JsonParseError> {
         r#"{"1":2}}"#,
         JsonParseError::UnexpectedTrailingChar {
             kind: JsonValueKind::Object,
-            position: 7
+            position: 7,
+            end: 8
         }
     );
 
@@ -355,7 +416,9 @@ fn parse_objects() -> Result<(), JsonParseError> {
         "[{}}]",
         JsonParseError::UnexpectedValueChar {
             kind: Some(JsonValueKind::Array),
-            position: 3
+            position: 3,
+            end: 4,
+            expected: "`,` or `]`",
         }
     );
 
@@ -395,6 +458,150 @@ fn error_context() {
     );
 }
 
+#[test]
+fn expected_tokens_in_syntax_errors() {
+    for (text, expected) in [
+        (r#"{"a" 1}"#, "`:`"),
+        (r#"{"a": 1 "b": 2}"#, "`,` or `}`"),
+        (r#"{"a": 1,}"#, "a string"),
+        ("{1: 2}", "a string or `}`"),
+        ("[1 2]", "`,` or `]`"),
+        ("[1,]", "a JSON value"),
+        ("[", "a JSON value or `]`"),
+        ("-a", "a digit"),
+        ("1.e5", "a digit"),
+        (r#""\x""#, "an escape character"),
+        ("\"a\nb\"", "`\"` or a non-control character"),
+        (r#""abc"#, "`\"`"),
+        (
+            r#""\ud800x""#,
+            "a low surrogate escape (`\\uDC00`-`\\uDFFF`)",
+        ),
+        ("nil", "`null`"),
+    ] {
+        let e = RawJson::parse(text).expect_err("syntax error");
+        assert_eq!(e.expected(), Some(expected), "{text}");
+        assert!(
+            e.to_string().ends_with(&format!("(expected {expected})")),
+            "{text}: {e}"
+        );
+    }
+
+    // Trailing commas are accepted in JSONC, so the expectations differ.
+    let e = RawJson::parse_jsonc(r#"{"a": 1, 2}"#).expect_err("syntax error");
+    assert_eq!(e.expected(), Some("a string or `}`"));
+    let e = RawJson::parse_jsonc("/* comment").expect_err("syntax error");
+    assert_eq!(e.expected(), Some("`*/`"));
+
+    let e = RawJson::parse("1 2").expect_err("trailing char");
+    assert_eq!(e.expected(), None);
+}
+
+#[test]
+fn error_codes() -> Result<(), JsonParseError> {
+    use nojson::{JsonErrorCode, JsonShape, validate};
+
+    let code = |text: &str| RawJson::parse(text).expect_err("syntax error").code();
+    assert_eq!(code("[1,"), JsonErrorCode::UnexpectedEos);
+    assert_eq!(code("[1] 2"), JsonErrorCode::UnexpectedTrailingChar);
+    assert_eq!(code("[1 2]"), JsonErrorCode::UnexpectedValueChar);
+
+    let json = RawJson::parse(
+        r#"{"n": 300, "f": 1.5, "s": "x", "a": [1, 2], "c": "ab", "color": "pink"}"#,
+    )?;
+    let v = json.value();
+    let code = |r: Result<(), JsonParseError>| r.expect_err("conversion error").code();
+    assert_eq!(
+        code(u8::try_from(v.expect_member("n")).map(|_| ())),
+        JsonErrorCode::InvalidNumber
+    );
+    assert_eq!(
+        code(
+            v.expect_member("f")
+                .as_lossless_integer::<i32>()
+                .map(|_| ())
+        ),
+        JsonErrorCode::InvalidNumber
+    );
+    assert_eq!(
+        code(u8::try_from(v.expect_member("s")).map(|_| ())),
+        JsonErrorCode::UnexpectedKind
+    );
+    assert_eq!(
+        code(v.to_member("missing")?.required().map(|_| ())),
+        JsonErrorCode::MissingMember
+    );
+    assert_eq!(
+        code(char::try_from(v.expect_member("c")).map(|_| ())),
+        JsonErrorCode::InvalidString
+    );
+    assert_eq!(
+        code(<[u8; 3]>::try_from(v.expect_member("a")).map(|_| ())),
+        JsonErrorCode::InvalidLength
+    );
+    assert_eq!(
+        code(
+            v.expect_member("color")
+                .to_enum(&["red", "blue"])
+                .map(|_| ())
+        ),
+        JsonErrorCode::UnknownVariant
+    );
+    assert_eq!(
+        code(validate::range(v.expect_member("n"), 0..=255).map(|_| ())),
+        JsonErrorCode::ConstraintViolation
+    );
+    assert_eq!(
+        code(Err(v.invalid("application-specific error"))),
+        JsonErrorCode::Custom
+    );
+
+    let shape = JsonShape::object()
+        .member("id", JsonShape::integer())
+        .deny_unknown_members();
+    let errors = shape.validate(v).expect_err("invalid shape");
+    assert_eq!(errors[0].code(), JsonErrorCode::UnknownMember);
+    assert_eq!(
+        errors.last().map(|e| e.code()),
+        Some(JsonErrorCode::MissingMember)
+    );
+
+    assert_eq!(JsonErrorCode::MissingMember.as_str(), "missing-member");
+    assert_eq!(JsonErrorCode::UnexpectedEos.to_string(), "unexpected-eos");
+
+    Ok(())
+}
+
+#[test]
+fn error_spans() -> Result<(), JsonParseError> {
+    // Syntax errors cover the offending character (which may be multi-byte).
+    let e = assert_parse_error_matches!("[1, é]", JsonParseError::UnexpectedValueChar { .. });
+    assert_eq!(e.span(), 4..6);
+    assert_eq!(e.position(), 4);
+
+    // Trailing garbage is covered up to the last non-whitespace character.
+    let text = "{} extra stuff \n";
+    let e = assert_parse_error_matches!(text, JsonParseError::UnexpectedTrailingChar { .. });
+    assert_eq!(&text[e.span()], "extra stuff");
+
+    // An unexpected end is an empty range at the end of the text.
+    let e = assert_parse_error_matches!("[1, 2", JsonParseError::UnexpectedEos { .. });
+    assert_eq!(e.span(), 5..5);
+
+    // Conversion errors cover the whole value.
+    let text = r#"{"items": [1, -2, 3]}"#;
+    let json = RawJson::parse(text)?;
+    let e = Vec::<u8>::try_from(json.value().expect_member("items")).expect_err("negative");
+    assert_eq!(&text[e.span()], "-2");
+    let e = json
+        .value()
+        .expect_member("items")
+        .invalid("too many items");
+    assert_eq!(&text[e.span()], "[1, -2, 3]");
+
+    Ok(())
+}
+
 #[test]
 fn to_member_required() -> Result<(), JsonParseError> {
     struct Person {
@@ -453,6 +660,7 @@ fn required_member_missing() -> Result<(), JsonParseError> {
         JsonParseError::InvalidValue {
             kind: JsonValueKind::Object,
             position: 0,
+            end: 16,
             ..
         }
     ));
@@ -476,6 +684,7 @@ fn member_access_requires_object() -> Result<(), JsonParseError> {
         JsonParseError::InvalidValue {
             kind: JsonValueKind::Null,
             position: 0,
+            end: 4,
             ..
         }
     ));
@@ -549,6 +758,7 @@ fn to_path_member_empty_path_error() -> Result<(), JsonParseError> {
         JsonParseError::InvalidValue {
             kind: JsonValueKind::Object,
             position: 0,
+            end: 7,
             ..
         }
     ));
@@ -616,6 +826,7 @@ fn to_path_member_root_not_object_error() -> Result<(), JsonParseError> {
         JsonParseError::InvalidValue {
             kind: JsonValueKind::Null,
             position: 0,
+            end: 4,
             ..
         }
     ));
@@ -665,6 +876,130 @@ fn get_value_by_position() {
     assert_eq!(value.position(), 11);
 }
 
+#[test]
+fn parse_owned_via_from_str() -> Result<(), JsonParseError> {
+    fn parse_all<T: std::str::FromStr>(texts: &[&str]) -> Result<Vec<T>, T::Err> {
+        texts.iter().map(|text| text.parse()).collect()
+    }
+
+    let docs: Vec<nojson::RawJsonOwned> = parse_all(&["1", r#"{"a": [true]}"#])?;
+    assert_eq!(docs[0].value().as_raw_str(), "1");
+    assert_eq!(docs[1].text(), r#"{"a": [true]}"#);
+    assert!(parse_all::<nojson::RawJsonOwned>(&["[1,"]).is_err());
+    Ok(())
+}
+
+#[test]
+fn decode_documents() -> Result<(), JsonParseError> {
+    fn first_name<'text>(json: &RawJson<'text>) -> Result<&'text str, JsonParseError> {
+        let names = json.decode::<Vec<&str>>()?;
+        Ok(names[0])
+    }
+
+    let text = r#"["foo", "bar"]"#;
+    let json = RawJson::parse(text)?;
+    assert_eq!(first_name(&json)?, "foo");
+    assert!(json.decode::<u32>().is_err());
+
+    let owned = json.into_owned();
+    assert_eq!(owned.decode::<Vec<String>>()?, ["foo", "bar"]);
+    Ok(())
+}
+
+#[test]
+fn key_case_conversion() -> Result<(), JsonParseError> {
+    use nojson::JsonKeyCase;
+
+    for (name, camel, pascal, snake, kebab) in [
+        ("user_id", "userId", "UserId", "user_id", "user-id"),
+        ("userId", "userId", "UserId", "user_id", "user-id"),
+        ("UserID", "userId", "UserId", "user_id", "user-id"),
+        (
+            "ipv4_addr",
+            "ipv4Addr",
+            "Ipv4Addr",
+            "ipv4_addr",
+            "ipv4-addr",
+        ),
+        ("__a--b__", "aB", "AB", "a_b", "a-b"),
+        ("", "", "", "", ""),
+    ] {
+        assert_eq!(JsonKeyCase::CamelCase.convert(name), camel, "{name}");
+        assert_eq!(JsonKeyCase::PascalCase.convert(name), pascal, "{name}");
+        assert_eq!(JsonKeyCase::SnakeCase.convert(name), snake, "{name}");
+        assert_eq!(JsonKeyCase::KebabCase.convert(name), kebab, "{name}");
+    }
+
+    let json = RawJson::parse(r#"{"retryCount": 3}"#)?;
+    let name = JsonKeyCase::CamelCase.convert("retry_count");
+    let count: u8 = json.value().to_member(&name)?.required()?.try_into()?;
+    assert_eq!(count, 3);
+
+    // Members can be looked up by their Rust names directly.
+    let json = RawJson::parse(r#"{"retryCount": 3, "Max\u0044elay": 10, "user-id": "x"}"#)?;
+    let obj = json.value();
+    let count: u8 = obj
+        .to_member_as("retry_count", JsonKeyCase::CamelCase)?
+        .required()?
+        .try_into()?;
+    assert_eq!(count, 3);
+    let delay: u8 = obj
+        .to_member_as("max_delay", JsonKeyCase::PascalCase)?
+        .required()?
+        .try_into()?;
+    assert_eq!(delay, 10);
+    let id: Option<String> = obj
+        .to_member_as("user_id", JsonKeyCase::KebabCase)?
+        .try_into()?;
+    assert_eq!(id.as_deref(), Some("x"));
+    assert!(
+        obj.to_member_as("retry", JsonKeyCase::CamelCase)?
+            .optional()
+            .is_none()
+    );
+
+    let e = obj
+        .to_member_as("user_name", JsonKeyCase::CamelCase)?
+        .required()
+        .unwrap_err();
+    assert_eq!(e.code(), nojson::JsonErrorCode::MissingMember);
+    assert!(e.to_string().contains("'userName'"), "{e}");
+    Ok(())
+}
+
+#[test]
+fn duplicate_members() -> Result<(), JsonParseError> {
+    let json = RawJson::parse(r#"{"a": 1, "b": 2, "\u0061": 3}"#)?;
+    let obj = json.value();
+    assert_eq!(obj.to_member("a")?.required()?.as_raw_str(), "1");
+    assert_eq!(obj.to_member_last("a")?.required()?.as_raw_str(), "3");
+    assert_eq!(obj.to_member_last("b")?.required()?.as_raw_str(), "2");
+    assert!(obj.to_member_last("c")?.optional().is_none());
+    assert!(obj.to_member_last("c")?.required().is_err());
+    assert!(RawJson::parse("[]")?.value().to_member_last("a").is_err());
+
+    use nojson::DuplicateMemberPolicy;
+    let unique = |policy| -> Result<Vec<_>, JsonParseError> {
+        Ok(obj
+            .to_object_unique(policy)?
+            .map(|(_, v)| v.as_raw_str())
+            .collect())
+    };
+    assert_eq!(unique(DuplicateMemberPolicy::First)?, ["1", "2"]);
+    assert_eq!(unique(DuplicateMemberPolicy::Last)?, ["2", "3"]);
+    let e = unique(DuplicateMemberPolicy::Reject).expect_err("duplicate");
+    assert_eq!(e.code(), nojson::JsonErrorCode::DuplicateMember);
+    assert_eq!(e.position(), 17);
+
+    let json = RawJson::parse(r#"{"x": 1, "y": 2}"#)?;
+    let members = json
+        .value()
+        .to_object_unique(DuplicateMemberPolicy::Reject)?
+        .count();
+    assert_eq!(members, 2);
+    Ok(())
+}
+
 #[test]
 fn value_parent() {
     let text = r#"{"1":1,"2":[null],"3":3}"#;
@@ -679,3 +1014,1361 @@ fn value_parent() {
     assert_eq!(grand_parent.as_raw_str(), text);
     assert_eq!(grand_parent.parent(), None);
 }
+
+#[test]
+fn value_depth() {
+    let text = r#"{"1":1,"2":[null,[true]],"3":3}"#;
+    let json = RawJson::parse(text).expect("ok");
+    assert_eq!(json.value().depth(), 0);
+
+    for (position, expected) in [(1, 1), (5, 1), (11, 1), (12, 2), (18, 3), (26, 1)] {
+        let value = json.get_value_by_position(position).expect("some");
+        assert_eq!(value.depth(), expected, "{}", value.as_raw_str());
+
+        let mut parents = 0;
+        let mut current = value;
+        while let Some(parent) = current.parent() {
+            parents += 1;
+            current = parent;
+        }
+        assert_eq!(value.depth(), parents);
+    }
+}
+
+#[test]
+fn value_index_in_parent_and_member_name() -> Result<(), JsonParseError> {
+    let text = r#"{"a":[10,20],"b":{"c":null}}"#;
+    let json = RawJson::parse(text)?;
+    let root = json.value();
+    assert_eq!(root.index_in_parent(), None);
+    assert_eq!(root.member_name(), None);
+
+    let a = root.to_member("a")?.required()?;
+    assert_eq!(a.index_in_parent(), None);
+    assert_eq!(a.member_name().map(|n| n.as_raw_str()), Some(r#""a""#));
+
+    let second = a.to_array()?.nth(1).expect("some");
+    assert_eq!(second.index_in_parent(), Some(1));
+    assert_eq!(second.member_name(), None);
+
+    let c = root
+        .to_member("b")?
+        .required()?
+        .to_member("c")?
+        .required()?;
+    assert_eq!(c.member_name().map(|n| n.as_raw_str()), Some(r#""c""#));
+
+    let name = c.member_name().expect("some");
+    assert_eq!(name.member_name(), None);
+    assert_eq!(name.index_in_parent(), None);
+    Ok(())
+}
+
+#[test]
+fn value_debug() -> Result<(), JsonParseError> {
+    let text = format!(r#"{{"users": [{{"bio": "{}"}}]}}"#, "x".repeat(50));
+    let json = RawJson::parse(&text)?;
+    let users = json.value().to_member("users")?.required()?;
+    assert_eq!(
+        format!("{users:?}"),
+        format!(
+            r#"RawJsonValue {{ kind: Array, span: 10..{}, path: "$.users", text: "[{{\"bio\": \"{}…" }}"#,
+            text.len() - 1,
+            "x".repeat(30)
+        )
+    );
+
+    let bio = users
+        .to_array()?
+        .next()
+        .expect("exists")
+        .to_member("bio")?
+        .required()?;
+    assert_eq!(
+        format!("{bio:?}"),
+        format!(
+            r#"RawJsonValue {{ kind: String, span: 19..71, path: "$.users[0].bio", text: "\"{}…" }}"#,
+            "x".repeat(39)
+        )
+    );
+
+    let json = RawJson::parse("true")?;
+    assert_eq!(
+        format!("{:?}", json.value()),
+        r#"RawJsonValue { kind: Boolean, span: 0..4, path: "$", text: "true" }"#
+    );
+    Ok(())
+}
+
+#[test]
+fn value_literal_eq() -> Result<(), JsonParseError> {
+    let json = RawJson::parse(
+        r#"["on", "o\u006e", "", 3, -0, 1.5, 1e2, 99999999999999999999, true, false, null, "3", "true"]"#,
+    )?;
+    let v = json.value().to_array()?.collect::<Vec<_>>();
+
+    assert!(v[0] == "on" && v[1] == "on" && v[2] == "");
+    assert!(v[0] != "o" && v[1] != "o\\u006e" && v[2] != "on");
+    assert!(v[3] == 3 && v[3] == 3.0 && v[3] != 4 && v[3] != "3");
+    assert!(v[4] == 0 && v[4] == 0.0);
+    assert!(v[5] == 1.5 && v[5] != 1);
+    assert!(v[6] == 100.0 && v[6] != 100);
+    assert!(v[7] != -1 && v[7] == 1e20);
+    assert!(v[8] == true && v[8] != false && v[9] == false && v[8] != "true");
+    assert!(v[10] != false && v[10] != 0 && v[10] != "null");
+    assert!(v[11] != 3 && v[11] != 3.0 && v[12] != true);
+    Ok(())
+}
+
+#[test]
+fn flatten() -> Result<(), JsonParseError> {
+    let text = r#"{"a": [1, [], {"x~y": null}], "b/c": {}, "": "e", "a": true}"#;
+    let json = RawJson::parse(text)?;
+    let leaves = |json: &RawJson<'_>| {
+        json.flatten()
+            .map(|(pointer, value)| (pointer, value.as_raw_str().to_owned()))
+            .collect::<Vec<_>>()
+    };
+    let expected = [
+        ("/a/0", "1"),
+        ("/a/1", "[]"),
+        ("/a/2/x~0y", "null"),
+        ("/b~1c", "{}"),
+        ("/", r#""e""#),
+        ("/a", "true"),
+    ]
+    .map(|(p, v)| (p.to_owned(), v.to_owned()));
+    assert_eq!(leaves(&json), expected);
+
+    // Every pointer except the duplicated one refers back to its value.
+    for (pointer, value) in json.flatten().filter(|(p, _)| !p.starts_with("/a")) {
+        let target = RawJson::parse_pointer(text, &pointer)?.expect("exists");
+        assert_eq!(target.text(), value.as_raw_str());
+    }
+
+    let owned = json.into_owned();
+    assert_eq!(owned.flatten().count(), expected.len());
+
+    let json = RawJson::parse(" 42 ")?;
+    assert_eq!(leaves(&json), [(String::new(), "42".to_owned())]);
+    Ok(())
+}
+
+#[test]
+fn fill_template() -> Result<(), JsonParseError> {
+    let template = RawJson::parse(
+        r#"{"a": "{{x}}", "{{x}}": ["{{y}}", 1, null, {"b": "{{\u0079}}"}], "c": "{{x", "d": "{{z}}"}"#,
+    )?;
+    let lookup = |name: &str| -> Option<&dyn nojson::DisplayJson> {
+        match name {
+            "x" => Some(&"<\"x\">"),
+            "y" => Some(&[1.5]),
+            _ => None,
+        }
+    };
+
+    let output = template.value().fill_template(lookup);
+    assert_eq!(
+        output.to_string(),
+        r#"{"a":"<\"x\">","{{x}}":[[1.5],1,null,{"b":[1.5]}],"c":"{{x","d":"{{z}}"}"#
+    );
+    let text = output.to_string();
+    let reparsed = RawJson::parse(&text)?;
+    assert_eq!(reparsed.value().to_member("a")?.required()?, "<\"x\">");
+
+    let template = RawJson::parse(r#"["{{y}}"]"#)?;
+    let output = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_spacing(true);
+        f.value(template.value().fill_template(lookup))
+    });
+    assert_eq!(output.to_string(), "[\n  [\n    1.5\n  ]\n]");
+    Ok(())
+}
+
+#[test]
+fn typed_accessors() -> Result<(), JsonParseError> {
+    let text = r#"[true, -7, 18446744073709551615, 2.5e1, "caf\u00e9", null]"#;
+    let json = RawJson::parse(text)?;
+    let v = json.value().to_array()?.collect::<Vec<_>>();
+
+    assert!(v[0].as_bool()?);
+    assert_eq!(v[1].as_i64()?, -7);
+    assert_eq!(v[2].as_u64()?, u64::MAX);
+    assert_eq!(v[3].as_f64()?, 25.0);
+    assert_eq!(v[1].as_f64()?, -7.0);
+    assert_eq!(v[4].as_str()?, "café");
+
+    assert!(v[1].as_u64().is_err());
+    assert!(v[2].as_i64().is_err());
+    assert!(v[3].as_i64().is_err());
+    for value in [v[0], v[4], v[5]] {
+        assert!(value.as_i64().is_err() && value.as_f64().is_err());
+    }
+    let e = v[5].as_str().expect_err("null");
+    assert_eq!(e.position(), text.find("null").unwrap());
+    let e = v[5].as_bool().expect_err("null");
+    assert_eq!(e.position(), text.find("null").unwrap());
+
+    v[5].as_null()?;
+    for value in &v[..5] {
+        let e = value.as_null().expect_err("not null");
+        assert_eq!(e.position(), value.position());
+        assert_eq!(e.code(), nojson::JsonErrorCode::UnexpectedKind);
+    }
+    Ok(())
+}
+
+#[test]
+fn expect_kind() -> Result<(), JsonParseError> {
+    let text = r#"{"a": [1, 2.5], "b": "x"}"#;
+    let json = RawJson::parse(text)?;
+    let a = json.value().to_member("a")?.required()?;
+    let b = json.value().to_member("b")?.required()?;
+
+    assert_eq!(a.expect_kind(&[JsonValueKind::Array])?, a);
+    for element in a.to_array()? {
+        element.expect_kind(&[JsonValueKind::Integer, JsonValueKind::Float])?;
+    }
+
+    let e = b.expect_kind(&[JsonValueKind::Array]).expect_err("string");
+    assert_eq!(e.position(), text.find(r#""x""#).unwrap());
+    assert_eq!(e.code(), nojson::JsonErrorCode::UnexpectedKind);
+    assert!(e.to_string().contains("expected Array, but found String"));
+
+    // The message is the same as the one from the built-in conversions.
+    let builtin = b.to_array().map(|_| ()).expect_err("string");
+    assert_eq!(builtin.to_string(), e.to_string());
+
+    assert!(b.expect_kind(&[]).is_err());
+    Ok(())
+}
+
+#[test]
+fn value_children() -> Result<(), JsonParseError> {
+    let json = RawJson::parse(r#"{"a":[1,{"b":2}],"c":"d"}"#)?;
+    let root = json.value();
+    let children = root
+        .children()
+        .map(|(name, v)| (name.map(|n| n.as_raw_str()), v.as_raw_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        children,
+        [
+            (Some(r#""a""#), r#"[1,{"b":2}]"#),
+            (Some(r#""c""#), r#""d""#)
+        ]
+    );
+
+    let a = root.first_child().expect("some");
+    let elements = a
+        .children()
+        .map(|(name, v)| (name, v.as_raw_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(elements, [(None, "1"), (None, r#"{"b":2}"#)]);
+    assert_eq!(a.last_child().map(|v| v.as_raw_str()), Some(r#"{"b":2}"#));
+    assert_eq!(root.last_child().map(|v| v.as_raw_str()), Some(r#""d""#));
+
+    for text in ["[]", "{}", "1", "null", r#""s""#] {
+        let json = RawJson::parse(text)?;
+        assert_eq!(json.value().children().count(), 0, "{text}");
+        assert_eq!(json.value().first_child(), None, "{text}");
+        assert_eq!(json.value().last_child(), None, "{text}");
+    }
+    Ok(())
+}
+
+#[test]
+fn object_index() -> Result<(), JsonParseError> {
+    let json = RawJson::parse(r#"{"a":1,"b\u0031":2,"a":3}"#)?;
+    let index = json.value().to_object_index()?;
+    assert_eq!(index.len(), 2);
+    assert!(!index.is_empty());
+    assert_eq!(index.object(), json.value());
+
+    for name in ["a", "b1", "c"] {
+        assert_eq!(
+            index.get(name).optional(),
+            json.value().to_member(name)?.optional(),
+            "{name}"
+        );
+    }
+    let a: i32 = index.get("a").required()?.try_into()?;
+    assert_eq!(a, 1);
+
+    let e = index.get("c").required().expect_err("missing");
+    assert_eq!(e.code(), nojson::JsonErrorCode::MissingMember);
+
+    assert!(RawJson::parse("{}")?.value().to_object_index()?.is_empty());
+    assert!(RawJson::parse("[]")?.value().to_object_index().is_err());
+    Ok(())
+}
+
+#[test]
+fn memory_usage() -> Result<(), JsonParseError> {
+    let small = RawJson::parse("[1]")?;
+    let large = RawJson::parse("[1,2,3,4,5,6,7,8,9]")?;
+    assert!(small.memory_usage() >= small.text().len());
+    assert!(large.memory_usage() > small.memory_usage());
+
+    let owned = large.clone().into_owned();
+    assert!(owned.memory_usage() >= owned.text().len());
+
+    // Scalar documents are indexed without any heap allocation.
+    for text in ["123", r#""foo""#, "null"] {
+        assert_eq!(RawJson::parse(text)?.memory_usage(), text.len());
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_pointer() -> Result<(), JsonParseError> {
+    let text = r#" {"a": [10, {"b/c": true, "d~e": null, "\u0066": "g"}], "": 0, "a": 1} "#;
+    let target = |pointer| -> Result<Option<String>, JsonParseError> {
+        Ok(RawJson::parse_pointer(text, pointer)?.map(|json| json.text().to_owned()))
+    };
+
+    assert_eq!(target("")?.as_deref(), Some(text.trim()));
+    assert_eq!(target("/a/0")?.as_deref(), Some("10"));
+    assert_eq!(target("/a/1/b~1c")?.as_deref(), Some("true"));
+    assert_eq!(target("/a/1/d~0e")?.as_deref(), Some("null"));
+    assert_eq!(target("/a/1/f")?.as_deref(), Some(r#""g""#));
+    assert_eq!(target("/")?.as_deref(), Some("0"));
+
+    for pointer in [
+        "a", "/b", "/a/2", "/a/01", "/a/+1", "/a/-", "/a/0/x", "/a/1/b/c",
+    ] {
+        assert_eq!(target(pointer)?, None, "{pointer}");
+    }
+
+    let json = RawJson::parse_pointer(text, "/a/1")?.expect("some");
+    assert_eq!(
+        json.value().to_member("f")?.required()?.as_raw_str(),
+        r#""g""#
+    );
+
+    // Syntax errors outside the target are still reported.
+    assert!(RawJson::parse_pointer(r#"{"a": 1, "b": [}"#, "/a").is_err());
+    Ok(())
+}
+
+#[test]
+fn redact() -> Result<(), JsonParseError> {
+    let text = r#" {"a": [10, {"b/c": true, "d~e": null, "\u0066": "g"}], "": 0, "a": 1} "#;
+    let json = RawJson::parse(text)?;
+
+    assert_eq!(json.redact([""]), r#" "***" "#);
+    assert_eq!(
+        json.redact(["/a/0", "/a/1/b~1c", "/a/1/f", "/"]),
+        r#" {"a": ["***", {"b/c": "***", "d~e": null, "\u0066": "***"}], "": "***", "a": 1} "#
+    );
+    // Nested and repeated pointers are merged into the outermost one.
+    assert_eq!(
+        json.redact(vec![
+            "/a/1/d~0e".to_owned(),
+            "/a".to_owned(),
+            "/a/0".to_owned(),
+            "/a".to_owned()
+        ]),
+        r#" {"a": "***", "": 0, "a": 1} "#
+    );
+    for pointer in ["a", "/b", "/a/2", "/a/01", "/a/-", "/a/0/x"] {
+        assert_eq!(json.redact([pointer]), text, "{pointer}");
+    }
+    assert_eq!(json.redact::<[&str; 0]>([]), text);
+
+    let (json, _) = RawJson::parse_jsonc("{\n  // comment\n  \"ssn\": \"123\" /* x */\n}")?;
+    assert_eq!(
+        json.redact(["/ssn"]),
+        "{\n  // comment\n  \"ssn\": \"***\" /* x */\n}"
+    );
+
+    let redacted = json.redact(["/ssn"]);
+    assert_eq!(json.into_owned().redact(["/ssn"]), redacted);
+    Ok(())
+}
+
+#[test]
+fn json_arena() -> Result<(), JsonParseError> {
+    let mut arena = nojson::JsonArena::new();
+    assert!(arena.is_empty());
+
+    let texts = ["1", r#"{"a": [true, null]}"#, r#""s""#, "[[], {}]"];
+    for (i, text) in texts.into_iter().enumerate() {
+        assert_eq!(arena.parse(text)?, i);
+    }
+    assert!(arena.parse("[1,").is_err());
+    assert!(arena.parse("{} 1").is_err());
+    assert_eq!(arena.len(), texts.len());
+    assert!(arena.get(texts.len()).is_none());
+
+    for (value, text) in arena.iter().zip(texts) {
+        assert_eq!(value.as_raw_str(), text);
+        assert_eq!(value.extract().text(), RawJson::parse(text)?.text());
+    }
+
+    let a = arena.get(1).expect("some").to_member("a")?.required()?;
+    assert_eq!(a.position(), 6);
+    assert_eq!(a.to_array()?.count(), 2);
+    assert_eq!(a.parent(), arena.get(1));
+    assert_eq!(arena.get(3).expect("some").to_array()?.count(), 2);
+
+    let usage = arena.memory_usage();
+    arena.clear();
+    assert!(arena.is_empty());
+    assert_eq!(arena.memory_usage(), usage);
+    assert_eq!(arena.parse("[0]")?, 0);
+    Ok(())
+}
+
+#[test]
+fn shared_json() -> Result<(), JsonParseError> {
+    let json: nojson::RawJsonShared = r#"{"a": [1, 2], "b": "c"}"#.parse()?;
+    let cloned = json.clone();
+    assert_eq!(cloned, json);
+    assert_eq!(cloned.text(), json.text());
+    assert_eq!(json.to_string(), r#"{"a":[1,2],"b":"c"}"#);
+
+    let a = json.value().to_member("a")?.required()?;
+    let shared = json.shared_value(a.index()).expect("some");
+    drop(json);
+
+    let sum = std::thread::spawn(move || -> Result<u32, JsonParseError> {
+        let values: Vec<u32> = shared.value().try_into()?;
+        Ok(values.into_iter().sum())
+    })
+    .join()
+    .expect("no panic")?;
+    assert_eq!(sum, 3);
+
+    let root = cloned.shared_value(0).expect("some");
+    assert_eq!(root.document(), &cloned);
+    assert_eq!(root.value(), cloned.value());
+    assert!(cloned.shared_value(100).is_none());
+    Ok(())
+}
+
+#[test]
+fn as_lossless_integer() -> Result<(), JsonParseError> {
+    for (text, expected) in [
+        ("1", 1),
+        ("-1.0", -1),
+        ("1e3", 1000),
+        ("12.50e1", 125),
+        ("0.0", 0),
+        ("-0.0e-5", 0),
+        ("1000e-3", 1),
+    ] {
+        let json = RawJson::parse(text)?;
+        assert_eq!(
+            json.value().as_lossless_integer::<i32>()?,
+            expected,
+            "{text}"
+        );
+    }
+
+    for text in [
+        "1.5",
+        "1e-1",
+        "1e10",
+        "1e99999999999999999999",
+        "-1e1",
+        "\"1\"",
+    ] {
+        let json = RawJson::parse(text)?;
+        assert!(json.value().as_lossless_integer::<u32>().is_err(), "{text}");
+    }
+
+    let json = RawJson::parse("[0, 2.5]")?;
+    let e = json
+        .value()
+        .to_array()?
+        .map(|v| v.as_lossless_integer::<u8>())
+        .collect::<Result<Vec<_>, _>>()
+        .expect_err("error");
+    assert_eq!(e.position(), 4);
+    assert!(
+        e.to_string().contains("fractional part"),
+        "unexpected error: {e}"
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_tuples() -> Result<(), JsonParseError> {
+    let json = RawJson::parse(r#"[1, "foo", [true, null]]"#)?;
+    let (a, b, (c, d)): (u32, String, (bool, Option<u8>)) = json.value().try_into()?;
+    assert_eq!((a, b.as_str(), c, d), (1, "foo", true, None));
+
+    let value: (u8,) = RawJson::parse("[7]")?.value().try_into()?;
+    assert_eq!(value, (7,));
+
+    let json = RawJson::parse("[1, 2, 3, 4, 5, 6, 7, 8]")?;
+    let value: (u8, u8, u8, u8, u8, u8, u8, u8) = json.value().try_into()?;
+    assert_eq!(value, (1, 2, 3, 4, 5, 6, 7, 8));
+
+    // Length mismatch.
+    let json = RawJson::parse("[1, 2, 3]")?;
+    let e = <(u8, u8)>::try_from(json.value()).expect_err("error");
+    assert!(
+        e.to_string().contains("expected an array with 2 elements"),
+        "unexpected error: {e}"
+    );
+
+    // Element type mismatch.
+    let json = RawJson::parse(r#"[1, 2]"#)?;
+    let e = <(u8, String)>::try_from(json.value()).expect_err("error");
+    assert_eq!(e.position(), 4);
+    Ok(())
+}
+
+#[test]
+fn parse_ranges_and_bounds() -> Result<(), JsonParseError> {
+    use std::ops::{Bound, Range, RangeInclusive};
+
+    let range = 1..5;
+    let text = Json(&range).to_string();
+    assert_eq!(text, r#"{"start":1,"end":5}"#);
+    assert_eq!(text.parse::<Json<Range<u8>>>()?.0, range);
+    assert_eq!(text.parse::<Json<RangeInclusive<u8>>>()?.0, 1..=5);
+    assert_eq!(Json(1..=5).to_string(), text);
+
+    for (bound, text) in [
+        (Bound::Included(1), r#"{"Included":1}"#),
+        (Bound::Excluded(2), r#"{"Excluded":2}"#),
+        (Bound::Unbounded, r#""Unbounded""#),
+    ] {
+        assert_eq!(Json(bound).to_string(), text);
+        assert_eq!(text.parse::<Json<Bound<i32>>>()?.0, bound);
+    }
+
+    assert!(r#"{"start":1}"#.parse::<Json<Range<u8>>>().is_err());
+    for text in [
+        r#""Bounded""#,
+        r#"{"Included":1,"Excluded":2}"#,
+        r#"{"Inclusive":1}"#,
+        "null",
+    ] {
+        assert!(text.parse::<Json<Bound<u8>>>().is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_atomics() -> Result<(), JsonParseError> {
+    use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU32, AtomicUsize, Ordering};
+
+    let text = "[true,-5,7,9]";
+    let (flag, signed, unsigned, size): (AtomicBool, AtomicI64, AtomicU32, AtomicUsize) =
+        RawJson::parse(text)?.value().try_into()?;
+    assert!(flag.load(Ordering::Relaxed));
+    assert_eq!(signed.load(Ordering::Relaxed), -5);
+    assert_eq!(unsigned.load(Ordering::Relaxed), 7);
+    assert_eq!(size.load(Ordering::Relaxed), 9);
+
+    unsigned.fetch_add(1, Ordering::Relaxed);
+    assert_eq!(
+        nojson::json(|f| f.array(|f| {
+            f.element(&flag)?;
+            f.element(&signed)?;
+            f.element(&unsigned)?;
+            f.element(&size)
+        }))
+        .to_string(),
+        "[true,-5,8,9]"
+    );
+
+    assert!(AtomicU32::try_from(RawJson::parse("-1")?.value()).is_err());
+    Ok(())
+}
+
+#[test]
+fn parse_shared_strings() -> Result<(), JsonParseError> {
+    use std::{rc::Rc, sync::Arc};
+
+    let text = r#"["foo","b\"ar",""]"#;
+    let json = RawJson::parse(text)?;
+    let boxed: [Box<str>; 3] = json.value().try_into()?;
+    let rc: [Rc<str>; 3] = json.value().try_into()?;
+    let arc: [Arc<str>; 3] = json.value().try_into()?;
+    for values in [
+        boxed.each_ref().map(|s| &**s),
+        rc.each_ref().map(|s| &**s),
+        arc.each_ref().map(|s| &**s),
+    ] {
+        assert_eq!(values, ["foo", "b\"ar", ""]);
+    }
+
+    assert!(Arc::<str>::try_from(RawJson::parse("1")?.value()).is_err());
+    Ok(())
+}
+
+#[test]
+fn parse_boxed_and_cow_slices() -> Result<(), JsonParseError> {
+    let text = "[1,2,3]";
+
+    let boxed: Json<Box<[u8]>> = text.parse()?;
+    assert_eq!(&*boxed.0, [1, 2, 3]);
+    assert_eq!(Json(&boxed.0).to_string(), text);
+
+    let cow: Json<Cow<'_, [u8]>> = text.parse()?;
+    assert_eq!(&*cow.0, [1, 2, 3]);
+    assert_eq!(Json(Cow::Borrowed(&[1u8, 2, 3][..])).to_string(), text);
+
+    assert!(r#"[1,"2"]"#.parse::<Json<Box<[u8]>>>().is_err());
+    Ok(())
+}
+
+#[test]
+fn expect_member_and_element() -> Result<(), JsonParseError> {
+    let json = RawJson::parse(r#"{"users": [{"name": "Alice"}, {"name": "Bob"}]}"#)?;
+    let users = json.value().expect_member("users");
+    assert_eq!(
+        users
+            .expect_element(1)
+            .expect_member("name")
+            .to_unquoted_string_str()?,
+        "Bob"
+    );
+
+    let missing = std::panic::catch_unwind(|| users.expect_element(2));
+    assert!(missing.is_err());
+    let missing = std::panic::catch_unwind(|| json.value().expect_member("groups"));
+    assert!(missing.is_err());
+    let not_object = std::panic::catch_unwind(|| users.expect_member("name"));
+    assert!(not_object.is_err());
+    Ok(())
+}
+
+#[test]
+fn structural_equality() -> Result<(), JsonParseError> {
+    assert!(nojson::json_eq(
+        r#"{"a": [1, 2.5, "x"], "b": {"c": null}}"#,
+        r#"{"b":{"c":null},"a":[1.0,25e-1,"x"]}"#
+    )?);
+    assert!(nojson::json_eq(r#"{"a": 1, "a": 2}"#, r#"{"a": 2}"#)?);
+    assert!(!nojson::json_eq("[1, 2]", "[1, 2, 3]")?);
+    assert!(!nojson::json_eq("1", r#""1""#)?);
+    assert!(!nojson::json_eq(
+        "12345678901234567890123",
+        "12345678901234567890124"
+    )?);
+    assert!(nojson::json_eq("[", "[]").is_err());
+
+    // Numbers are compared exactly, without rounding through `f64`.
+    for (left, right) in [
+        ("1e400", "2e400"),
+        ("9007199254740993", "9007199254740992.0"),
+        ("1e-400", "0"),
+    ] {
+        assert!(!nojson::json_eq(left, right)?, "{left} vs {right}");
+        let left = RawJson::parse(left)?;
+        let right = RawJson::parse(right)?;
+        assert!(left.value().structural_cmp(right.value()).is_ne());
+    }
+    assert!(nojson::json_eq("[1e400, 100e-2]", "[10e399, 1]")?);
+
+    nojson::assert_json_eq("[true, {}]", " [ true , { } ] ");
+
+    let message = std::panic::catch_unwind(|| {
+        nojson::assert_json_eq(
+            r#"{"users": [{"name": "Alice"}], "a b": 1}"#,
+            r#"{"users": [{"name": "Bob"}, 2], "c": 3}"#,
+        )
+    })
+    .expect_err("should panic")
+    .downcast::<String>()
+    .expect("string message");
+    assert!(message.contains(r#"$.users[0].name: "Alice" != "Bob""#));
+    assert!(message.contains("$.users[1]: missing in left"));
+    assert!(message.contains(r#"$["a b"]: missing in right"#));
+    assert!(message.contains("$.c: missing in left"));
+    Ok(())
+}
+
+#[test]
+fn canonical_hash() -> Result<(), JsonParseError> {
+    use std::hash::{DefaultHasher, Hasher};
+
+    let hash = |text: &str| -> Result<u64, JsonParseError> {
+        let mut hasher = DefaultHasher::new();
+        RawJson::parse(text)?.value().canonical_hash(&mut hasher);
+        Ok(hasher.finish())
+    };
+
+    let equal_pairs = [
+        (
+            r#"{"a": [1, 2.5, "x"], "b": {"c": null}}"#,
+            r#"{"b":{"c":null},"a":[1.0,25e-1,"\u0078"]}"#,
+        ),
+        (r#"{"a": 1, "a": 2}"#, r#"{"a": 2}"#),
+        ("-0", "0.0"),
+        ("1e400", "10E+399"),
+        ("[[], {}]", " [ [ ] , { } ] "),
+    ];
+    for (left, right) in equal_pairs {
+        assert!(nojson::json_eq(left, right)?);
+        assert_eq!(hash(left)?, hash(right)?, "{left} vs {right}");
+    }
+
+    let different = [
+        "null",
+        "false",
+        "true",
+        "0",
+        "1",
+        "1e400",
+        "2e400",
+        "9007199254740993",
+        "9007199254740992",
+        "1e-400",
+        r#""1""#,
+        "[]",
+        "{}",
+        "[[]]",
+        "[[], []]",
+        "[[1], 2]",
+        "[[1, 2]]",
+        r#"{"a": 1}"#,
+        r#"{"a": 2}"#,
+        r#"{"b": 1}"#,
+        "[1, 2]",
+        "[2, 1]",
+    ];
+    let hashes = different
+        .iter()
+        .map(|text| hash(text))
+        .collect::<Result<std::collections::HashSet<_>, _>>()?;
+    assert_eq!(hashes.len(), different.len());
+    Ok(())
+}
+
+#[test]
+fn structural_cmp() -> Result<(), JsonParseError> {
+    use std::cmp::Ordering;
+
+    // In ascending order; each group contains structurally equal values.
+    let groups: &[&[&str]] = &[
+        &["null"],
+        &["false"],
+        &["true"],
+        &["-1e400"],
+        &["-12345678901234567890124"],
+        &["-12345678901234567890123", "-1.2345678901234567890123e22"],
+        &["-2"],
+        &["-1.5", "-15e-1"],
+        &["0", "-0", "0.0", "0e10", "-0.0E-5"],
+        &["0.001", "1e-3", "10E-4"],
+        &["1", "1.0", "1e0", "0.1e1", "100e-2"],
+        &["2"],
+        &["10", "1e1", "1E+1"],
+        &["9007199254740993"],
+        &["9007199254740993.5"],
+        &["1e400"],
+        &[r#""""#],
+        &[r#""a""#, r#""\u0061""#],
+        &[r#""ab""#],
+        &[r#""b""#],
+        &["[]", "[ ]"],
+        &["[1]", "[1.0]"],
+        &["[1, 2]"],
+        &["[2]"],
+        &["{}"],
+        &[r#"{"a": 1}"#, r#"{"a": 0, "a": 1}"#],
+        &[r#"{"a": 1, "b": 2}"#, r#"{"b": 2, "a": 1.0}"#],
+        &[r#"{"a": 2}"#],
+        &[r#"{"b": 0}"#],
+    ];
+    let docs = groups
+        .iter()
+        .map(|group| group.iter().map(|text| RawJson::parse(text)).collect())
+        .collect::<Result<Vec<Vec<_>>, _>>()?;
+    for (i, left_group) in docs.iter().enumerate() {
+        for (j, right_group) in docs.iter().enumerate() {
+            for left in left_group {
+                for right in right_group {
+                    assert_eq!(
+                        left.structural_cmp(right),
+                        i.cmp(&j),
+                        "{} vs {}",
+                        left.text(),
+                        right.text()
+                    );
+                    if i == j {
+                        assert!(nojson::json_eq(left.text(), right.text())?);
+                    }
+                }
+            }
+        }
+    }
+
+    let json = RawJson::parse(r#"[3, [2, 1], 1]"#)?;
+    let values = json.value().to_array()?.collect::<Vec<_>>();
+    assert_eq!(values[0].structural_cmp(values[2]), Ordering::Greater);
+    assert_eq!(
+        values[2].structural_cmp(docs[10][0].value()),
+        Ordering::Equal
+    );
+    assert_eq!(
+        json.clone().into_owned().structural_cmp(&json.into_owned()),
+        Ordering::Equal
+    );
+    Ok(())
+}
+
+#[test]
+fn validation_helpers() -> Result<(), JsonParseError> {
+    use nojson::validate;
+
+    let text = r#"{"users": [{"name": "al ice", "age": 200, "tags": []}]}"#;
+    let json = RawJson::parse(text)?;
+    let user = json.value().expect_member("users").expect_element(0);
+
+    let e = validate::range(user.expect_member("age"), 0..=150u8).expect_err("out of range");
+    assert_eq!(e.position(), text.find("200").unwrap());
+    assert!(
+        e.to_string()
+            .contains("$.users[0].age: must be at least 0 and at most 150")
+    );
+
+    let e = validate::chars(user.expect_member("name"), |c| c != ' ').expect_err("space");
+    assert_eq!(e.position(), text.find(" ice").unwrap());
+    assert!(e.to_string().contains("$.users[0].name"));
+
+    let e = validate::length(user.expect_member("tags"), 1..).expect_err("empty");
+    assert!(
+        e.to_string()
+            .contains("length must be at least 1 elements, but got 0")
+    );
+    assert!(validate::length(user.expect_member("age"), 1..).is_err());
+
+    let name = validate::starts_with(user.expect_member("name"), "al")?;
+    let name = validate::ends_with(name, "ice")?;
+    let name = validate::one_of(name, &["al ice", "bob"])?;
+    assert_eq!(String::try_from(name)?, "al ice");
+    assert!(validate::one_of(name, &["bob"]).is_err());
+    Ok(())
+}
+
+#[test]
+fn parse_string_enums() -> Result<(), JsonParseError> {
+    let variants = ["debug", "info", "warn", "error"];
+    let json = RawJson::parse(r#"["debug", "error", "info", "fatal", 1]"#)?;
+    let values: Vec<_> = json.value().to_array()?.collect();
+    assert_eq!(values[0].to_enum(&variants)?, 0);
+    assert_eq!(values[1].to_enum(&variants)?, 3);
+    assert_eq!(values[2].to_enum(&variants)?, 1);
+
+    let e = values[3].to_enum(&variants).expect_err("unknown variant");
+    assert_eq!(e.position(), values[3].position());
+    assert!(
+        e.to_string()
+            .contains(r#"expected one of "debug", "info", "warn", "error", but got "fatal""#)
+    );
+    assert!(values[4].to_enum(&variants).is_err());
+    Ok(())
+}
+
+#[test]
+fn parse_tagged_enums() -> Result<(), JsonParseError> {
+    let json = RawJson::parse(r#"{"radius": 2, "type": "circle"}"#)?;
+    let (tag, value) = json.value().to_tagged("type")?;
+    assert_eq!(tag, "circle");
+    assert_eq!(value.expect_member("radius").as_raw_str(), "2");
+    assert!(json.value().to_tagged("kind").is_err());
+
+    let json = RawJson::parse(r#"{"type": 1}"#)?;
+    assert!(json.value().to_tagged("type").is_err());
+
+    let json = RawJson::parse(r#"{"data": [1, 2], "type": "line"}"#)?;
+    let (tag, data) = json.value().to_adjacently_tagged("type", "data")?;
+    assert_eq!(tag, "line");
+    assert_eq!(data.map(|v| v.as_raw_str()), Some("[1, 2]"));
+
+    let json = RawJson::parse(r#"{"line": [1, 2]}"#)?;
+    let (tag, data) = json.value().to_externally_tagged()?;
+    assert_eq!(tag, "line");
+    assert_eq!(data.as_raw_str(), "[1, 2]");
+    for text in ["{}", "[]", r#"{"a": 1, "b": 2}"#] {
+        let json = RawJson::parse(text)?;
+        assert!(json.value().to_externally_tagged().is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn validate_shapes() -> Result<(), JsonParseError> {
+    use nojson::JsonShape;
+
+    let shape = JsonShape::array(
+        JsonShape::object()
+            .member("id", JsonShape::integer().min(1.0))
+            .member("score", JsonShape::number().max(1.0).nullable())
+            .optional_member("tags", JsonShape::array(JsonShape::string()).max_len(2))
+            .deny_unknown_members(),
+    );
+
+    let text = r#"[{"id": 1, "score": 0.5}, {"id": 2, "score": null, "tags": ["a", "b"]}]"#;
+    assert!(shape.validate(RawJson::parse(text)?.value()).is_ok());
+
+    let text = r#"[{"id": 0, "score": 2, "extra": true}, {"score": 1, "tags": ["a", 1, "c"]}, 3]"#;
+    let json = RawJson::parse(text)?;
+    let errors = shape.validate(json.value()).expect_err("invalid");
+    let messages: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+    assert_eq!(messages.len(), 7, "{messages:#?}");
+    assert!(messages[0].contains("$[0].id: must be at least 1"));
+    assert!(messages[1].contains("$[0].score: must be at most 1"));
+    assert!(messages[2].contains("$[0].extra: unknown member"));
+    assert!(messages[3].contains("$[1].tags: length must be at most 2 elements"));
+    assert!(messages[4].contains("$[1].tags[1]: expected a string, but got Integer"));
+    assert!(messages[5].contains(r#"$[1]: missing required member "id""#));
+    assert!(messages[6].contains("$[2]: expected an object"));
+    assert_eq!(errors[2].position(), text.find(r#""extra""#).unwrap());
+    Ok(())
+}
+
+#[test]
+fn parse_missing_vs_null_members() -> Result<(), JsonParseError> {
+    let json = RawJson::parse(r#"{"a": 1, "b": null}"#)?;
+    let obj = json.value();
+    assert_eq!(
+        obj.to_member("a")?.into_option_option::<u8>()?,
+        Some(Some(1))
+    );
+    assert_eq!(obj.to_member("b")?.into_option_option::<u8>()?, Some(None));
+    assert_eq!(obj.to_member("c")?.into_option_option::<u8>()?, None);
+
+    let b: Option<Option<u8>> = obj.to_member("b")?.try_into()?;
+    assert_eq!(b, Some(None));
+
+    let json = RawJson::parse(r#"{"a": "x"}"#)?;
+    assert!(
+        json.value()
+            .to_member("a")?
+            .into_option_option::<u8>()
+            .is_err()
+    );
+    Ok(())
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn validate_json_schema() -> Result<(), JsonParseError> {
+    use nojson::JsonSchema;
+
+    let schema_text = r#"{
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer", "minimum": 1},
+            "ratio": {"type": "number", "exclusiveMaximum": 1},
+            "name": {"type": ["string", "null"], "minLength": 1, "maxLength": 4},
+            "tags": {"type": "array", "items": {"enum": ["a", "b", 3]}, "minItems": 1}
+        },
+        "required": ["id", "name"],
+        "additionalProperties": false
+    }"#;
+    let schema: JsonSchema = RawJson::parse(schema_text)?.value().try_into()?;
+
+    for text in [
+        r#"{"id": 1, "name": "abc"}"#,
+        r#"{"id": 2.0, "name": null, "ratio": 0.5, "tags": ["a", 3.0]}"#,
+    ] {
+        let json = RawJson::parse(text)?;
+        assert!(schema.validate(json.value()).is_ok(), "{text}");
+    }
+
+    let text = r#"{"id": 0.5, "ratio": 1, "name": "abcde", "tags": ["c"], "x": 1}"#;
+    let json = RawJson::parse(text)?;
+    let errors = schema.validate(json.value()).expect_err("invalid");
+    let messages: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+    assert_eq!(messages.len(), 5, "{messages:#?}");
+    assert!(messages[0].contains("$.id: expected integer, but got Float"));
+    assert!(messages[1].contains("$.ratio: must be less than 1"));
+    assert!(
+        messages[2]
+            .contains("$.name: length must be at least 1 and at most 4 characters, but got 5")
+    );
+    assert!(messages[3].contains(r#"$.tags[0]: must be one of "a", "b", 3"#));
+    assert!(messages[4].contains("$.x: no value is allowed"));
+    assert_eq!(errors[4].position(), text.find("1}").unwrap());
+
+    let json = RawJson::parse("{}")?;
+    let errors = schema.validate(json.value()).expect_err("missing members");
+    assert_eq!(errors.len(), 2);
+
+    let json = RawJson::parse(r#"{"type": "integr"}"#)?;
+    assert!(JsonSchema::try_from(json.value()).is_err());
+    Ok(())
+}
+
+#[test]
+fn parse_jsonc_document() -> Result<(), JsonParseError> {
+    use nojson::JsoncCommentKind;
+
+    let text = "// header\r\n{\n  \"a\": 1, /* multi\n  line */\n  \"b\": [2,], // tail\n}";
+    let doc = RawJson::parse_jsonc_document(text)?;
+    assert_eq!(doc.json().value().expect_member("b").as_raw_str(), "[2,]");
+    assert_eq!(doc.comment_ranges().len(), 3);
+
+    let comments: Vec<_> = doc.comments().collect();
+    let summary: Vec<_> = comments
+        .iter()
+        .map(|c| (c.kind(), c.content(), c.line().get()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (JsoncCommentKind::Line, " header", 1),
+            (JsoncCommentKind::Block, " multi\n  line ", 3),
+            (JsoncCommentKind::Line, " tail", 5),
+        ]
+    );
+    assert_eq!(&text[comments[1].range()], comments[1].text());
+    assert_eq!(doc.into_json().text(), text);
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn load_json_files() -> Result<(), Box<dyn std::error::Error>> {
+    use nojson::{JsonFileError, RawJsonOwned};
+
+    let dir = std::env::temp_dir().join(format!("nojson-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+
+    let json_path = dir.join("config.json");
+    std::fs::write(&json_path, r#"{"port": 8080}"#)?;
+    let json = RawJsonOwned::from_file(&json_path)?;
+    assert_eq!(json.value().expect_member("port").as_raw_str(), "8080");
+
+    let jsonc_path = dir.join("config.jsonc");
+    std::fs::write(&jsonc_path, "{\n  // comment\n  \"ports\": [1, 2,],\n}")?;
+    let Json(ports) = Json::<BTreeMap<String, Vec<u16>>>::from_file(&jsonc_path)?;
+    assert_eq!(ports["ports"], [1, 2]);
+
+    // JSONC syntax is rejected in `.json` files.
+    std::fs::write(&json_path, "{\n  // comment\n}")?;
+    let e = RawJsonOwned::from_file(&json_path).expect_err("comments");
+    assert!(
+        matches!(e, JsonFileError::Parse { line, column, .. } if line.get() == 2 && column.get() == 3)
+    );
+    assert_eq!(e.path(), json_path);
+    assert!(
+        e.to_string()
+            .starts_with(&format!("{}:2:3: ", json_path.display()))
+    );
+
+    // Conversion errors are reported with the file position too.
+    std::fs::write(&json_path, "[\n  1,\n  -2\n]")?;
+    let e = Json::<Vec<u8>>::from_file(&json_path).expect_err("negative");
+    assert!(matches!(e, JsonFileError::Parse { line, .. } if line.get() == 3));
+
+    let e = RawJsonOwned::from_file(dir.join("missing.json")).expect_err("missing");
+    assert!(matches!(e, JsonFileError::Io { .. }));
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn read_and_write_streams() -> Result<(), Box<dyn std::error::Error>> {
+    let Json(values) = Json::<Vec<Option<u8>>>::from_reader(&b"[1, null, 3]"[..])?;
+    assert_eq!(values, [Some(1), None, Some(3)]);
+
+    for (input, position) in [
+        (&b"[1, "[..], Some(4)),
+        (b"[256]", Some(1)),
+        (b"[\xff]", None),
+    ] {
+        let e = Json::<Vec<u8>>::from_reader(input).expect_err("invalid");
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+        let e = e
+            .into_inner()
+            .and_then(|e| e.downcast::<JsonParseError>().ok());
+        assert_eq!(e.map(|e| e.position()), position);
+    }
+
+    let mut output = Vec::new();
+    Json(&values).to_writer(&mut output)?;
+    assert_eq!(output, b"[1,null,3]");
+
+    // Formatting errors are reported as `InvalidData` instead of panicking.
+    struct Failing;
+    impl nojson::DisplayJson for Failing {
+        fn fmt(&self, f: &mut nojson::JsonFormatter<'_, '_>) -> std::fmt::Result {
+            f.value(1)?;
+            Err(std::fmt::Error)
+        }
+    }
+    let e = Json([Failing]).to_writer(Vec::new()).expect_err("failing");
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+
+    // I/O errors are returned as is.
+    let mut output = [0u8; 2];
+    let e = Json(&values)
+        .to_writer(&mut output[..])
+        .expect_err("too short");
+    assert_eq!(e.kind(), std::io::ErrorKind::WriteZero);
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn resolve_includes() -> Result<(), Box<dyn std::error::Error>> {
+    use nojson::{JsonFileError, JsonIncludeResolver};
+
+    let dir = std::env::temp_dir().join(format!("nojson-include-test-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("sub"))?;
+    std::fs::write(
+        dir.join("sub/base.json"),
+        r#"{"server": {"host": "localhost", "port": 80}, "tags": ["a"]}"#,
+    )?;
+    std::fs::write(
+        dir.join("extra.jsonc"),
+        "{\n  // comment\n  \"debug\": true,\n}",
+    )?;
+    std::fs::write(
+        dir.join("config.json"),
+        r#"{"$include": ["sub/base.json", "extra.jsonc"], "server": {"port": 8080}, "tags": ["b"]}"#,
+    )?;
+
+    let resolver = JsonIncludeResolver::new("$include");
+    let json = resolver.load(dir.join("config.json"))?;
+    assert_eq!(
+        json.text(),
+        r#"{"server":{"host":"localhost","port":8080},"tags":["b"],"debug":true}"#
+    );
+
+    // Nested includes are resolved relative to the including file.
+    std::fs::write(
+        dir.join("sub/nested.json"),
+        r#"{"inner": {"$include": "base.json"}}"#,
+    )?;
+    let json = resolver.load(dir.join("sub/nested.json"))?;
+    assert_eq!(
+        json.value()
+            .expect_member("inner")
+            .expect_member("tags")
+            .as_raw_str(),
+        r#"["a"]"#
+    );
+
+    // Non-object values are included as is, unless they have to be merged.
+    std::fs::write(dir.join("list.json"), "[1, 2]")?;
+    std::fs::write(dir.join("top.json"), r#"{"$include": "list.json"}"#)?;
+    assert_eq!(resolver.load(dir.join("top.json"))?.text(), "[1,2]");
+    std::fs::write(
+        dir.join("servers.json"),
+        r#"{"servers": {"$include": "list.json"}}"#,
+    )?;
+    assert_eq!(
+        resolver.load(dir.join("servers.json"))?.text(),
+        r#"{"servers":[1,2]}"#
+    );
+    std::fs::write(
+        dir.join("mixed.json"),
+        r#"{"servers": {"$include": "list.json", "x": 1}}"#,
+    )?;
+    let e = resolver
+        .load(dir.join("mixed.json"))
+        .expect_err("not mergeable");
+    assert!(matches!(e, JsonFileError::Parse { column, .. } if column.get() == 26));
+    assert!(e.to_string().contains("is not an object"));
+
+    // Cycles are detected.
+    std::fs::write(dir.join("a.json"), r#"{"$include": "b.json"}"#)?;
+    std::fs::write(dir.join("b.json"), r#"{"x": 1, "$include": "a.json"}"#)?;
+    let e = resolver.load(dir.join("a.json")).expect_err("cycle");
+    assert!(matches!(e, JsonFileError::Parse { column, .. } if column.get() == 22));
+    assert!(e.path().ends_with("b.json"));
+    assert!(e.to_string().contains("include cycle detected"));
+
+    let e = resolver
+        .load(dir.join("missing.json"))
+        .expect_err("missing");
+    assert!(matches!(e, JsonFileError::Io { .. }));
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn parse_extension_literals() -> Result<(), JsonParseError> {
+    use nojson::{JsonErrorCode, RawJsonOwned};
+
+    let scan = |text: &str| {
+        if text.starts_with("undefined") {
+            Some("undefined".len())
+        } else if text.starts_with("@") {
+            text.find(')').map(|i| i + 1)
+        } else if text.starts_with("é") {
+            Some(1) // Not on a character boundary.
+        } else if text.starts_with("?") {
+            Some(0)
+        } else {
+            None
+        }
+    };
+
+    let json = RawJsonOwned::parse_with_literals(r#"[undefined, @ts(1 2), null]"#, scan)?;
+    let [a, b, c] = <[RawJsonValue; 3]>::try_from(json.value())?;
+    assert!(a.is_extension() && b.is_extension() && !c.is_extension());
+    assert_eq!(a.kind(), JsonValueKind::Null);
+    assert_eq!((a.position(), a.as_raw_str()), (1, "undefined"));
+    assert_eq!((b.position(), b.as_raw_str()), (12, "@ts(1 2)"));
+    assert_eq!(c.kind(), JsonValueKind::Null);
+    assert!(a.extract().value().is_extension());
+
+    // Extension values are written as is and are rejected by conversions.
+    assert_eq!(json.value().to_string(), "[undefined,@ts(1 2),null]");
+    let e = u32::try_from(a).expect_err("extension");
+    assert_eq!(e.code(), JsonErrorCode::UnexpectedKind);
+    assert!(e.to_string().contains("but found a non-standard token"));
+    assert!(Option::<u32>::try_from(a).is_err());
+    assert!(<()>::try_from(a).is_err());
+    assert!(a.as_null().is_err());
+    assert_eq!(Option::<u32>::try_from(c)?, None);
+    assert_eq!(a.structural_cmp(c), std::cmp::Ordering::Greater);
+
+    // Standard values and object keys are not affected by the scanner.
+    assert!(RawJson::parse_with_literals(r#"{undefined: 1}"#, scan).is_err());
+    assert!(RawJson::parse_with_literals("nope", scan).is_err());
+
+    for text in ["[unknown]", "[é]", "[?]"] {
+        let e = RawJson::parse_with_literals(text, scan).expect_err("invalid literal");
+        assert!(
+            matches!(e, JsonParseError::UnexpectedValueChar { .. }),
+            "{text}: {e}"
+        );
+        assert_eq!(e.position(), 1);
+    }
+
+    // Plain parsing never produces extension values.
+    assert!(RawJson::parse("undefined").is_err());
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn minify_streams() -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Read;
+
+    // A reader that returns one byte at a time and is sometimes interrupted.
+    struct Trickle<'a>(&'a [u8], bool);
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.1 = !self.1;
+            if self.1 {
+                return Err(std::io::ErrorKind::Interrupted.into());
+            }
+            let n = self.0.len().min(buf.len()).min(1);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    let minify = |text: &str| -> std::io::Result<String> {
+        let mut output = Vec::new();
+        nojson::minify_stream(Trickle(text.as_bytes(), false), &mut output)?;
+        Ok(String::from_utf8(output).expect("valid UTF-8"))
+    };
+
+    for (text, expected) in [
+        (" null ", "null"),
+        ("-1.5e+3", "-1.5e+3"),
+        (
+            r#"[ 1, "a b", true, false, [], {} ]"#,
+            r#"[1,"a b",true,false,[],{}]"#,
+        ),
+        (
+            "{\n  // line\n  \"k\" /* block */ : [1, 2,],\n  \"é\\u00e9\\ud83d\\ude00\": {},\n}",
+            r#"{"k":[1,2],"é\u00e9\ud83d\ude00":{}}"#,
+        ),
+    ] {
+        let output = minify(text)?;
+        assert_eq!(output, expected);
+        let (original, _) = RawJson::parse_jsonc(text)?;
+        assert!(nojson::json_eq(&original.value().to_string(), &output)?);
+    }
+
+    // Syntax errors are reported in the same way as `RawJson::parse_jsonc()`.
+    for text in [
+        "",
+        "[1, 2",
+        "[1 2]",
+        "{\"a\" 1}",
+        "{1: 2}",
+        "nul",
+        "-",
+        "01",
+        "\"\\x\"",
+        "\"\\ud800\\u0041\"",
+        "\"a\nb\"",
+        "[é]",
+        "/* comment",
+        "[1] x",
+    ] {
+        let expected = RawJson::parse_jsonc(text).expect_err("syntax error");
+        let e = minify(text).expect_err("syntax error");
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+        let e = e
+            .into_inner()
+            .and_then(|e| e.downcast::<JsonParseError>().ok())
+            .expect("JSON error");
+        assert_eq!(e.to_string(), expected.to_string(), "{text:?}");
+    }
+
+    // Invalid UTF-8 in strings is rejected.
+    let e = nojson::minify_stream(&b"\"\xff\""[..], &mut Vec::new()).expect_err("invalid UTF-8");
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+
+    Ok(())
+}
+
+#[test]
+fn validate_syntax_only() {
+    for text in [
+        "null",
+        r#"{"a": [1, 2.5e3, "x\n", {"b": null}], "c": true}"#,
+        r#""😀""#,
+        "",
+        "[1, 2",
+        r#"{"a" 1}"#,
+        "[1] 2",
+        "01",
+    ] {
+        let expected = RawJson::parse(text).map(|_| ()).map_err(|e| e.to_string());
+        let actual = RawJson::validate(text).map_err(|e| e.to_string());
+        assert_eq!(actual, expected, "{text:?}");
+        let actual = RawJson::validate_bytes(text.as_bytes()).map_err(|e| e.to_string());
+        assert_eq!(actual, expected, "{text:?}");
+    }
+
+    // Syntax errors before an invalid UTF-8 sequence take precedence.
+    let e = RawJson::validate_bytes(b"[1 2, \"\xff\"]").expect_err("syntax error");
+    assert_eq!(e.expected(), Some("`,` or `]`"));
+
+    let e = RawJson::validate_bytes(b"[\"\xff\"]").expect_err("invalid UTF-8");
+    assert!(matches!(
+        e,
+        JsonParseError::UnexpectedValueChar {
+            kind: Some(JsonValueKind::String),
+            position: 2,
+            end: 3,
+            expected: "valid UTF-8",
+        }
+    ));
+
+    let e = RawJson::validate_bytes(b"\"\xe3\x81").expect_err("truncated UTF-8");
+    assert!(matches!(
+        e,
+        JsonParseError::UnexpectedEos {
+            position: 3,
+            end: 3,
+            ..
+        }
+    ));
+}